@@ -0,0 +1,55 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use html_parser::{Attribute, Tag};
+
+fn tag_with(count: usize) -> Tag {
+    let mut tag = Tag::new_start_tag_with_name("div".to_string());
+    tag.attributes = (0..count)
+        .map(|i| Attribute {
+            name: format!("data-attribute-{}", i),
+            value: i.to_string(),
+            ..Attribute::new()
+        })
+        .collect();
+    tag
+}
+
+// Looks up the first and last attributes and one that isn't there, as code that reads a few
+// attributes off each tag would.
+fn attributes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("attributes");
+
+    for count in [1, 5, 10, 20] {
+        let tag = tag_with(count);
+        let last = format!("data-attribute-{}", count - 1);
+        let names = ["data-attribute-0", last.as_str(), "missing"];
+
+        group.bench_with_input(BenchmarkId::new("find_attribute", count), &tag, |b, tag| {
+            b.iter(|| {
+                names
+                    .iter()
+                    .filter_map(|name| tag.find_attribute(black_box(name)))
+                    .count()
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("attributes_as_map", count),
+            &tag,
+            |b, tag| {
+                b.iter(|| {
+                    let map = tag.attributes_as_map();
+                    names
+                        .iter()
+                        .filter_map(|name| map.get(black_box(name)))
+                        .count()
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, attributes);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use html_parser::{scan_head, Tokenizer};
+
+// A news article: a head full of metadata followed by about 100 KB of body.
+fn article() -> String {
+    let head = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <meta name="viewport" content="width=device-width, initial-scale=1">
+  <title>City council approves new transit plan | Example News</title>
+  <meta name="description" content="The plan adds three bus lines and extends the tram.">
+  <meta property="og:title" content="City council approves new transit plan">
+  <meta property="og:image" content="https://example.com/images/transit.jpg">
+  <link rel="canonical" href="https://example.com/news/transit-plan">
+  <link rel="stylesheet" href="/assets/site.css">
+  <link rel="stylesheet" href="/assets/article.css">
+  <script src="/assets/analytics.js" async></script>
+  <script src="/assets/site.js" defer></script>
+</head>
+"#;
+    let paragraph = r#"<p>The council voted <strong>7 to 2</strong> on Tuesday to approve the plan,
+which <a href="/news/transit-history">has been debated</a> for more than two years. Supporters
+say the new lines will cut commute times &mdash; critics question the cost.</p>
+"#;
+
+    format!(
+        "{}<body><article><h1>City council approves new transit plan</h1>\n{}</article></body></html>",
+        head,
+        paragraph.repeat(100 * 1024 / paragraph.len())
+    )
+}
+
+fn head(c: &mut Criterion) {
+    let html = article();
+    let mut group = c.benchmark_group("head");
+    group.throughput(Throughput::Bytes(html.len() as u64));
+
+    group.bench_function("scan_head", |b| b.iter(|| scan_head(black_box(&html))));
+    group.bench_function("full_tokenization", |b| {
+        b.iter(|| Tokenizer::new(black_box(&html)).count())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, head);
+criterion_main!(benches);
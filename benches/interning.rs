@@ -0,0 +1,49 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use html_parser::{Tokenizer, TokenizerOptions};
+
+mod common;
+
+use common::{allocated, CountingAllocator};
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// About 100 KB of the same few tags over and over.
+fn document() -> String {
+    let mut html = String::from("<!DOCTYPE html><html><body>");
+    while html.len() < 100 * 1024 {
+        html.push_str(
+            r#"<div class="item"><p>Some <span>text</span> and <a href="/x">a link</a>.</p><ul><li>one</li><li>two</li></ul></div>"#,
+        );
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+fn interning(c: &mut Criterion) {
+    let html = document();
+    let mut group = c.benchmark_group("interning");
+    group.throughput(Throughput::Bytes(html.len() as u64));
+
+    for (name, intern) in [("owned", false), ("interned", true)] {
+        let options = TokenizerOptions::new().intern_tag_names(intern);
+
+        let before = allocated();
+        let mut tokens = Tokenizer::with_options(&html, options.clone()).collect::<Vec<_>>();
+        tokens.shrink_to_fit();
+        let held = allocated().saturating_sub(before);
+        println!("{}: {} tokens hold {} bytes", name, tokens.len(), held);
+        drop(tokens);
+
+        group.bench_function(name, |b| {
+            b.iter(|| Tokenizer::with_options(black_box(&html), options.clone()).count())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, interning);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use html_parser::Tokenizer;
+
+// A user-submitted comment of about 1 KB.
+fn comment() -> String {
+    let paragraph = r#"<p>Thanks for the write-up! I tried the <a href="https://example.com/guide">steps in
+the guide</a> and <em>mostly</em> got it working &mdash; the second part still fails for me.</p>
+"#;
+
+    paragraph.repeat(1024 / paragraph.len() + 1)
+}
+
+fn reset(c: &mut Criterion) {
+    let html = comment();
+    let mut group = c.benchmark_group("reset");
+    group.throughput(Throughput::Bytes(html.len() as u64));
+
+    group.bench_function("new_tokenizer", |b| {
+        b.iter(|| Tokenizer::new(black_box(&html)).count())
+    });
+    group.bench_function("reset_tokenizer", |b| {
+        let mut tokenizer = Tokenizer::new("");
+        b.iter(|| {
+            tokenizer.reset(black_box(&html));
+            tokenizer.by_ref().count()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, reset);
+criterion_main!(benches);
@@ -0,0 +1,74 @@
+use std::{fs, hint::black_box};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use html_parser::Tokenizer;
+
+mod common;
+
+use common::{peak_during, CountingAllocator};
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// The document from `test_basic_html`.
+const BASIC_HTML: &str =
+    "<!DOCTYPE html><html><head><title>Test</title></head><body><h1>Hello World</h1></body></html>";
+
+// The realistic page fixture repeated up to about 50 KB.
+fn page() -> String {
+    let page = fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/page.html"
+    ))
+    .expect("failed to read the page fixture");
+
+    page.repeat((50 * 1024) / page.len() + 1)
+}
+
+fn character_references() -> String {
+    (0..5000)
+        .map(|i| format!("&#{};&#x{:X};", 0x20 + i % 0x5F, 0x3B1 + i % 25))
+        .collect()
+}
+
+fn nested_elements() -> String {
+    let depth = 2000;
+    format!(
+        "{}text{}",
+        r#"<div class="level">"#.repeat(depth),
+        "</div>".repeat(depth)
+    )
+}
+
+// Script data with `<!--` and `<script>` inside, which go through the escaped states.
+fn scripts() -> String {
+    r#"<script><!-- if (a < b && c > d) { document.write("<script>x()</script>"); } --></script>"#
+        .repeat(500)
+}
+
+fn tokenizer(c: &mut Criterion) {
+    let inputs = [
+        ("basic", BASIC_HTML.to_string()),
+        ("page", page()),
+        ("character_references", character_references()),
+        ("nested_elements", nested_elements()),
+        ("scripts", scripts()),
+    ];
+
+    let mut group = c.benchmark_group("tokenizer");
+
+    for (name, html) in &inputs {
+        let peak = peak_during(|| Tokenizer::new(html).count());
+        println!("{}: {} bytes, peak heap {} bytes", name, html.len(), peak);
+
+        group.throughput(Throughput::Bytes(html.len() as u64));
+        group.bench_function(*name, |b| {
+            b.iter(|| Tokenizer::new(black_box(html)).count())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, tokenizer);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+use std::{fs, hint::black_box};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use html_parser::{Token, TokenWriter, Tokenizer, WriterOptions};
+
+// At least 100,000 tokens from the realistic page fixture.
+fn tokens() -> Vec<Token> {
+    let page = fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/page.html"
+    ))
+    .expect("failed to read the page fixture");
+    let tokens: Vec<Token> = Tokenizer::new(&page).collect();
+
+    tokens.iter().cycle().take(100_000).cloned().collect()
+}
+
+fn writer(c: &mut Criterion) {
+    let tokens = tokens();
+    let mut group = c.benchmark_group("writer");
+
+    group.bench_function("token_writer", |b| {
+        b.iter(|| {
+            let mut writer = TokenWriter::new(WriterOptions::default());
+            for token in black_box(&tokens) {
+                writer.write_token(token);
+            }
+            writer.finish()
+        })
+    });
+    group.bench_function("to_string_join", |b| {
+        b.iter(|| {
+            black_box(&tokens)
+                .iter()
+                .map(Token::to_string)
+                .collect::<Vec<_>>()
+                .join("")
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, writer);
+criterion_main!(benches);
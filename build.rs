@@ -0,0 +1,71 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    generate_entities(Path::new(&out_dir));
+    generate_elements(Path::new(&out_dir));
+}
+
+// Generates the named character reference table as a compile-time perfect hash map.
+fn generate_entities(out_dir: &Path) {
+    let source = "data/entities.tsv";
+    println!("cargo:rerun-if-changed={}", source);
+
+    let entities = fs::read_to_string(source).expect("failed to read the entity table");
+    let mut map = phf_codegen::Map::new();
+
+    for (name, value) in entries(&entities) {
+        map.entry(name, &format!("\"{}\"", value));
+    }
+
+    fs::write(
+        out_dir.join("entities.rs"),
+        format!(
+            "static ENTITIES: phf::Map<&'static str, &'static str> = {};\n",
+            map.build()
+        ),
+    )
+    .expect("failed to write the entity table");
+}
+
+// Generates a compile-time perfect hash set of element names for each kind of element.
+fn generate_elements(out_dir: &Path) {
+    let source = "data/elements.tsv";
+    println!("cargo:rerun-if-changed={}", source);
+
+    let elements = fs::read_to_string(source).expect("failed to read the element table");
+    let mut code = String::new();
+
+    for (kind, set_name) in [
+        ("void", "VOID_ELEMENTS"),
+        ("raw_text", "RAW_TEXT_ELEMENTS"),
+        ("rcdata", "RCDATA_ELEMENTS"),
+    ] {
+        let mut set = phf_codegen::Set::new();
+        for (name, _) in entries(&elements).filter(|(_, entry_kind)| *entry_kind == kind) {
+            set.entry(name);
+        }
+
+        code.push_str(&format!(
+            "pub(crate) static {}: phf::Set<&'static str> = {};\n",
+            set_name,
+            set.build()
+        ));
+    }
+
+    fs::write(out_dir.join("elements.rs"), code).expect("failed to write the element table");
+}
+
+// The tab-separated pairs in a data file, skipping blank lines and `#` comments.
+fn entries(table: &str) -> impl Iterator<Item = (&str, &str)> {
+    table
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.split_once('\t')
+                .unwrap_or_else(|| panic!("malformed line: {:?}", line))
+        })
+}
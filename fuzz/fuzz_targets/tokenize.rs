@@ -0,0 +1,11 @@
+#![no_main]
+
+use html_parser::Tokenizer;
+use libfuzzer_sys::fuzz_target;
+
+// Tokenizing must never panic or hang, whatever the input.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = Tokenizer::new(s).collect::<Vec<_>>();
+    }
+});
@@ -0,0 +1,136 @@
+use html_parser::{Attribute, Doctype, Tag, Token, Tokenizer};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+/// Tokenizes a string literal at compile time and expands to the `Vec<Token>` that
+/// `Tokenizer::new(..).collect()` would return for it, `Token::EOF` included.
+///
+/// Input with parse errors is rejected:
+///
+/// ```compile_fail
+/// let tokens = html_parser_macros::html!("<div></div foo>");
+/// ```
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as LitStr), true)
+}
+
+/// Like `html!`, but leaves out the text, for tests that only care about the structure.
+#[proc_macro]
+pub fn html_tags(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as LitStr), false)
+}
+
+fn expand(html: LitStr, keep_text: bool) -> TokenStream {
+    let source = html.value();
+    let mut tokenizer = Tokenizer::new(&source);
+    let tokens = tokenizer.by_ref().collect::<Vec<_>>();
+
+    if let Some(error) = tokenizer.errors().first() {
+        return syn::Error::new(html.span(), format!("malformed HTML: {}", error))
+            .to_compile_error()
+            .into();
+    }
+
+    let tokens = tokens
+        .iter()
+        .filter(|token| keep_text || !matches!(token, Token::Char(_) | Token::Text(_)))
+        .map(token_expr);
+
+    quote!(::std::vec![#(#tokens),*]).into()
+}
+
+fn token_expr(token: &Token) -> TokenStream2 {
+    match token {
+        Token::DOCTYPE(doctype) => {
+            let doctype = doctype_expr(doctype);
+            quote!(::html_parser::Token::DOCTYPE(#doctype))
+        }
+        Token::Tag(tag) => {
+            let tag = tag_expr(tag);
+            quote!(::html_parser::Token::Tag(#tag))
+        }
+        Token::Comment(comment) => quote!(::html_parser::Token::Comment(#comment.to_string())),
+        Token::Char(c) => quote!(::html_parser::Token::Char(#c)),
+        Token::Text(text) => quote!(::html_parser::Token::Text(#text.to_string())),
+        Token::CDATASection(data) => {
+            quote!(::html_parser::Token::CDATASection(#data.to_string()))
+        }
+        Token::ProcessingInstruction { target, data } => {
+            quote!(::html_parser::Token::ProcessingInstruction {
+                target: #target.to_string(),
+                data: #data.to_string(),
+            })
+        }
+        Token::Template(content) => {
+            let content = content.iter().map(token_expr);
+            quote!(::html_parser::Token::Template(::std::vec![#(#content),*]))
+        }
+        Token::EOF => quote!(::html_parser::Token::EOF),
+    }
+}
+
+// The structs are destructured in full so that a new field breaks the build here instead of
+// being left out of the expansion.
+fn doctype_expr(doctype: &Doctype) -> TokenStream2 {
+    let Doctype {
+        name,
+        public_id,
+        system_id,
+        force_quirks,
+    } = doctype;
+    let public_id = option_expr(public_id);
+    let system_id = option_expr(system_id);
+
+    quote!(::html_parser::Doctype {
+        name: #name.to_string(),
+        public_id: #public_id,
+        system_id: #system_id,
+        force_quirks: #force_quirks,
+    })
+}
+
+fn tag_expr(tag: &Tag) -> TokenStream2 {
+    let Tag {
+        tag_name,
+        self_closing,
+        attributes,
+        is_end_tag,
+        self_closing_acknowledged,
+    } = tag;
+    let tag_name = tag_name.as_str();
+    let attributes = attributes.iter().map(attribute_expr);
+
+    quote!(::html_parser::Tag {
+        tag_name: ::html_parser::TagName::from(#tag_name),
+        self_closing: #self_closing,
+        attributes: ::std::vec![#(#attributes),*],
+        is_end_tag: #is_end_tag,
+        self_closing_acknowledged: #self_closing_acknowledged,
+    })
+}
+
+fn attribute_expr(attribute: &Attribute) -> TokenStream2 {
+    let Attribute {
+        name,
+        value,
+        prefix,
+        namespace,
+    } = attribute;
+
+    quote!(::html_parser::Attribute {
+        name: #name.to_string(),
+        value: #value.to_string(),
+        prefix: #prefix.to_string(),
+        namespace: #namespace.to_string(),
+    })
+}
+
+fn option_expr(value: &Option<String>) -> TokenStream2 {
+    match value {
+        Some(value) => quote!(::std::option::Option::Some(#value.to_string())),
+        None => quote!(::std::option::Option::None),
+    }
+}
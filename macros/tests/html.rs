@@ -0,0 +1,52 @@
+use html_parser::{Attribute, Tag, Token, Tokenizer};
+use html_parser_macros::{html, html_tags};
+
+fn tokenize(html: &str) -> Vec<Token> {
+    Tokenizer::new(html).collect()
+}
+
+#[test]
+fn test_matches_tokenizer() {
+    assert_eq!(
+        html!("<!DOCTYPE html><div class='foo'>text &amp; more</div><!-- x --><br/>"),
+        tokenize("<!DOCTYPE html><div class='foo'>text &amp; more</div><!-- x --><br/>")
+    );
+    assert_eq!(
+        html!(r#"<svg viewBox="0 0 1 1"><circle r=1 /></svg>"#),
+        tokenize(r#"<svg viewBox="0 0 1 1"><circle r=1 /></svg>"#)
+    );
+    assert_eq!(html!(""), [Token::EOF]);
+}
+
+#[test]
+fn test_replaces_hand_written_tokens() {
+    let mut div = Tag::new_start_tag_with_name("div".to_string());
+    div.attributes.push(Attribute {
+        name: "class".to_string(),
+        value: "foo".to_string(),
+        ..Attribute::new()
+    });
+
+    assert_eq!(
+        html!("<div class='foo'>text</div>"),
+        [
+            Token::Tag(div),
+            Token::Text("text".to_string()),
+            Token::Tag(Tag::new_end_tag_with_name("div".to_string())),
+            Token::EOF,
+        ]
+    );
+}
+
+#[test]
+fn test_html_tags() {
+    let tags = html_tags!("<ul><li>a</li><li>b <b>c</b></li></ul>");
+
+    assert!(tags
+        .iter()
+        .all(|token| token.as_tag().is_some() || token.is_eof()));
+    assert_eq!(
+        tags.iter().filter_map(Token::tag_name).collect::<Vec<_>>(),
+        ["ul", "li", "li", "li", "b", "b", "li", "ul"]
+    );
+}
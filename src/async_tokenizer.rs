@@ -0,0 +1,82 @@
+use std::{collections::VecDeque, io};
+
+use futures_core::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::tokenizer::{Decoder, PushTokenizer, Token, CHUNK_SIZE};
+
+// The async counterpart of `ReadTokenizer`: reads another chunk from a `tokio` reader only
+// once every token from the previous ones has been handed out, and decodes the bytes the same
+// way.
+//
+// A read error ends the input as if the reader had reached EOF; it can be inspected with
+// `error` afterwards.
+pub struct AsyncTokenizer<R> {
+    reader: R,
+    tokenizer: PushTokenizer,
+    tokens: VecDeque<Token>,
+
+    chunk: Vec<u8>,
+    decoder: Decoder,
+
+    finished: bool,
+    error: Option<io::Error>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncTokenizer<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            tokenizer: PushTokenizer::new(),
+            tokens: VecDeque::new(),
+            chunk: vec![0; CHUNK_SIZE],
+            decoder: Decoder::default(),
+            finished: false,
+            error: None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    pub async fn next_token(&mut self) -> Option<Token> {
+        while self.tokens.is_empty() && !self.finished {
+            self.read_chunk().await;
+        }
+
+        self.tokens.pop_front()
+    }
+
+    pub fn into_stream(mut self) -> impl Stream<Item = Token> {
+        async_stream::stream! {
+            while let Some(token) = self.next_token().await {
+                yield token;
+            }
+        }
+    }
+
+    async fn read_chunk(&mut self) {
+        match self.reader.read(&mut self.chunk).await {
+            Ok(0) => self.finish(),
+            Ok(len) => {
+                let text = self.decoder.decode(&self.chunk[..len]);
+                self.tokenizer.push(&text);
+                self.tokens.extend(self.tokenizer.drain_tokens());
+            }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => {
+                self.error = Some(error);
+                self.finish();
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        let text = self.decoder.finish();
+        self.tokenizer.push(&text);
+
+        self.tokens.extend(self.tokenizer.finish());
+        self.finished = true;
+    }
+}
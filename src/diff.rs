@@ -0,0 +1,223 @@
+use crate::tokenizer::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TokenDiff {
+    Same(Token),
+    Added(Token),
+    Removed(Token),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+// The edits that turn `old` into `new`, keeping their longest common subsequence unchanged.
+// Tokens are compared with `==`; spans live in `SpannedToken`, so positions never make two
+// tokens differ.
+pub fn diff_token_streams(old: &[Token], new: &[Token]) -> Vec<TokenDiff> {
+    // lengths[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(TokenDiff::Same(old[i].clone()));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            diff.push(TokenDiff::Removed(old[i].clone()));
+            i += 1;
+        } else {
+            diff.push(TokenDiff::Added(new[j].clone()));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().cloned().map(TokenDiff::Removed));
+    diff.extend(new[j..].iter().cloned().map(TokenDiff::Added));
+
+    diff
+}
+
+// Applies `diff` to `base`, which should be the `old` tokens it was made from. Tokens of
+// `base` past the end of the diff are kept.
+pub fn apply_diff(base: Vec<Token>, diff: &[TokenDiff]) -> Vec<Token> {
+    let mut base = base.into_iter();
+    let mut patched = Vec::with_capacity(diff.len());
+
+    for change in diff {
+        match change {
+            TokenDiff::Same(_) => patched.extend(base.next()),
+            TokenDiff::Added(token) => patched.push(token.clone()),
+            TokenDiff::Removed(_) => {
+                base.next();
+            }
+        }
+    }
+    patched.extend(base);
+
+    patched
+}
+
+// Rewrites tokens into a form where markup that means the same compares equal, so diffs only
+// show real changes. Tag and attribute names are lowercased, attributes sorted by name,
+// character data merged into single `Text` tokens and whitespace-only text collapsed to a
+// single space. Tokens don't record how attribute values were quoted, so serializing the
+// result quotes every value the same way.
+pub fn canonicalize_token_stream(tokens: Vec<Token>) -> Vec<Token> {
+    let mut canonical: Vec<Token> = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        let token = match token {
+            Token::Tag(mut tag) => {
+                if tag.tag_name.chars().any(|c| c.is_ascii_uppercase()) {
+                    tag.rename(&tag.tag_name.to_ascii_lowercase());
+                }
+                for attr in &mut tag.attributes {
+                    attr.name.make_ascii_lowercase();
+                }
+                tag.sort_attributes_alphabetically();
+                Token::Tag(tag)
+            }
+            Token::Char(c) => Token::Text(c.to_string()),
+            token => token,
+        };
+
+        match (canonical.last_mut(), token) {
+            (Some(Token::Text(text)), Token::Text(more)) => text.push_str(&more),
+            (_, token) => canonical.push(token),
+        }
+    }
+
+    for token in &mut canonical {
+        if let Token::Text(text) = token {
+            if text.chars().all(|c| c.is_ascii_whitespace()) {
+                *text = String::from(" ");
+            }
+        }
+    }
+
+    canonical
+}
+
+pub fn diff_summary(diff: &[TokenDiff]) -> DiffSummary {
+    let mut summary = DiffSummary::default();
+
+    for change in diff {
+        match change {
+            TokenDiff::Same(_) => summary.unchanged += 1,
+            TokenDiff::Added(_) => summary.added += 1,
+            TokenDiff::Removed(_) => summary.removed += 1,
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::{Tag, Tokenizer};
+
+    fn tokens(html: &str) -> Vec<Token> {
+        Tokenizer::new(html).collect()
+    }
+
+    #[test]
+    fn test_identical_documents() {
+        let page = tokens("<!DOCTYPE html><ul><li>a</li><li>b</li></ul><!-- end -->");
+        let diff = diff_token_streams(&page, &page);
+
+        assert!(diff
+            .iter()
+            .all(|change| matches!(change, TokenDiff::Same(_))));
+        assert_eq!(
+            diff_summary(&diff),
+            DiffSummary {
+                added: 0,
+                removed: 0,
+                unchanged: page.len(),
+            }
+        );
+        assert_eq!(diff_token_streams(&[], &[]), []);
+    }
+
+    #[test]
+    fn test_changes() {
+        let old = tokens("<p>a</p><p>b</p>");
+        let new = tokens("<p>a</p><hr/><p>c</p>");
+        let diff = diff_token_streams(&old, &new);
+
+        let changes = diff
+            .iter()
+            .filter(|change| !matches!(change, TokenDiff::Same(_)))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut hr = Tag::new_start_tag_with_name("hr".to_string());
+        hr.self_closing = true;
+
+        assert_eq!(
+            changes,
+            [
+                TokenDiff::Added(Token::Tag(hr)),
+                TokenDiff::Removed(Token::Text("b".to_string())),
+                TokenDiff::Added(Token::Text("c".to_string())),
+            ]
+        );
+        assert_eq!(
+            diff_summary(&diff),
+            DiffSummary {
+                added: 2,
+                removed: 1,
+                unchanged: 6,
+            }
+        );
+        assert_eq!(apply_diff(old, &diff), new);
+    }
+
+    #[test]
+    fn test_canonicalize_token_stream() {
+        let canonical = |html: &str| canonicalize_token_stream(tokens(html));
+
+        let old = canonical("<div id=main class='a b'>\n  <a title=\"x\" href=/>Home</a>\n</div>");
+        let new =
+            canonical("<DIV Class=\"a b\" ID=\"main\"> <a href=\"/\" title=x>Home</a>\t</div>");
+        assert_eq!(old, new);
+        assert!(diff_token_streams(&old, &new)
+            .iter()
+            .all(|change| matches!(change, TokenDiff::Same(_))));
+
+        // Only whitespace-only text is collapsed.
+        assert_ne!(canonical("<p>a  b</p>"), canonical("<p>a b</p>"));
+        assert_eq!(
+            canonicalize_token_stream(vec![Token::Char('a'), Token::Text("b".to_string())]),
+            [Token::Text("ab".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_all_added_or_removed() {
+        let page = tokens("<b>x</b>");
+
+        let diff = diff_token_streams(&[], &page);
+        assert_eq!(apply_diff(Vec::new(), &diff), page);
+        assert_eq!(diff_summary(&diff).added, page.len());
+
+        let diff = diff_token_streams(&page, &[]);
+        assert_eq!(apply_diff(page.clone(), &diff), []);
+        assert_eq!(diff_summary(&diff).removed, page.len());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::namespace::Namespace;
+use crate::node::Node;
+use crate::selector::SimpleSelector;
+use crate::serializer::{serialize_doctype, serialize_node};
+use crate::tokenizer::{Doctype, Token, Tokenizer};
+use crate::tree_builder::TreeBuilder;
+
+// The result of tree construction: the DOCTYPE and the `<html>` element, which holds the
+// `<head>` and `<body>`.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Document {
+    pub doctype: Option<Doctype>,
+    pub root: Option<Node>,
+}
+
+impl Document {
+    pub fn from_tokens(tokens: Vec<Token>) -> Self {
+        let mut builder = TreeBuilder::new();
+        for token in tokens {
+            builder.process(token);
+        }
+        builder.finish()
+    }
+
+    // Parsing can't fail, so unlike `FromStr::from_str` this returns the document itself.
+    // The tokenizer is kept informed of SVG and MathML content, where attribute names such as
+    // `xlink:href` are split.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(html: &str) -> Self {
+        let mut tokenizer = Tokenizer::new(html);
+        let mut builder = TreeBuilder::new();
+        while let Some(token) = tokenizer.next() {
+            builder.process(token);
+            tokenizer.set_in_foreign_content(builder.current_namespace() != Namespace::Html);
+        }
+        builder.finish()
+    }
+
+    // https://html.spec.whatwg.org/#serialising-html-fragments
+    pub fn serialize(&self) -> String {
+        let mut html = String::new();
+
+        if let Some(doctype) = &self.doctype {
+            serialize_doctype(&mut html, doctype);
+        }
+        if let Some(root) = &self.root {
+            serialize_node(&mut html, root, None);
+        }
+
+        html
+    }
+
+    pub fn head(&self) -> Option<&Node> {
+        self.root_child("head")
+    }
+
+    pub fn body(&self) -> Option<&Node> {
+        self.root_child("body")
+    }
+
+    // The text of the first `<title>`, with leading and trailing whitespace removed and other
+    // runs of whitespace collapsed to a single space.
+    // https://html.spec.whatwg.org/#document.title
+    pub fn title(&self) -> Option<String> {
+        let title = self.element_by_tag("title")?;
+
+        Some(
+            title
+                .text_content()
+                .split_ascii_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    // The `lang` attribute of the `<html>` element.
+    pub fn lang(&self) -> Option<&str> {
+        self.root.as_ref()?.get_attribute("lang")
+    }
+
+    // The first element, in document order, whose `id` is `id`.
+    // https://dom.spec.whatwg.org/#dom-nonelementparentnode-getelementbyid
+    pub fn find_by_id(&self, id: &str) -> Option<&Node> {
+        self.root.as_ref()?.find_by_id(id)
+    }
+
+    // The first element, in document order, matching a selector `SimpleSelector` can parse,
+    // e.g. `div.note`. Selectors it can't parse match nothing.
+    // https://dom.spec.whatwg.org/#dom-parentnode-queryselector
+    pub fn query_selector(&self, selector: &str) -> Option<&Node> {
+        let selector = SimpleSelector::parse(selector)?;
+        self.root
+            .as_ref()?
+            .find(&|node| selector.matches_node(node))
+    }
+
+    // Every element matching the selector, in document order.
+    pub fn query_selector_all(&self, selector: &str) -> Vec<&Node> {
+        let (Some(selector), Some(root)) = (SimpleSelector::parse(selector), &self.root) else {
+            return Vec::new();
+        };
+        root.find_all(&|node| selector.matches_node(node))
+    }
+
+    // The first element with the given tag name, compared ASCII case-insensitively.
+    pub fn element_by_tag(&self, name: &str) -> Option<&Node> {
+        self.root.as_ref()?.find(&|node| {
+            node.tag_name()
+                .is_some_and(|tag_name| tag_name.eq_ignore_ascii_case(name))
+        })
+    }
+
+    fn root_child(&self, name: &str) -> Option<&Node> {
+        self.root
+            .as_ref()?
+            .children()
+            .iter()
+            .find(|child| child.tag_name() == Some(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accessors() {
+        let document = Document::from_str(
+            "<html lang=fr><head><title>\n  Bonjour   le\tmonde </title></head><body>Salut",
+        );
+
+        assert_eq!(document.title().as_deref(), Some("Bonjour le monde"));
+        assert_eq!(document.lang(), Some("fr"));
+        assert_eq!(document.head().and_then(Node::tag_name), Some("head"));
+        assert_eq!(
+            document.body().map(Node::text_content).as_deref(),
+            Some("Salut")
+        );
+
+        let document = Document::from_tokens(Vec::new());
+        assert_eq!(document, Document::default());
+        assert_eq!(document.title(), None);
+        assert_eq!(document.body(), None);
+    }
+
+    #[test]
+    fn test_queries() {
+        let document = Document::from_str(
+            r#"<div id="header" class="bar top"><h1>Site</h1></div>
+            <div id="content">
+                <div class="post" data-id="1"><p id="first">One</p></div>
+                <div class="post featured" data-id="2"><p>Two</p></div>
+            </div>
+            <div id="footer" class="bar">Bye</div>"#,
+        );
+        let text = |node: Option<&Node>| node.map(Node::text_content);
+
+        assert_eq!(text(document.find_by_id("first")).as_deref(), Some("One"));
+        assert_eq!(
+            document
+                .find_by_id("footer")
+                .and_then(|node| node.get_attribute("class")),
+            Some("bar")
+        );
+        assert_eq!(document.find_by_id("missing"), None);
+
+        let post = document.query_selector(".post");
+        assert_eq!(
+            post.and_then(|node| node.get_attribute("data-id")),
+            Some("1")
+        );
+        assert_eq!(document.query_selector("[data-id='1']"), post);
+        assert_eq!(
+            text(document.query_selector("div.post.featured")).as_deref(),
+            Some("Two")
+        );
+        assert_eq!(
+            document
+                .query_selector_all("div.bar")
+                .into_iter()
+                .filter_map(|node| node.get_attribute("id"))
+                .collect::<Vec<_>>(),
+            ["header", "footer"]
+        );
+        assert_eq!(document.query_selector_all("div").len(), 5);
+        assert!(document.query_selector_all("div > p").is_empty());
+        assert_eq!(document.query_selector("span"), None);
+
+        assert_eq!(text(document.element_by_tag("H1")).as_deref(), Some("Site"));
+        assert_eq!(document.element_by_tag("body"), document.body());
+        assert_eq!(Document::default().query_selector("div"), None);
+    }
+
+    #[test]
+    fn test_foreign_content() {
+        let document = Document::from_str(
+            r##"<svg><style><a>x</a></style><use xlink:href="#icon"/><foreignObject><style>p {}</style></foreignObject></svg>"##,
+        );
+        let svg = document.element_by_tag("svg").unwrap();
+
+        // Unlike in HTML, `<style>` holds elements rather than text.
+        assert_eq!(svg.children()[0].children()[0].tag_name(), Some("a"));
+        let href = &svg.children()[1].attributes()[0];
+        assert_eq!(
+            (href.prefix.as_str(), href.name.as_str()),
+            ("xlink", "href")
+        );
+        assert_eq!(
+            svg.children()[2].children()[0].namespace(),
+            Some(&Namespace::Html)
+        );
+    }
+
+    #[test]
+    fn test_cdata_sections() {
+        // Only foreign content has CDATA sections; in HTML, `<![CDATA[` starts a bogus comment.
+        let document = Document::from_str("<svg><![CDATA[a<b]]></svg><p><![CDATA[c<d]]></p>");
+
+        assert_eq!(
+            document.element_by_tag("svg").unwrap().text_content(),
+            "a<b"
+        );
+        assert_eq!(document.element_by_tag("p").unwrap().text_content(), "");
+    }
+}
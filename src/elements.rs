@@ -0,0 +1,4 @@
+// `VOID_ELEMENTS`, `RAW_TEXT_ELEMENTS` and `RCDATA_ELEMENTS`, the names of each kind of element
+// in lowercase.
+// Generated by build.rs from data/elements.tsv.
+include!(concat!(env!("OUT_DIR"), "/elements.rs"));
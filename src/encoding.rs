@@ -0,0 +1,171 @@
+use crate::entity::{is_numeric_surrogate, replace_control};
+use crate::extractors::find_meta_charset;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum EncodingConsistencyIssue {
+    // The declared encoding cannot represent a character that appears in the document.
+    DeclarationMismatch {
+        declared: String,
+        character: char,
+        offset: usize,
+    },
+    // A `&str` can never hold a surrogate code point, so they only reach the document as
+    // character references such as `&#xD83D;` (usually half of a mangled UTF-16 pair).
+    SurrogateInInput {
+        offset: usize,
+    },
+    // The input starts with a byte order mark but declares a non UTF-8 encoding.
+    // The BOM always takes precedence over the declaration.
+    BomAndDeclarationConflict {
+        declared: String,
+    },
+}
+
+// https://encoding.spec.whatwg.org/#names-and-labels
+const UTF_8_LABELS: [&str; 6] = [
+    "unicode-1-1-utf-8",
+    "unicode11utf8",
+    "unicode20utf8",
+    "utf-8",
+    "utf8",
+    "x-unicode20utf8",
+];
+
+const WINDOWS_1252_LABELS: [&str; 17] = [
+    "ansi_x3.4-1968",
+    "ascii",
+    "cp1252",
+    "cp819",
+    "csisolatin1",
+    "ibm819",
+    "iso-8859-1",
+    "iso-ir-100",
+    "iso8859-1",
+    "iso88591",
+    "iso_8859-1",
+    "iso_8859-1:1987",
+    "l1",
+    "latin1",
+    "us-ascii",
+    "windows-1252",
+    "x-cp1252",
+];
+
+pub fn check_character_encoding_consistency(html: &str) -> Vec<EncodingConsistencyIssue> {
+    let mut issues = Vec::new();
+
+    if let Some(declared) = find_meta_charset(html) {
+        let label = declared.trim().to_ascii_lowercase();
+
+        if html.starts_with('\u{FEFF}') && !UTF_8_LABELS.contains(&label.as_str()) {
+            issues.push(EncodingConsistencyIssue::BomAndDeclarationConflict {
+                declared: declared.clone(),
+            });
+        }
+
+        if WINDOWS_1252_LABELS.contains(&label.as_str()) {
+            if let Some((offset, character)) = html
+                .char_indices()
+                .find(|(_, c)| *c != '\u{FEFF}' && !is_windows_1252(*c))
+            {
+                issues.push(EncodingConsistencyIssue::DeclarationMismatch {
+                    declared,
+                    character,
+                    offset,
+                });
+            }
+        }
+    }
+
+    for offset in surrogate_character_references(html) {
+        issues.push(EncodingConsistencyIssue::SurrogateInInput { offset });
+    }
+
+    issues
+}
+
+// windows-1252 covers Latin-1, except that 0x80 to 0x9F map to the same characters
+// the tokenizer substitutes for C1 control character references.
+fn is_windows_1252(c: char) -> bool {
+    let code = c as u32;
+
+    code < 0x80
+        || (0xA0..=0xFF).contains(&code)
+        || (0x80..=0x9F).any(|b| replace_control(b) == code)
+}
+
+fn surrogate_character_references(html: &str) -> Vec<usize> {
+    let mut offsets = Vec::new();
+
+    for (offset, _) in html.match_indices("&#") {
+        let rest = &html[offset + 2..];
+
+        let (digits, radix) = match rest.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16),
+            None => (rest, 10),
+        };
+
+        let end = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+
+        if let Ok(code) = u32::from_str_radix(&digits[..end], radix) {
+            if is_numeric_surrogate(code) {
+                offsets.push(offset);
+            }
+        }
+    }
+
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_document() {
+        let html = r#"<!DOCTYPE html><html><head><meta charset="utf-8"></head><body>café ☕</body></html>"#;
+        assert_eq!(check_character_encoding_consistency(html), vec![]);
+    }
+
+    #[test]
+    fn test_declaration_mismatch() {
+        let html = r#"<meta charset="windows-1252"><p>naïve € ☕</p>"#;
+        let offset = html.find('☕').unwrap();
+
+        assert_eq!(
+            check_character_encoding_consistency(html),
+            vec![EncodingConsistencyIssue::DeclarationMismatch {
+                declared: "windows-1252".to_string(),
+                character: '☕',
+                offset,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_surrogate_in_input() {
+        let html = "<p>&#xD83D;&#9731;&#55357;</p>";
+
+        assert_eq!(
+            check_character_encoding_consistency(html),
+            vec![
+                EncodingConsistencyIssue::SurrogateInInput { offset: 3 },
+                EncodingConsistencyIssue::SurrogateInInput { offset: 18 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bom_and_declaration_conflict() {
+        let html = "\u{FEFF}<meta charset=\"iso-8859-1\"><p>plain</p>";
+
+        assert_eq!(
+            check_character_encoding_consistency(html),
+            vec![EncodingConsistencyIssue::BomAndDeclarationConflict {
+                declared: "iso-8859-1".to_string(),
+            }]
+        );
+    }
+}
@@ -1,2249 +1,121 @@
-use std::collections::HashMap;
-
-use lazy_static::lazy_static;
+use std::borrow::Cow;
 
 pub const MAX_NUMBER_OF_CHARACTERS_WITHOUT_SEMICOLON: usize = 6;
 pub const MAX_NUMBER_OF_CHARACTERS_POSSIBLE: usize = 33;
 pub const MIN_NUMBER_OF_CHARACTERS_POSSIBLE: usize = 3;
 
-lazy_static! {
-// htt&ps://html.spec.whatwg.org/#named-character-references
-static ref ENTITIES: HashMap<&'static str, &'static str> = HashMap::from([
-    ("&AElig", "\u{00C6}"),
-    ("&AElig;", "\u{00C6}"),
-    ("&AMP", "\u{0026}"),
-    ("&AMP;", "\u{0026}"),
-    ("&Aacute", "\u{00C1}"),
-    ("&Aacute;", "\u{00C1}"),
-    ("&Abreve;", "\u{0102}"),
-    ("&Acirc", "\u{00C2}"),
-    ("&Acirc;", "\u{00C2}"),
-    ("&Acy;", "\u{0410}"),
-    ("&Afr;", "\u{1D504}"),
-    ("&Agrave", "\u{00C0}"),
-    ("&Agrave;", "\u{00C0}"),
-    ("&Alpha;", "\u{0391}"),
-    ("&Amacr;", "\u{0100}"),
-    ("&And;", "\u{2A53}"),
-    ("&Aogon;", "\u{0104}"),
-    ("&Aopf;", "\u{1D538}"),
-    ("&ApplyFunction;", "\u{2061}"),
-    ("&Aring", "\u{00C5}"),
-    ("&Aring;", "\u{00C5}"),
-    ("&Ascr;", "\u{1D49C}"),
-    ("&Assign;", "\u{2254}"),
-    ("&Atilde", "\u{00C3}"),
-    ("&Atilde;", "\u{00C3}"),
-    ("&Auml", "\u{00C4}"),
-    ("&Auml;", "\u{00C4}"),
-    ("&Backslash;", "\u{2216}"),
-    ("&Barv;", "\u{2AE7}"),
-    ("&Barwed;", "\u{2306}"),
-    ("&Bcy;", "\u{0411}"),
-    ("&Because;", "\u{2235}"),
-    ("&Bernoullis;", "\u{212C}"),
-    ("&Beta;", "\u{0392}"),
-    ("&Bfr;", "\u{1D505}"),
-    ("&Bopf;", "\u{1D539}"),
-    ("&Breve;", "\u{02D8}"),
-    ("&Bscr;", "\u{212C}"),
-    ("&Bumpeq;", "\u{224E}"),
-    ("&CHcy;", "\u{0427}"),
-    ("&COPY", "\u{00A9}"),
-    ("&COPY;", "\u{00A9}"),
-    ("&Cacute;", "\u{0106}"),
-    ("&Cap;", "\u{22D2}"),
-    ("&CapitalDifferentialD;", "\u{2145}"),
-    ("&Cayleys;", "\u{212D}"),
-    ("&Ccaron;", "\u{010C}"),
-    ("&Ccedil", "\u{00C7}"),
-    ("&Ccedil;", "\u{00C7}"),
-    ("&Ccirc;", "\u{0108}"),
-    ("&Cconint;", "\u{2230}"),
-    ("&Cdot;", "\u{010A}"),
-    ("&Cedilla;", "\u{00B8}"),
-    ("&CenterDot;", "\u{00B7}"),
-    ("&Cfr;", "\u{212D}"),
-    ("&Chi;", "\u{03A7}"),
-    ("&CircleDot;", "\u{2299}"),
-    ("&CircleMinus;", "\u{2296}"),
-    ("&CirclePlus;", "\u{2295}"),
-    ("&CircleTimes;", "\u{2297}"),
-    ("&ClockwiseContourIntegral;", "\u{2232}"),
-    ("&CloseCurlyDoubleQuote;", "\u{201D}"),
-    ("&CloseCurlyQuote;", "\u{2019}"),
-    ("&Colon;", "\u{2237}"),
-    ("&Colone;", "\u{2A74}"),
-    ("&Congruent;", "\u{2261}"),
-    ("&Conint;", "\u{222F}"),
-    ("&ContourIntegral;", "\u{222E}"),
-    ("&Copf;", "\u{2102}"),
-    ("&Coproduct;", "\u{2210}"),
-    ("&CounterClockwiseContourIntegral;", "\u{2233}"),
-    ("&Cross;", "\u{2A2F}"),
-    ("&Cscr;", "\u{1D49E}"),
-    ("&Cup;", "\u{22D3}"),
-    ("&CupCap;", "\u{224D}"),
-    ("&DD;", "\u{2145}"),
-    ("&DDotrahd;", "\u{2911}"),
-    ("&DJcy;", "\u{0402}"),
-    ("&DScy;", "\u{0405}"),
-    ("&DZcy;", "\u{040F}"),
-    ("&Dagger;", "\u{2021}"),
-    ("&Darr;", "\u{21A1}"),
-    ("&Dashv;", "\u{2AE4}"),
-    ("&Dcaron;", "\u{010E}"),
-    ("&Dcy;", "\u{0414}"),
-    ("&Del;", "\u{2207}"),
-    ("&Delta;", "\u{0394}"),
-    ("&Dfr;", "\u{1D507}"),
-    ("&DiacriticalAcute;", "\u{00B4}"),
-    ("&DiacriticalDot;", "\u{02D9}"),
-    ("&DiacriticalDoubleAcute;", "\u{02DD}"),
-    ("&DiacriticalGrave;", "\u{0060}"),
-    ("&DiacriticalTilde;", "\u{02DC}"),
-    ("&Diamond;", "\u{22C4}"),
-    ("&DifferentialD;", "\u{2146}"),
-    ("&Dopf;", "\u{1D53B}"),
-    ("&Dot;", "\u{00A8}"),
-    ("&DotDot;", "\u{20DC}"),
-    ("&DotEqual;", "\u{2250}"),
-    ("&DoubleContourIntegral;", "\u{222F}"),
-    ("&DoubleDot;", "\u{00A8}"),
-    ("&DoubleDownArrow;", "\u{21D3}"),
-    ("&DoubleLeftArrow;", "\u{21D0}"),
-    ("&DoubleLeftRightArrow;", "\u{21D4}"),
-    ("&DoubleLeftTee;", "\u{2AE4}"),
-    ("&DoubleLongLeftArrow;", "\u{27F8}"),
-    ("&DoubleLongLeftRightArrow;", "\u{27FA}"),
-    ("&DoubleLongRightArrow;", "\u{27F9}"),
-    ("&DoubleRightArrow;", "\u{21D2}"),
-    ("&DoubleRightTee;", "\u{22A8}"),
-    ("&DoubleUpArrow;", "\u{21D1}"),
-    ("&DoubleUpDownArrow;", "\u{21D5}"),
-    ("&DoubleVerticalBar;", "\u{2225}"),
-    ("&DownArrow;", "\u{2193}"),
-    ("&DownArrowBar;", "\u{2913}"),
-    ("&DownArrowUpArrow;", "\u{21F5}"),
-    ("&DownBreve;", "\u{0311}"),
-    ("&DownLeftRightVector;", "\u{2950}"),
-    ("&DownLeftTeeVector;", "\u{295E}"),
-    ("&DownLeftVector;", "\u{21BD}"),
-    ("&DownLeftVectorBar;", "\u{2956}"),
-    ("&DownRightTeeVector;", "\u{295F}"),
-    ("&DownRightVector;", "\u{21C1}"),
-    ("&DownRightVectorBar;", "\u{2957}"),
-    ("&DownTee;", "\u{22A4}"),
-    ("&DownTeeArrow;", "\u{21A7}"),
-    ("&Downarrow;", "\u{21D3}"),
-    ("&Dscr;", "\u{1D49F}"),
-    ("&Dstrok;", "\u{0110}"),
-    ("&ENG;", "\u{014A}"),
-    ("&ETH", "\u{00D0}"),
-    ("&ETH;", "\u{00D0}"),
-    ("&Eacute", "\u{00C9}"),
-    ("&Eacute;", "\u{00C9}"),
-    ("&Ecaron;", "\u{011A}"),
-    ("&Ecirc", "\u{00CA}"),
-    ("&Ecirc;", "\u{00CA}"),
-    ("&Ecy;", "\u{042D}"),
-    ("&Edot;", "\u{0116}"),
-    ("&Efr;", "\u{1D508}"),
-    ("&Egrave", "\u{00C8}"),
-    ("&Egrave;", "\u{00C8}"),
-    ("&Element;", "\u{2208}"),
-    ("&Emacr;", "\u{0112}"),
-    ("&EmptySmallSquare;", "\u{25FB}"),
-    ("&EmptyVerySmallSquare;", "\u{25AB}"),
-    ("&Eogon;", "\u{0118}"),
-    ("&Eopf;", "\u{1D53C}"),
-    ("&Epsilon;", "\u{0395}"),
-    ("&Equal;", "\u{2A75}"),
-    ("&EqualTilde;", "\u{2242}"),
-    ("&Equilibrium;", "\u{21CC}"),
-    ("&Escr;", "\u{2130}"),
-    ("&Esim;", "\u{2A73}"),
-    ("&Eta;", "\u{0397}"),
-    ("&Euml", "\u{00CB}"),
-    ("&Euml;", "\u{00CB}"),
-    ("&Exists;", "\u{2203}"),
-    ("&ExponentialE;", "\u{2147}"),
-    ("&Fcy;", "\u{0424}"),
-    ("&Ffr;", "\u{1D509}"),
-    ("&FilledSmallSquare;", "\u{25FC}"),
-    ("&FilledVerySmallSquare;", "\u{25AA}"),
-    ("&Fopf;", "\u{1D53D}"),
-    ("&ForAll;", "\u{2200}"),
-    ("&Fouriertrf;", "\u{2131}"),
-    ("&Fscr;", "\u{2131}"),
-    ("&GJcy;", "\u{0403}"),
-    ("&GT", "\u{003E}"),
-    ("&GT;", "\u{003E}"),
-    ("&Gamma;", "\u{0393}"),
-    ("&Gammad;", "\u{03DC}"),
-    ("&Gbreve;", "\u{011E}"),
-    ("&Gcedil;", "\u{0122}"),
-    ("&Gcirc;", "\u{011C}"),
-    ("&Gcy;", "\u{0413}"),
-    ("&Gdot;", "\u{0120}"),
-    ("&Gfr;", "\u{1D50A}"),
-    ("&Gg;", "\u{22D9}"),
-    ("&Gopf;", "\u{1D53E}"),
-    ("&GreaterEqual;", "\u{2265}"),
-    ("&GreaterEqualLess;", "\u{22DB}"),
-    ("&GreaterFullEqual;", "\u{2267}"),
-    ("&GreaterGreater;", "\u{2AA2}"),
-    ("&GreaterLess;", "\u{2277}"),
-    ("&GreaterSlantEqual;", "\u{2A7E}"),
-    ("&GreaterTilde;", "\u{2273}"),
-    ("&Gscr;", "\u{1D4A2}"),
-    ("&Gt;", "\u{226B}"),
-    ("&HARDcy;", "\u{042A}"),
-    ("&Hacek;", "\u{02C7}"),
-    ("&Hat;", "\u{005E}"),
-    ("&Hcirc;", "\u{0124}"),
-    ("&Hfr;", "\u{210C}"),
-    ("&HilbertSpace;", "\u{210B}"),
-    ("&Hopf;", "\u{210D}"),
-    ("&HorizontalLine;", "\u{2500}"),
-    ("&Hscr;", "\u{210B}"),
-    ("&Hstrok;", "\u{0126}"),
-    ("&HumpDownHump;", "\u{224E}"),
-    ("&HumpEqual;", "\u{224F}"),
-    ("&IEcy;", "\u{0415}"),
-    ("&IJlig;", "\u{0132}"),
-    ("&IOcy;", "\u{0401}"),
-    ("&Iacute", "\u{00CD}"),
-    ("&Iacute;", "\u{00CD}"),
-    ("&Icirc", "\u{00CE}"),
-    ("&Icirc;", "\u{00CE}"),
-    ("&Icy;", "\u{0418}"),
-    ("&Idot;", "\u{0130}"),
-    ("&Ifr;", "\u{2111}"),
-    ("&Igrave", "\u{00CC}"),
-    ("&Igrave;", "\u{00CC}"),
-    ("&Im;", "\u{2111}"),
-    ("&Imacr;", "\u{012A}"),
-    ("&ImaginaryI;", "\u{2148}"),
-    ("&Implies;", "\u{21D2}"),
-    ("&Int;", "\u{222C}"),
-    ("&Integral;", "\u{222B}"),
-    ("&Intersection;", "\u{22C2}"),
-    ("&InvisibleComma;", "\u{2063}"),
-    ("&InvisibleTimes;", "\u{2062}"),
-    ("&Iogon;", "\u{012E}"),
-    ("&Iopf;", "\u{1D540}"),
-    ("&Iota;", "\u{0399}"),
-    ("&Iscr;", "\u{2110}"),
-    ("&Itilde;", "\u{0128}"),
-    ("&Iukcy;", "\u{0406}"),
-    ("&Iuml", "\u{00CF}"),
-    ("&Iuml;", "\u{00CF}"),
-    ("&Jcirc;", "\u{0134}"),
-    ("&Jcy;", "\u{0419}"),
-    ("&Jfr;", "\u{1D50D}"),
-    ("&Jopf;", "\u{1D541}"),
-    ("&Jscr;", "\u{1D4A5}"),
-    ("&Jsercy;", "\u{0408}"),
-    ("&Jukcy;", "\u{0404}"),
-    ("&KHcy;", "\u{0425}"),
-    ("&KJcy;", "\u{040C}"),
-    ("&Kappa;", "\u{039A}"),
-    ("&Kcedil;", "\u{0136}"),
-    ("&Kcy;", "\u{041A}"),
-    ("&Kfr;", "\u{1D50E}"),
-    ("&Kopf;", "\u{1D542}"),
-    ("&Kscr;", "\u{1D4A6}"),
-    ("&LJcy;", "\u{0409}"),
-    ("&LT", "\u{003C}"),
-    ("&LT;", "\u{003C}"),
-    ("&Lacute;", "\u{0139}"),
-    ("&Lambda;", "\u{039B}"),
-    ("&Lang;", "\u{27EA}"),
-    ("&Laplacetrf;", "\u{2112}"),
-    ("&Larr;", "\u{219E}"),
-    ("&Lcaron;", "\u{013D}"),
-    ("&Lcedil;", "\u{013B}"),
-    ("&Lcy;", "\u{041B}"),
-    ("&LeftAngleBracket;", "\u{27E8}"),
-    ("&LeftArrow;", "\u{2190}"),
-    ("&LeftArrowBar;", "\u{21E4}"),
-    ("&LeftArrowRightArrow;", "\u{21C6}"),
-    ("&LeftCeiling;", "\u{2308}"),
-    ("&LeftDoubleBracket;", "\u{27E6}"),
-    ("&LeftDownTeeVector;", "\u{2961}"),
-    ("&LeftDownVector;", "\u{21C3}"),
-    ("&LeftDownVectorBar;", "\u{2959}"),
-    ("&LeftFloor;", "\u{230A}"),
-    ("&LeftRightArrow;", "\u{2194}"),
-    ("&LeftRightVector;", "\u{294E}"),
-    ("&LeftTee;", "\u{22A3}"),
-    ("&LeftTeeArrow;", "\u{21A4}"),
-    ("&LeftTeeVector;", "\u{295A}"),
-    ("&LeftTriangle;", "\u{22B2}"),
-    ("&LeftTriangleBar;", "\u{29CF}"),
-    ("&LeftTriangleEqual;", "\u{22B4}"),
-    ("&LeftUpDownVector;", "\u{2951}"),
-    ("&LeftUpTeeVector;", "\u{2960}"),
-    ("&LeftUpVector;", "\u{21BF}"),
-    ("&LeftUpVectorBar;", "\u{2958}"),
-    ("&LeftVector;", "\u{21BC}"),
-    ("&LeftVectorBar;", "\u{2952}"),
-    ("&Leftarrow;", "\u{21D0}"),
-    ("&Leftrightarrow;", "\u{21D4}"),
-    ("&LessEqualGreater;", "\u{22DA}"),
-    ("&LessFullEqual;", "\u{2266}"),
-    ("&LessGreater;", "\u{2276}"),
-    ("&LessLess;", "\u{2AA1}"),
-    ("&LessSlantEqual;", "\u{2A7D}"),
-    ("&LessTilde;", "\u{2272}"),
-    ("&Lfr;", "\u{1D50F}"),
-    ("&Ll;", "\u{22D8}"),
-    ("&Lleftarrow;", "\u{21DA}"),
-    ("&Lmidot;", "\u{013F}"),
-    ("&LongLeftArrow;", "\u{27F5}"),
-    ("&LongLeftRightArrow;", "\u{27F7}"),
-    ("&LongRightArrow;", "\u{27F6}"),
-    ("&Longleftarrow;", "\u{27F8}"),
-    ("&Longleftrightarrow;", "\u{27FA}"),
-    ("&Longrightarrow;", "\u{27F9}"),
-    ("&Lopf;", "\u{1D543}"),
-    ("&LowerLeftArrow;", "\u{2199}"),
-    ("&LowerRightArrow;", "\u{2198}"),
-    ("&Lscr;", "\u{2112}"),
-    ("&Lsh;", "\u{21B0}"),
-    ("&Lstrok;", "\u{0141}"),
-    ("&Lt;", "\u{226A}"),
-    ("&Map;", "\u{2905}"),
-    ("&Mcy;", "\u{041C}"),
-    ("&MediumSpace;", "\u{205F}"),
-    ("&Mellintrf;", "\u{2133}"),
-    ("&Mfr;", "\u{1D510}"),
-    ("&MinusPlus;", "\u{2213}"),
-    ("&Mopf;", "\u{1D544}"),
-    ("&Mscr;", "\u{2133}"),
-    ("&Mu;", "\u{039C}"),
-    ("&NJcy;", "\u{040A}"),
-    ("&Nacute;", "\u{0143}"),
-    ("&Ncaron;", "\u{0147}"),
-    ("&Ncedil;", "\u{0145}"),
-    ("&Ncy;", "\u{041D}"),
-    ("&NegativeMediumSpace;", "\u{200B}"),
-    ("&NegativeThickSpace;", "\u{200B}"),
-    ("&NegativeThinSpace;", "\u{200B}"),
-    ("&NegativeVeryThinSpace;", "\u{200B}"),
-    ("&NestedGreaterGreater;", "\u{226B}"),
-    ("&NestedLessLess;", "\u{226A}"),
-    ("&NewLine;", "\u{000A}"),
-    ("&Nfr;", "\u{1D511}"),
-    ("&NoBreak;", "\u{2060}"),
-    ("&NonBreakingSpace;", "\u{00A0}"),
-    ("&Nopf;", "\u{2115}"),
-    ("&Not;", "\u{2AEC}"),
-    ("&NotCongruent;", "\u{2262}"),
-    ("&NotCupCap;", "\u{226D}"),
-    ("&NotDoubleVerticalBar;", "\u{2226}"),
-    ("&NotElement;", "\u{2209}"),
-    ("&NotEqual;", "\u{2260}"),
-    ("&NotEqualTilde;", "\u{2242}\u{0338}"),
-    ("&NotExists;", "\u{2204}"),
-    ("&NotGreater;", "\u{226F}"),
-    ("&NotGreaterEqual;", "\u{2271}"),
-    ("&NotGreaterFullEqual;", "\u{2267}\u{0338}"),
-    ("&NotGreaterGreater;", "\u{226B}\u{0338}"),
-    ("&NotGreaterLess;", "\u{2279}"),
-    ("&NotGreaterSlantEqual;", "\u{2A7E}\u{0338}"),
-    ("&NotGreaterTilde;", "\u{2275}"),
-    ("&NotHumpDownHump;", "\u{224E}\u{0338}"),
-    ("&NotHumpEqual;", "\u{224F}\u{0338}"),
-    ("&NotLeftTriangle;", "\u{22EA}"),
-    ("&NotLeftTriangleBar;", "\u{29CF}\u{0338}"),
-    ("&NotLeftTriangleEqual;", "\u{22EC}"),
-    ("&NotLess;", "\u{226E}"),
-    ("&NotLessEqual;", "\u{2270}"),
-    ("&NotLessGreater;", "\u{2278}"),
-    ("&NotLessLess;", "\u{226A}\u{0338}"),
-    ("&NotLessSlantEqual;", "\u{2A7D}\u{0338}"),
-    ("&NotLessTilde;", "\u{2274}"),
-    ("&NotNestedGreaterGreater;", "\u{2AA2}\u{0338}"),
-    ("&NotNestedLessLess;", "\u{2AA1}\u{0338}"),
-    ("&NotPrecedes;", "\u{2280}"),
-    ("&NotPrecedesEqual;", "\u{2AAF}\u{0338}"),
-    ("&NotPrecedesSlantEqual;", "\u{22E0}"),
-    ("&NotReverseElement;", "\u{220C}"),
-    ("&NotRightTriangle;", "\u{22EB}"),
-    ("&NotRightTriangleBar;", "\u{29D0}\u{0338}"),
-    ("&NotRightTriangleEqual;", "\u{22ED}"),
-    ("&NotSquareSubset;", "\u{228F}\u{0338}"),
-    ("&NotSquareSubsetEqual;", "\u{22E2}"),
-    ("&NotSquareSuperset;", "\u{2290}\u{0338}"),
-    ("&NotSquareSupersetEqual;", "\u{22E3}"),
-    ("&NotSubset;", "\u{2282}\u{20D2}"),
-    ("&NotSubsetEqual;", "\u{2288}"),
-    ("&NotSucceeds;", "\u{2281}"),
-    ("&NotSucceedsEqual;", "\u{2AB0}\u{0338}"),
-    ("&NotSucceedsSlantEqual;", "\u{22E1}"),
-    ("&NotSucceedsTilde;", "\u{227F}\u{0338}"),
-    ("&NotSuperset;", "\u{2283}\u{20D2}"),
-    ("&NotSupersetEqual;", "\u{2289}"),
-    ("&NotTilde;", "\u{2241}"),
-    ("&NotTildeEqual;", "\u{2244}"),
-    ("&NotTildeFullEqual;", "\u{2247}"),
-    ("&NotTildeTilde;", "\u{2249}"),
-    ("&NotVerticalBar;", "\u{2224}"),
-    ("&Nscr;", "\u{1D4A9}"),
-    ("&Ntilde", "\u{00D1}"),
-    ("&Ntilde;", "\u{00D1}"),
-    ("&Nu;", "\u{039D}"),
-    ("&OElig;", "\u{0152}"),
-    ("&Oacute", "\u{00D3}"),
-    ("&Oacute;", "\u{00D3}"),
-    ("&Ocirc", "\u{00D4}"),
-    ("&Ocirc;", "\u{00D4}"),
-    ("&Ocy;", "\u{041E}"),
-    ("&Odblac;", "\u{0150}"),
-    ("&Ofr;", "\u{1D512}"),
-    ("&Ograve", "\u{00D2}"),
-    ("&Ograve;", "\u{00D2}"),
-    ("&Omacr;", "\u{014C}"),
-    ("&Omega;", "\u{03A9}"),
-    ("&Omicron;", "\u{039F}"),
-    ("&Oopf;", "\u{1D546}"),
-    ("&OpenCurlyDoubleQuote;", "\u{201C}"),
-    ("&OpenCurlyQuote;", "\u{2018}"),
-    ("&Or;", "\u{2A54}"),
-    ("&Oscr;", "\u{1D4AA}"),
-    ("&Oslash", "\u{00D8}"),
-    ("&Oslash;", "\u{00D8}"),
-    ("&Otilde", "\u{00D5}"),
-    ("&Otilde;", "\u{00D5}"),
-    ("&Otimes;", "\u{2A37}"),
-    ("&Ouml", "\u{00D6}"),
-    ("&Ouml;", "\u{00D6}"),
-    ("&OverBar;", "\u{203E}"),
-    ("&OverBrace;", "\u{23DE}"),
-    ("&OverBracket;", "\u{23B4}"),
-    ("&OverParenthesis;", "\u{23DC}"),
-    ("&PartialD;", "\u{2202}"),
-    ("&Pcy;", "\u{041F}"),
-    ("&Pfr;", "\u{1D513}"),
-    ("&Phi;", "\u{03A6}"),
-    ("&Pi;", "\u{03A0}"),
-    ("&PlusMinus;", "\u{00B1}"),
-    ("&Poincareplane;", "\u{210C}"),
-    ("&Popf;", "\u{2119}"),
-    ("&Pr;", "\u{2ABB}"),
-    ("&Precedes;", "\u{227A}"),
-    ("&PrecedesEqual;", "\u{2AAF}"),
-    ("&PrecedesSlantEqual;", "\u{227C}"),
-    ("&PrecedesTilde;", "\u{227E}"),
-    ("&Prime;", "\u{2033}"),
-    ("&Product;", "\u{220F}"),
-    ("&Proportion;", "\u{2237}"),
-    ("&Proportional;", "\u{221D}"),
-    ("&Pscr;", "\u{1D4AB}"),
-    ("&Psi;", "\u{03A8}"),
-    ("&QUOT", "\u{0022}"),
-    ("&QUOT;", "\u{0022}"),
-    ("&Qfr;", "\u{1D514}"),
-    ("&Qopf;", "\u{211A}"),
-    ("&Qscr;", "\u{1D4AC}"),
-    ("&RBarr;", "\u{2910}"),
-    ("&REG", "\u{00AE}"),
-    ("&REG;", "\u{00AE}"),
-    ("&Racute;", "\u{0154}"),
-    ("&Rang;", "\u{27EB}"),
-    ("&Rarr;", "\u{21A0}"),
-    ("&Rarrtl;", "\u{2916}"),
-    ("&Rcaron;", "\u{0158}"),
-    ("&Rcedil;", "\u{0156}"),
-    ("&Rcy;", "\u{0420}"),
-    ("&Re;", "\u{211C}"),
-    ("&ReverseElement;", "\u{220B}"),
-    ("&ReverseEquilibrium;", "\u{21CB}"),
-    ("&ReverseUpEquilibrium;", "\u{296F}"),
-    ("&Rfr;", "\u{211C}"),
-    ("&Rho;", "\u{03A1}"),
-    ("&RightAngleBracket;", "\u{27E9}"),
-    ("&RightArrow;", "\u{2192}"),
-    ("&RightArrowBar;", "\u{21E5}"),
-    ("&RightArrowLeftArrow;", "\u{21C4}"),
-    ("&RightCeiling;", "\u{2309}"),
-    ("&RightDoubleBracket;", "\u{27E7}"),
-    ("&RightDownTeeVector;", "\u{295D}"),
-    ("&RightDownVector;", "\u{21C2}"),
-    ("&RightDownVectorBar;", "\u{2955}"),
-    ("&RightFloor;", "\u{230B}"),
-    ("&RightTee;", "\u{22A2}"),
-    ("&RightTeeArrow;", "\u{21A6}"),
-    ("&RightTeeVector;", "\u{295B}"),
-    ("&RightTriangle;", "\u{22B3}"),
-    ("&RightTriangleBar;", "\u{29D0}"),
-    ("&RightTriangleEqual;", "\u{22B5}"),
-    ("&RightUpDownVector;", "\u{294F}"),
-    ("&RightUpTeeVector;", "\u{295C}"),
-    ("&RightUpVector;", "\u{21BE}"),
-    ("&RightUpVectorBar;", "\u{2954}"),
-    ("&RightVector;", "\u{21C0}"),
-    ("&RightVectorBar;", "\u{2953}"),
-    ("&Rightarrow;", "\u{21D2}"),
-    ("&Ropf;", "\u{211D}"),
-    ("&RoundImplies;", "\u{2970}"),
-    ("&Rrightarrow;", "\u{21DB}"),
-    ("&Rscr;", "\u{211B}"),
-    ("&Rsh;", "\u{21B1}"),
-    ("&RuleDelayed;", "\u{29F4}"),
-    ("&SHCHcy;", "\u{0429}"),
-    ("&SHcy;", "\u{0428}"),
-    ("&SOFTcy;", "\u{042C}"),
-    ("&Sacute;", "\u{015A}"),
-    ("&Sc;", "\u{2ABC}"),
-    ("&Scaron;", "\u{0160}"),
-    ("&Scedil;", "\u{015E}"),
-    ("&Scirc;", "\u{015C}"),
-    ("&Scy;", "\u{0421}"),
-    ("&Sfr;", "\u{1D516}"),
-    ("&ShortDownArrow;", "\u{2193}"),
-    ("&ShortLeftArrow;", "\u{2190}"),
-    ("&ShortRightArrow;", "\u{2192}"),
-    ("&ShortUpArrow;", "\u{2191}"),
-    ("&Sigma;", "\u{03A3}"),
-    ("&SmallCircle;", "\u{2218}"),
-    ("&Sopf;", "\u{1D54A}"),
-    ("&Sqrt;", "\u{221A}"),
-    ("&Square;", "\u{25A1}"),
-    ("&SquareIntersection;", "\u{2293}"),
-    ("&SquareSubset;", "\u{228F}"),
-    ("&SquareSubsetEqual;", "\u{2291}"),
-    ("&SquareSuperset;", "\u{2290}"),
-    ("&SquareSupersetEqual;", "\u{2292}"),
-    ("&SquareUnion;", "\u{2294}"),
-    ("&Sscr;", "\u{1D4AE}"),
-    ("&Star;", "\u{22C6}"),
-    ("&Sub;", "\u{22D0}"),
-    ("&Subset;", "\u{22D0}"),
-    ("&SubsetEqual;", "\u{2286}"),
-    ("&Succeeds;", "\u{227B}"),
-    ("&SucceedsEqual;", "\u{2AB0}"),
-    ("&SucceedsSlantEqual;", "\u{227D}"),
-    ("&SucceedsTilde;", "\u{227F}"),
-    ("&SuchThat;", "\u{220B}"),
-    ("&Sum;", "\u{2211}"),
-    ("&Sup;", "\u{22D1}"),
-    ("&Superset;", "\u{2283}"),
-    ("&SupersetEqual;", "\u{2287}"),
-    ("&Supset;", "\u{22D1}"),
-    ("&THORN", "\u{00DE}"),
-    ("&THORN;", "\u{00DE}"),
-    ("&TRADE;", "\u{2122}"),
-    ("&TSHcy;", "\u{040B}"),
-    ("&TScy;", "\u{0426}"),
-    ("&Tab;", "\u{0009}"),
-    ("&Tau;", "\u{03A4}"),
-    ("&Tcaron;", "\u{0164}"),
-    ("&Tcedil;", "\u{0162}"),
-    ("&Tcy;", "\u{0422}"),
-    ("&Tfr;", "\u{1D517}"),
-    ("&Therefore;", "\u{2234}"),
-    ("&Theta;", "\u{0398}"),
-    ("&ThickSpace;", "\u{205F}\u{200A}"),
-    ("&ThinSpace;", "\u{2009}"),
-    ("&Tilde;", "\u{223C}"),
-    ("&TildeEqual;", "\u{2243}"),
-    ("&TildeFullEqual;", "\u{2245}"),
-    ("&TildeTilde;", "\u{2248}"),
-    ("&Topf;", "\u{1D54B}"),
-    ("&TripleDot;", "\u{20DB}"),
-    ("&Tscr;", "\u{1D4AF}"),
-    ("&Tstrok;", "\u{0166}"),
-    ("&Uacute", "\u{00DA}"),
-    ("&Uacute;", "\u{00DA}"),
-    ("&Uarr;", "\u{219F}"),
-    ("&Uarrocir;", "\u{2949}"),
-    ("&Ubrcy;", "\u{040E}"),
-    ("&Ubreve;", "\u{016C}"),
-    ("&Ucirc", "\u{00DB}"),
-    ("&Ucirc;", "\u{00DB}"),
-    ("&Ucy;", "\u{0423}"),
-    ("&Udblac;", "\u{0170}"),
-    ("&Ufr;", "\u{1D518}"),
-    ("&Ugrave", "\u{00D9}"),
-    ("&Ugrave;", "\u{00D9}"),
-    ("&Umacr;", "\u{016A}"),
-    ("&UnderBar;", "\u{005F}"),
-    ("&UnderBrace;", "\u{23DF}"),
-    ("&UnderBracket;", "\u{23B5}"),
-    ("&UnderParenthesis;", "\u{23DD}"),
-    ("&Union;", "\u{22C3}"),
-    ("&UnionPlus;", "\u{228E}"),
-    ("&Uogon;", "\u{0172}"),
-    ("&Uopf;", "\u{1D54C}"),
-    ("&UpArrow;", "\u{2191}"),
-    ("&UpArrowBar;", "\u{2912}"),
-    ("&UpArrowDownArrow;", "\u{21C5}"),
-    ("&UpDownArrow;", "\u{2195}"),
-    ("&UpEquilibrium;", "\u{296E}"),
-    ("&UpTee;", "\u{22A5}"),
-    ("&UpTeeArrow;", "\u{21A5}"),
-    ("&Uparrow;", "\u{21D1}"),
-    ("&Updownarrow;", "\u{21D5}"),
-    ("&UpperLeftArrow;", "\u{2196}"),
-    ("&UpperRightArrow;", "\u{2197}"),
-    ("&Upsi;", "\u{03D2}"),
-    ("&Upsilon;", "\u{03A5}"),
-    ("&Uring;", "\u{016E}"),
-    ("&Uscr;", "\u{1D4B0}"),
-    ("&Utilde;", "\u{0168}"),
-    ("&Uuml", "\u{00DC}"),
-    ("&Uuml;", "\u{00DC}"),
-    ("&VDash;", "\u{22AB}"),
-    ("&Vbar;", "\u{2AEB}"),
-    ("&Vcy;", "\u{0412}"),
-    ("&Vdash;", "\u{22A9}"),
-    ("&Vdashl;", "\u{2AE6}"),
-    ("&Vee;", "\u{22C1}"),
-    ("&Verbar;", "\u{2016}"),
-    ("&Vert;", "\u{2016}"),
-    ("&VerticalBar;", "\u{2223}"),
-    ("&VerticalLine;", "\u{007C}"),
-    ("&VerticalSeparator;", "\u{2758}"),
-    ("&VerticalTilde;", "\u{2240}"),
-    ("&VeryThinSpace;", "\u{200A}"),
-    ("&Vfr;", "\u{1D519}"),
-    ("&Vopf;", "\u{1D54D}"),
-    ("&Vscr;", "\u{1D4B1}"),
-    ("&Vvdash;", "\u{22AA}"),
-    ("&Wcirc;", "\u{0174}"),
-    ("&Wedge;", "\u{22C0}"),
-    ("&Wfr;", "\u{1D51A}"),
-    ("&Wopf;", "\u{1D54E}"),
-    ("&Wscr;", "\u{1D4B2}"),
-    ("&Xfr;", "\u{1D51B}"),
-    ("&Xi;", "\u{039E}"),
-    ("&Xopf;", "\u{1D54F}"),
-    ("&Xscr;", "\u{1D4B3}"),
-    ("&YAcy;", "\u{042F}"),
-    ("&YIcy;", "\u{0407}"),
-    ("&YUcy;", "\u{042E}"),
-    ("&Yacute", "\u{00DD}"),
-    ("&Yacute;", "\u{00DD}"),
-    ("&Ycirc;", "\u{0176}"),
-    ("&Ycy;", "\u{042B}"),
-    ("&Yfr;", "\u{1D51C}"),
-    ("&Yopf;", "\u{1D550}"),
-    ("&Yscr;", "\u{1D4B4}"),
-    ("&Yuml;", "\u{0178}"),
-    ("&ZHcy;", "\u{0416}"),
-    ("&Zacute;", "\u{0179}"),
-    ("&Zcaron;", "\u{017D}"),
-    ("&Zcy;", "\u{0417}"),
-    ("&Zdot;", "\u{017B}"),
-    ("&ZeroWidthSpace;", "\u{200B}"),
-    ("&Zeta;", "\u{0396}"),
-    ("&Zfr;", "\u{2128}"),
-    ("&Zopf;", "\u{2124}"),
-    ("&Zscr;", "\u{1D4B5}"),
-    ("&aacute", "\u{00E1}"),
-    ("&aacute;", "\u{00E1}"),
-    ("&abreve;", "\u{0103}"),
-    ("&ac;", "\u{223E}"),
-    ("&acE;", "\u{223E}\u{0333}"),
-    ("&acd;", "\u{223F}"),
-    ("&acirc", "\u{00E2}"),
-    ("&acirc;", "\u{00E2}"),
-    ("&acute", "\u{00B4}"),
-    ("&acute;", "\u{00B4}"),
-    ("&acy;", "\u{0430}"),
-    ("&aelig", "\u{00E6}"),
-    ("&aelig;", "\u{00E6}"),
-    ("&af;", "\u{2061}"),
-    ("&afr;", "\u{1D51E}"),
-    ("&agrave", "\u{00E0}"),
-    ("&agrave;", "\u{00E0}"),
-    ("&alefsym;", "\u{2135}"),
-    ("&aleph;", "\u{2135}"),
-    ("&alpha;", "\u{03B1}"),
-    ("&amacr;", "\u{0101}"),
-    ("&amalg;", "\u{2A3F}"),
-    ("&amp", "\u{0026}"),
-    ("&amp;", "\u{0026}"),
-    ("&and;", "\u{2227}"),
-    ("&andand;", "\u{2A55}"),
-    ("&andd;", "\u{2A5C}"),
-    ("&andslope;", "\u{2A58}"),
-    ("&andv;", "\u{2A5A}"),
-    ("&ang;", "\u{2220}"),
-    ("&ange;", "\u{29A4}"),
-    ("&angle;", "\u{2220}"),
-    ("&angmsd;", "\u{2221}"),
-    ("&angmsdaa;", "\u{29A8}"),
-    ("&angmsdab;", "\u{29A9}"),
-    ("&angmsdac;", "\u{29AA}"),
-    ("&angmsdad;", "\u{29AB}"),
-    ("&angmsdae;", "\u{29AC}"),
-    ("&angmsdaf;", "\u{29AD}"),
-    ("&angmsdag;", "\u{29AE}"),
-    ("&angmsdah;", "\u{29AF}"),
-    ("&angrt;", "\u{221F}"),
-    ("&angrtvb;", "\u{22BE}"),
-    ("&angrtvbd;", "\u{299D}"),
-    ("&angsph;", "\u{2222}"),
-    ("&angst;", "\u{00C5}"),
-    ("&angzarr;", "\u{237C}"),
-    ("&aogon;", "\u{0105}"),
-    ("&aopf;", "\u{1D552}"),
-    ("&ap;", "\u{2248}"),
-    ("&apE;", "\u{2A70}"),
-    ("&apacir;", "\u{2A6F}"),
-    ("&ape;", "\u{224A}"),
-    ("&apid;", "\u{224B}"),
-    ("&apos;", "\u{0027}"),
-    ("&approx;", "\u{2248}"),
-    ("&approxeq;", "\u{224A}"),
-    ("&aring", "\u{00E5}"),
-    ("&aring;", "\u{00E5}"),
-    ("&ascr;", "\u{1D4B6}"),
-    ("&ast;", "\u{002A}"),
-    ("&asymp;", "\u{2248}"),
-    ("&asympeq;", "\u{224D}"),
-    ("&atilde", "\u{00E3}"),
-    ("&atilde;", "\u{00E3}"),
-    ("&auml", "\u{00E4}"),
-    ("&auml;", "\u{00E4}"),
-    ("&awconint;", "\u{2233}"),
-    ("&awint;", "\u{2A11}"),
-    ("&bNot;", "\u{2AED}"),
-    ("&backcong;", "\u{224C}"),
-    ("&backepsilon;", "\u{03F6}"),
-    ("&backprime;", "\u{2035}"),
-    ("&backsim;", "\u{223D}"),
-    ("&backsimeq;", "\u{22CD}"),
-    ("&barvee;", "\u{22BD}"),
-    ("&barwed;", "\u{2305}"),
-    ("&barwedge;", "\u{2305}"),
-    ("&bbrk;", "\u{23B5}"),
-    ("&bbrktbrk;", "\u{23B6}"),
-    ("&bcong;", "\u{224C}"),
-    ("&bcy;", "\u{0431}"),
-    ("&bdquo;", "\u{201E}"),
-    ("&becaus;", "\u{2235}"),
-    ("&because;", "\u{2235}"),
-    ("&bemptyv;", "\u{29B0}"),
-    ("&bepsi;", "\u{03F6}"),
-    ("&bernou;", "\u{212C}"),
-    ("&beta;", "\u{03B2}"),
-    ("&beth;", "\u{2136}"),
-    ("&between;", "\u{226C}"),
-    ("&bfr;", "\u{1D51F}"),
-    ("&bigcap;", "\u{22C2}"),
-    ("&bigcirc;", "\u{25EF}"),
-    ("&bigcup;", "\u{22C3}"),
-    ("&bigodot;", "\u{2A00}"),
-    ("&bigoplus;", "\u{2A01}"),
-    ("&bigotimes;", "\u{2A02}"),
-    ("&bigsqcup;", "\u{2A06}"),
-    ("&bigstar;", "\u{2605}"),
-    ("&bigtriangledown;", "\u{25BD}"),
-    ("&bigtriangleup;", "\u{25B3}"),
-    ("&biguplus;", "\u{2A04}"),
-    ("&bigvee;", "\u{22C1}"),
-    ("&bigwedge;", "\u{22C0}"),
-    ("&bkarow;", "\u{290D}"),
-    ("&blacklozenge;", "\u{29EB}"),
-    ("&blacksquare;", "\u{25AA}"),
-    ("&blacktriangle;", "\u{25B4}"),
-    ("&blacktriangledown;", "\u{25BE}"),
-    ("&blacktriangleleft;", "\u{25C2}"),
-    ("&blacktriangleright;", "\u{25B8}"),
-    ("&blank;", "\u{2423}"),
-    ("&blk12;", "\u{2592}"),
-    ("&blk14;", "\u{2591}"),
-    ("&blk34;", "\u{2593}"),
-    ("&block;", "\u{2588}"),
-    ("&bne;", "\u{003D}\u{20E5}"),
-    ("&bnequiv;", "\u{2261}\u{20E5}"),
-    ("&bnot;", "\u{2310}"),
-    ("&bopf;", "\u{1D553}"),
-    ("&bot;", "\u{22A5}"),
-    ("&bottom;", "\u{22A5}"),
-    ("&bowtie;", "\u{22C8}"),
-    ("&boxDL;", "\u{2557}"),
-    ("&boxDR;", "\u{2554}"),
-    ("&boxDl;", "\u{2556}"),
-    ("&boxDr;", "\u{2553}"),
-    ("&boxH;", "\u{2550}"),
-    ("&boxHD;", "\u{2566}"),
-    ("&boxHU;", "\u{2569}"),
-    ("&boxHd;", "\u{2564}"),
-    ("&boxHu;", "\u{2567}"),
-    ("&boxUL;", "\u{255D}"),
-    ("&boxUR;", "\u{255A}"),
-    ("&boxUl;", "\u{255C}"),
-    ("&boxUr;", "\u{2559}"),
-    ("&boxV;", "\u{2551}"),
-    ("&boxVH;", "\u{256C}"),
-    ("&boxVL;", "\u{2563}"),
-    ("&boxVR;", "\u{2560}"),
-    ("&boxVh;", "\u{256B}"),
-    ("&boxVl;", "\u{2562}"),
-    ("&boxVr;", "\u{255F}"),
-    ("&boxbox;", "\u{29C9}"),
-    ("&boxdL;", "\u{2555}"),
-    ("&boxdR;", "\u{2552}"),
-    ("&boxdl;", "\u{2510}"),
-    ("&boxdr;", "\u{250C}"),
-    ("&boxh;", "\u{2500}"),
-    ("&boxhD;", "\u{2565}"),
-    ("&boxhU;", "\u{2568}"),
-    ("&boxhd;", "\u{252C}"),
-    ("&boxhu;", "\u{2534}"),
-    ("&boxminus;", "\u{229F}"),
-    ("&boxplus;", "\u{229E}"),
-    ("&boxtimes;", "\u{22A0}"),
-    ("&boxuL;", "\u{255B}"),
-    ("&boxuR;", "\u{2558}"),
-    ("&boxul;", "\u{2518}"),
-    ("&boxur;", "\u{2514}"),
-    ("&boxv;", "\u{2502}"),
-    ("&boxvH;", "\u{256A}"),
-    ("&boxvL;", "\u{2561}"),
-    ("&boxvR;", "\u{255E}"),
-    ("&boxvh;", "\u{253C}"),
-    ("&boxvl;", "\u{2524}"),
-    ("&boxvr;", "\u{251C}"),
-    ("&bprime;", "\u{2035}"),
-    ("&breve;", "\u{02D8}"),
-    ("&brvbar", "\u{00A6}"),
-    ("&brvbar;", "\u{00A6}"),
-    ("&bscr;", "\u{1D4B7}"),
-    ("&bsemi;", "\u{204F}"),
-    ("&bsim;", "\u{223D}"),
-    ("&bsime;", "\u{22CD}"),
-    ("&bsol;", "\u{005C}"),
-    ("&bsolb;", "\u{29C5}"),
-    ("&bsolhsub;", "\u{27C8}"),
-    ("&bull;", "\u{2022}"),
-    ("&bullet;", "\u{2022}"),
-    ("&bump;", "\u{224E}"),
-    ("&bumpE;", "\u{2AAE}"),
-    ("&bumpe;", "\u{224F}"),
-    ("&bumpeq;", "\u{224F}"),
-    ("&cacute;", "\u{0107}"),
-    ("&cap;", "\u{2229}"),
-    ("&capand;", "\u{2A44}"),
-    ("&capbrcup;", "\u{2A49}"),
-    ("&capcap;", "\u{2A4B}"),
-    ("&capcup;", "\u{2A47}"),
-    ("&capdot;", "\u{2A40}"),
-    ("&caps;", "\u{2229}\u{FE00}"),
-    ("&caret;", "\u{2041}"),
-    ("&caron;", "\u{02C7}"),
-    ("&ccaps;", "\u{2A4D}"),
-    ("&ccaron;", "\u{010D}"),
-    ("&ccedil", "\u{00E7}"),
-    ("&ccedil;", "\u{00E7}"),
-    ("&ccirc;", "\u{0109}"),
-    ("&ccups;", "\u{2A4C}"),
-    ("&ccupssm;", "\u{2A50}"),
-    ("&cdot;", "\u{010B}"),
-    ("&cedil", "\u{00B8}"),
-    ("&cedil;", "\u{00B8}"),
-    ("&cemptyv;", "\u{29B2}"),
-    ("&cent", "\u{00A2}"),
-    ("&cent;", "\u{00A2}"),
-    ("&centerdot;", "\u{00B7}"),
-    ("&cfr;", "\u{1D520}"),
-    ("&chcy;", "\u{0447}"),
-    ("&check;", "\u{2713}"),
-    ("&checkmark;", "\u{2713}"),
-    ("&chi;", "\u{03C7}"),
-    ("&cir;", "\u{25CB}"),
-    ("&cirE;", "\u{29C3}"),
-    ("&circ;", "\u{02C6}"),
-    ("&circeq;", "\u{2257}"),
-    ("&circlearrowleft;", "\u{21BA}"),
-    ("&circlearrowright;", "\u{21BB}"),
-    ("&circledR;", "\u{00AE}"),
-    ("&circledS;", "\u{24C8}"),
-    ("&circledast;", "\u{229B}"),
-    ("&circledcirc;", "\u{229A}"),
-    ("&circleddash;", "\u{229D}"),
-    ("&cire;", "\u{2257}"),
-    ("&cirfnint;", "\u{2A10}"),
-    ("&cirmid;", "\u{2AEF}"),
-    ("&cirscir;", "\u{29C2}"),
-    ("&clubs;", "\u{2663}"),
-    ("&clubsuit;", "\u{2663}"),
-    ("&colon;", "\u{003A}"),
-    ("&colone;", "\u{2254}"),
-    ("&coloneq;", "\u{2254}"),
-    ("&comma;", "\u{002C}"),
-    ("&commat;", "\u{0040}"),
-    ("&comp;", "\u{2201}"),
-    ("&compfn;", "\u{2218}"),
-    ("&complement;", "\u{2201}"),
-    ("&complexes;", "\u{2102}"),
-    ("&cong;", "\u{2245}"),
-    ("&congdot;", "\u{2A6D}"),
-    ("&conint;", "\u{222E}"),
-    ("&copf;", "\u{1D554}"),
-    ("&coprod;", "\u{2210}"),
-    ("&copy", "\u{00A9}"),
-    ("&copy;", "\u{00A9}"),
-    ("&copysr;", "\u{2117}"),
-    ("&crarr;", "\u{21B5}"),
-    ("&cross;", "\u{2717}"),
-    ("&cscr;", "\u{1D4B8}"),
-    ("&csub;", "\u{2ACF}"),
-    ("&csube;", "\u{2AD1}"),
-    ("&csup;", "\u{2AD0}"),
-    ("&csupe;", "\u{2AD2}"),
-    ("&ctdot;", "\u{22EF}"),
-    ("&cudarrl;", "\u{2938}"),
-    ("&cudarrr;", "\u{2935}"),
-    ("&cuepr;", "\u{22DE}"),
-    ("&cuesc;", "\u{22DF}"),
-    ("&cularr;", "\u{21B6}"),
-    ("&cularrp;", "\u{293D}"),
-    ("&cup;", "\u{222A}"),
-    ("&cupbrcap;", "\u{2A48}"),
-    ("&cupcap;", "\u{2A46}"),
-    ("&cupcup;", "\u{2A4A}"),
-    ("&cupdot;", "\u{228D}"),
-    ("&cupor;", "\u{2A45}"),
-    ("&cups;", "\u{222A}\u{FE00}"),
-    ("&curarr;", "\u{21B7}"),
-    ("&curarrm;", "\u{293C}"),
-    ("&curlyeqprec;", "\u{22DE}"),
-    ("&curlyeqsucc;", "\u{22DF}"),
-    ("&curlyvee;", "\u{22CE}"),
-    ("&curlywedge;", "\u{22CF}"),
-    ("&curren", "\u{00A4}"),
-    ("&curren;", "\u{00A4}"),
-    ("&curvearrowleft;", "\u{21B6}"),
-    ("&curvearrowright;", "\u{21B7}"),
-    ("&cuwed;", "\u{22CF}"),
-    ("&cuvee;", "\u{22CE}"),
-    ("&cwint;", "\u{2231}"),
-    ("&cwconint;", "\u{2232}"),
-    ("&dArr;", "\u{21D3}"),
-    ("&cylcty;", "\u{232D}"),
-    ("&dagger;", "\u{2020}"),
-    ("&dHar;", "\u{2965}"),
-    ("&darr;", "\u{2193}"),
-    ("&daleth;", "\u{2138}"),
-    ("&dashv;", "\u{22A3}"),
-    ("&dash;", "\u{2010}"),
-    ("&dblac;", "\u{02DD}"),
-    ("&dbkarow;", "\u{290F}"),
-    ("&dcy;", "\u{0434}"),
-    ("&dcaron;", "\u{010F}"),
-    ("&ddagger;", "\u{2021}"),
-    ("&dd;", "\u{2146}"),
-    ("&ddotseq;", "\u{2A77}"),
-    ("&ddarr;", "\u{21CA}"),
-    ("&deg;", "\u{00B0}"),
-    ("&deg", "\u{00B0}"),
-    ("&demptyv;", "\u{29B1}"),
-    ("&delta;", "\u{03B4}"),
-    ("&dfr;", "\u{1D521}"),
-    ("&dfisht;", "\u{297F}"),
-    ("&dharr;", "\u{21C2}"),
-    ("&dharl;", "\u{21C3}"),
-    ("&diamond;", "\u{22C4}"),
-    ("&diam;", "\u{22C4}"),
-    ("&diams;", "\u{2666}"),
-    ("&diamondsuit;", "\u{2666}"),
-    ("&digamma;", "\u{03DD}"),
-    ("&die;", "\u{00A8}"),
-    ("&div;", "\u{00F7}"),
-    ("&disin;", "\u{22F2}"),
-    ("&divide;", "\u{00F7}"),
-    ("&divide", "\u{00F7}"),
-    ("&divonx;", "\u{22C7}"),
-    ("&divideontimes;", "\u{22C7}"),
-    ("&dlcorn;", "\u{231E}"),
-    ("&djcy;", "\u{0452}"),
-    ("&dollar;", "\u{0024}"),
-    ("&dlcrop;", "\u{230D}"),
-    ("&dot;", "\u{02D9}"),
-    ("&dopf;", "\u{1D555}"),
-    ("&doteqdot;", "\u{2251}"),
-    ("&doteq;", "\u{2250}"),
-    ("&dotplus;", "\u{2214}"),
-    ("&dotminus;", "\u{2238}"),
-    ("&doublebarwedge;", "\u{2306}"),
-    ("&dotsquare;", "\u{22A1}"),
-    ("&downdownarrows;", "\u{21CA}"),
-    ("&downarrow;", "\u{2193}"),
-    ("&downharpoonright;", "\u{21C2}"),
-    ("&downharpoonleft;", "\u{21C3}"),
-    ("&drcorn;", "\u{231F}"),
-    ("&drbkarow;", "\u{2910}"),
-    ("&dscr;", "\u{1D4B9}"),
-    ("&drcrop;", "\u{230C}"),
-    ("&dsol;", "\u{29F6}"),
-    ("&dscy;", "\u{0455}"),
-    ("&dtdot;", "\u{22F1}"),
-    ("&dstrok;", "\u{0111}"),
-    ("&dtrif;", "\u{25BE}"),
-    ("&dtri;", "\u{25BF}"),
-    ("&duhar;", "\u{296F}"),
-    ("&duarr;", "\u{21F5}"),
-    ("&dzcy;", "\u{045F}"),
-    ("&dwangle;", "\u{29A6}"),
-    ("&eDDot;", "\u{2A77}"),
-    ("&dzigrarr;", "\u{27FF}"),
-    ("&eacute", "\u{00E9}"),
-    ("&eDot;", "\u{2251}"),
-    ("&easter;", "\u{2A6E}"),
-    ("&eacute;", "\u{00E9}"),
-    ("&ecir;", "\u{2256}"),
-    ("&ecaron;", "\u{011B}"),
-    ("&ecirc;", "\u{00EA}"),
-    ("&ecirc", "\u{00EA}"),
-    ("&ecy;", "\u{044D}"),
-    ("&ecolon;", "\u{2255}"),
-    ("&ee;", "\u{2147}"),
-    ("&edot;", "\u{0117}"),
-    ("&efr;", "\u{1D522}"),
-    ("&efDot;", "\u{2252}"),
-    ("&egrave", "\u{00E8}"),
-    ("&eg;", "\u{2A9A}"),
-    ("&egs;", "\u{2A96}"),
-    ("&egrave;", "\u{00E8}"),
-    ("&el;", "\u{2A99}"),
-    ("&egsdot;", "\u{2A98}"),
-    ("&ell;", "\u{2113}"),
-    ("&elinters;", "\u{23E7}"),
-    ("&elsdot;", "\u{2A97}"),
-    ("&els;", "\u{2A95}"),
-    ("&empty;", "\u{2205}"),
-    ("&emacr;", "\u{0113}"),
-    ("&emptyv;", "\u{2205}"),
-    ("&emptyset;", "\u{2205}"),
-    ("&emsp14;", "\u{2005}"),
-    ("&emsp13;", "\u{2004}"),
-    ("&eng;", "\u{014B}"),
-    ("&emsp;", "\u{2003}"),
-    ("&eogon;", "\u{0119}"),
-    ("&ensp;", "\u{2002}"),
-    ("&epar;", "\u{22D5}"),
-    ("&eopf;", "\u{1D556}"),
-    ("&eplus;", "\u{2A71}"),
-    ("&eparsl;", "\u{29E3}"),
-    ("&epsilon;", "\u{03B5}"),
-    ("&epsi;", "\u{03B5}"),
-    ("&eqcirc;", "\u{2256}"),
-    ("&epsiv;", "\u{03F5}"),
-    ("&eqsim;", "\u{2242}"),
-    ("&eqcolon;", "\u{2255}"),
-    ("&eqslantless;", "\u{2A95}"),
-    ("&eqslantgtr;", "\u{2A96}"),
-    ("&equest;", "\u{225F}"),
-    ("&equals;", "\u{003D}"),
-    ("&equivDD;", "\u{2A78}"),
-    ("&equiv;", "\u{2261}"),
-    ("&erDot;", "\u{2253}"),
-    ("&eqvparsl;", "\u{29E5}"),
-    ("&escr;", "\u{212F}"),
-    ("&erarr;", "\u{2971}"),
-    ("&esim;", "\u{2242}"),
-    ("&esdot;", "\u{2250}"),
-    ("&eth", "\u{00F0}"),
-    ("&eta;", "\u{03B7}"),
-    ("&euml", "\u{00EB}"),
-    ("&eth;", "\u{00F0}"),
-    ("&euro;", "\u{20AC}"),
-    ("&euml;", "\u{00EB}"),
-    ("&exist;", "\u{2203}"),
-    ("&excl;", "\u{0021}"),
-    ("&exponentiale;", "\u{2147}"),
-    ("&expectation;", "\u{2130}"),
-    ("&fcy;", "\u{0444}"),
-    ("&fallingdotseq;", "\u{2252}"),
-    ("&ffilig;", "\u{FB03}"),
-    ("&female;", "\u{2640}"),
-    ("&ffllig;", "\u{FB04}"),
-    ("&fflig;", "\u{FB00}"),
-    ("&filig;", "\u{FB01}"),
-    ("&ffr;", "\u{1D523}"),
-    ("&flat;", "\u{266D}"),
-    ("&fjlig;", "\u{0066}\u{006A}"),
-    ("&fltns;", "\u{25B1}"),
-    ("&fllig;", "\u{FB02}"),
-    ("&fopf;", "\u{1D557}"),
-    ("&fnof;", "\u{0192}"),
-    ("&fork;", "\u{22D4}"),
-    ("&forall;", "\u{2200}"),
-    ("&fpartint;", "\u{2A0D}"),
-    ("&forkv;", "\u{2AD9}"),
-    ("&frac12;", "\u{00BD}"),
-    ("&frac12", "\u{00BD}"),
-    ("&frac14", "\u{00BC}"),
-    ("&frac13;", "\u{2153}"),
-    ("&frac15;", "\u{2155}"),
-    ("&frac14;", "\u{00BC}"),
-    ("&frac18;", "\u{215B}"),
-    ("&frac16;", "\u{2159}"),
-    ("&frac25;", "\u{2156}"),
-    ("&frac23;", "\u{2154}"),
-    ("&frac34;", "\u{00BE}"),
-    ("&frac34", "\u{00BE}"),
-    ("&frac38;", "\u{215C}"),
-    ("&frac35;", "\u{2157}"),
-    ("&frac56;", "\u{215A}"),
-    ("&frac45;", "\u{2158}"),
-    ("&frac78;", "\u{215E}"),
-    ("&frac58;", "\u{215D}"),
-    ("&frown;", "\u{2322}"),
-    ("&frasl;", "\u{2044}"),
-    ("&gE;", "\u{2267}"),
-    ("&fscr;", "\u{1D4BB}"),
-    ("&gacute;", "\u{01F5}"),
-    ("&gEl;", "\u{2A8C}"),
-    ("&gammad;", "\u{03DD}"),
-    ("&gamma;", "\u{03B3}"),
-    ("&gbreve;", "\u{011F}"),
-    ("&gap;", "\u{2A86}"),
-    ("&gcy;", "\u{0433}"),
-    ("&gcirc;", "\u{011D}"),
-    ("&ge;", "\u{2265}"),
-    ("&gdot;", "\u{0121}"),
-    ("&geq;", "\u{2265}"),
-    ("&gel;", "\u{22DB}"),
-    ("&geqslant;", "\u{2A7E}"),
-    ("&geqq;", "\u{2267}"),
-    ("&gescc;", "\u{2AA9}"),
-    ("&ges;", "\u{2A7E}"),
-    ("&gesdoto;", "\u{2A82}"),
-    ("&gesdot;", "\u{2A80}"),
-    ("&gesl;", "\u{22DB}\u{FE00}"),
-    ("&gesdotol;", "\u{2A84}"),
-    ("&gfr;", "\u{1D524}"),
-    ("&gesles;", "\u{2A94}"),
-    ("&ggg;", "\u{22D9}"),
-    ("&gg;", "\u{226B}"),
-    ("&gjcy;", "\u{0453}"),
-    ("&gimel;", "\u{2137}"),
-    ("&glE;", "\u{2A92}"),
-    ("&gl;", "\u{2277}"),
-    ("&glj;", "\u{2AA4}"),
-    ("&gla;", "\u{2AA5}"),
-    ("&gnap;", "\u{2A8A}"),
-    ("&gnE;", "\u{2269}"),
-    ("&gne;", "\u{2A88}"),
-    ("&gnapprox;", "\u{2A8A}"),
-    ("&gneqq;", "\u{2269}"),
-    ("&gneq;", "\u{2A88}"),
-    ("&gopf;", "\u{1D558}"),
-    ("&gnsim;", "\u{22E7}"),
-    ("&gscr;", "\u{210A}"),
-    ("&grave;", "\u{0060}"),
-    ("&gsime;", "\u{2A8E}"),
-    ("&gsim;", "\u{2273}"),
-    ("&gt", "\u{003E}"),
-    ("&gsiml;", "\u{2A90}"),
-    ("&gtcc;", "\u{2AA7}"),
-    ("&gt;", "\u{003E}"),
-    ("&gtdot;", "\u{22D7}"),
-    ("&gtcir;", "\u{2A7A}"),
-    ("&gtquest;", "\u{2A7C}"),
-    ("&gtlPar;", "\u{2995}"),
-    ("&gtrarr;", "\u{2978}"),
-    ("&gtrapprox;", "\u{2A86}"),
-    ("&gtreqless;", "\u{22DB}"),
-    ("&gtrdot;", "\u{22D7}"),
-    ("&gtrless;", "\u{2277}"),
-    ("&gtreqqless;", "\u{2A8C}"),
-    ("&gvertneqq;", "\u{2269}\u{FE00}"),
-    ("&gtrsim;", "\u{2273}"),
-    ("&hArr;", "\u{21D4}"),
-    ("&gvnE;", "\u{2269}\u{FE00}"),
-    ("&half;", "\u{00BD}"),
-    ("&hairsp;", "\u{200A}"),
-    ("&hardcy;", "\u{044A}"),
-    ("&hamilt;", "\u{210B}"),
-    ("&harrcir;", "\u{2948}"),
-    ("&harr;", "\u{2194}"),
-    ("&hbar;", "\u{210F}"),
-    ("&harrw;", "\u{21AD}"),
-    ("&hearts;", "\u{2665}"),
-    ("&hcirc;", "\u{0125}"),
-    ("&hellip;", "\u{2026}"),
-    ("&heartsuit;", "\u{2665}"),
-    ("&hfr;", "\u{1D525}"),
-    ("&hercon;", "\u{22B9}"),
-    ("&hkswarow;", "\u{2926}"),
-    ("&hksearow;", "\u{2925}"),
-    ("&homtht;", "\u{223B}"),
-    ("&hoarr;", "\u{21FF}"),
-    ("&hookrightarrow;", "\u{21AA}"),
-    ("&hookleftarrow;", "\u{21A9}"),
-    ("&horbar;", "\u{2015}"),
-    ("&hopf;", "\u{1D559}"),
-    ("&hslash;", "\u{210F}"),
-    ("&hscr;", "\u{1D4BD}"),
-    ("&hybull;", "\u{2043}"),
-    ("&hstrok;", "\u{0127}"),
-    ("&iacute", "\u{00ED}"),
-    ("&hyphen;", "\u{2010}"),
-    ("&ic;", "\u{2063}"),
-    ("&iacute;", "\u{00ED}"),
-    ("&icirc;", "\u{00EE}"),
-    ("&icirc", "\u{00EE}"),
-    ("&iecy;", "\u{0435}"),
-    ("&icy;", "\u{0438}"),
-    ("&iexcl;", "\u{00A1}"),
-    ("&iexcl", "\u{00A1}"),
-    ("&ifr;", "\u{1D526}"),
-    ("&iff;", "\u{21D4}"),
-    ("&igrave;", "\u{00EC}"),
-    ("&igrave", "\u{00EC}"),
-    ("&iiiint;", "\u{2A0C}"),
-    ("&ii;", "\u{2148}"),
-    ("&iinfin;", "\u{29DC}"),
-    ("&iiint;", "\u{222D}"),
-    ("&ijlig;", "\u{0133}"),
-    ("&iiota;", "\u{2129}"),
-    ("&image;", "\u{2111}"),
-    ("&imacr;", "\u{012B}"),
-    ("&imagpart;", "\u{2111}"),
-    ("&imagline;", "\u{2110}"),
-    ("&imof;", "\u{22B7}"),
-    ("&imath;", "\u{0131}"),
-    ("&in;", "\u{2208}"),
-    ("&imped;", "\u{01B5}"),
-    ("&infin;", "\u{221E}"),
-    ("&incare;", "\u{2105}"),
-    ("&inodot;", "\u{0131}"),
-    ("&infintie;", "\u{29DD}"),
-    ("&intcal;", "\u{22BA}"),
-    ("&int;", "\u{222B}"),
-    ("&intercal;", "\u{22BA}"),
-    ("&integers;", "\u{2124}"),
-    ("&intprod;", "\u{2A3C}"),
-    ("&intlarhk;", "\u{2A17}"),
-    ("&iogon;", "\u{012F}"),
-    ("&iocy;", "\u{0451}"),
-    ("&iota;", "\u{03B9}"),
-    ("&iopf;", "\u{1D55A}"),
-    ("&iquest", "\u{00BF}"),
-    ("&iprod;", "\u{2A3C}"),
-    ("&iscr;", "\u{1D4BE}"),
-    ("&iquest;", "\u{00BF}"),
-    ("&isinE;", "\u{22F9}"),
-    ("&isin;", "\u{2208}"),
-    ("&isins;", "\u{22F4}"),
-    ("&isindot;", "\u{22F5}"),
-    ("&isinv;", "\u{2208}"),
-    ("&isinsv;", "\u{22F3}"),
-    ("&itilde;", "\u{0129}"),
-    ("&it;", "\u{2062}"),
-    ("&iuml", "\u{00EF}"),
-    ("&iukcy;", "\u{0456}"),
-    ("&jcirc;", "\u{0135}"),
-    ("&iuml;", "\u{00EF}"),
-    ("&jfr;", "\u{1D527}"),
-    ("&jcy;", "\u{0439}"),
-    ("&jopf;", "\u{1D55B}"),
-    ("&jmath;", "\u{0237}"),
-    ("&jsercy;", "\u{0458}"),
-    ("&jscr;", "\u{1D4BF}"),
-    ("&kappa;", "\u{03BA}"),
-    ("&jukcy;", "\u{0454}"),
-    ("&kcedil;", "\u{0137}"),
-    ("&kappav;", "\u{03F0}"),
-    ("&kfr;", "\u{1D528}"),
-    ("&kcy;", "\u{043A}"),
-    ("&khcy;", "\u{0445}"),
-    ("&kgreen;", "\u{0138}"),
-    ("&kopf;", "\u{1D55C}"),
-    ("&kjcy;", "\u{045C}"),
-    ("&lAarr;", "\u{21DA}"),
-    ("&kscr;", "\u{1D4C0}"),
-    ("&lAtail;", "\u{291B}"),
-    ("&lArr;", "\u{21D0}"),
-    ("&lE;", "\u{2266}"),
-    ("&lBarr;", "\u{290E}"),
-    ("&lHar;", "\u{2962}"),
-    ("&lEg;", "\u{2A8B}"),
-    ("&laemptyv;", "\u{29B4}"),
-    ("&lacute;", "\u{013A}"),
-    ("&lambda;", "\u{03BB}"),
-    ("&lagran;", "\u{2112}"),
-    ("&langd;", "\u{2991}"),
-    ("&lang;", "\u{27E8}"),
-    ("&lap;", "\u{2A85}"),
-    ("&langle;", "\u{27E8}"),
-    ("&laquo;", "\u{00AB}"),
-    ("&laquo", "\u{00AB}"),
-    ("&larrb;", "\u{21E4}"),
-    ("&larr;", "\u{2190}"),
-    ("&larrfs;", "\u{291D}"),
-    ("&larrbfs;", "\u{291F}"),
-    ("&larrlp;", "\u{21AB}"),
-    ("&larrhk;", "\u{21A9}"),
-    ("&larrsim;", "\u{2973}"),
-    ("&larrpl;", "\u{2939}"),
-    ("&lat;", "\u{2AAB}"),
-    ("&larrtl;", "\u{21A2}"),
-    ("&late;", "\u{2AAD}"),
-    ("&latail;", "\u{2919}"),
-    ("&lbarr;", "\u{290C}"),
-    ("&lates;", "\u{2AAD}\u{FE00}"),
-    ("&lbrace;", "\u{007B}"),
-    ("&lbbrk;", "\u{2772}"),
-    ("&lbrke;", "\u{298B}"),
-    ("&lbrack;", "\u{005B}"),
-    ("&lbrkslu;", "\u{298D}"),
-    ("&lbrksld;", "\u{298F}"),
-    ("&lcedil;", "\u{013C}"),
-    ("&lcaron;", "\u{013E}"),
-    ("&lcub;", "\u{007B}"),
-    ("&lceil;", "\u{2308}"),
-    ("&ldca;", "\u{2936}"),
-    ("&lcy;", "\u{043B}"),
-    ("&ldquor;", "\u{201E}"),
-    ("&ldquo;", "\u{201C}"),
-    ("&ldrushar;", "\u{294B}"),
-    ("&ldrdhar;", "\u{2967}"),
-    ("&le;", "\u{2264}"),
-    ("&ldsh;", "\u{21B2}"),
-    ("&leftarrowtail;", "\u{21A2}"),
-    ("&leftarrow;", "\u{2190}"),
-    ("&leftharpoonup;", "\u{21BC}"),
-    ("&leftharpoondown;", "\u{21BD}"),
-    ("&leftrightarrow;", "\u{2194}"),
-    ("&leftleftarrows;", "\u{21C7}"),
-    ("&leftrightharpoons;", "\u{21CB}"),
-    ("&leftrightarrows;", "\u{21C6}"),
-    ("&leftthreetimes;", "\u{22CB}"),
-    ("&leftrightsquigarrow;", "\u{21AD}"),
-    ("&leq;", "\u{2264}"),
-    ("&leg;", "\u{22DA}"),
-    ("&leqslant;", "\u{2A7D}"),
-    ("&leqq;", "\u{2266}"),
-    ("&lescc;", "\u{2AA8}"),
-    ("&les;", "\u{2A7D}"),
-    ("&lesdoto;", "\u{2A81}"),
-    ("&lesdot;", "\u{2A7F}"),
-    ("&lesg;", "\u{22DA}\u{FE00}"),
-    ("&lesdotor;", "\u{2A83}"),
-    ("&lessapprox;", "\u{2A85}"),
-    ("&lesges;", "\u{2A93}"),
-    ("&lesseqgtr;", "\u{22DA}"),
-    ("&lessdot;", "\u{22D6}"),
-    ("&lessgtr;", "\u{2276}"),
-    ("&lesseqqgtr;", "\u{2A8B}"),
-    ("&lfisht;", "\u{297C}"),
-    ("&lesssim;", "\u{2272}"),
-    ("&lfr;", "\u{1D529}"),
-    ("&lfloor;", "\u{230A}"),
-    ("&lgE;", "\u{2A91}"),
-    ("&lg;", "\u{2276}"),
-    ("&lharu;", "\u{21BC}"),
-    ("&lhard;", "\u{21BD}"),
-    ("&lhblk;", "\u{2584}"),
-    ("&lharul;", "\u{296A}"),
-    ("&ll;", "\u{226A}"),
-    ("&ljcy;", "\u{0459}"),
-    ("&llcorner;", "\u{231E}"),
-    ("&llarr;", "\u{21C7}"),
-    ("&lltri;", "\u{25FA}"),
-    ("&llhard;", "\u{296B}"),
-    ("&lmoust;", "\u{23B0}"),
-    ("&lmidot;", "\u{0140}"),
-    ("&lnE;", "\u{2268}"),
-    ("&lmoustache;", "\u{23B0}"),
-    ("&lnapprox;", "\u{2A89}"),
-    ("&lnap;", "\u{2A89}"),
-    ("&lneq;", "\u{2A87}"),
-    ("&lne;", "\u{2A87}"),
-    ("&lnsim;", "\u{22E6}"),
-    ("&lneqq;", "\u{2268}"),
-    ("&loarr;", "\u{21FD}"),
-    ("&loang;", "\u{27EC}"),
-    ("&longleftarrow;", "\u{27F5}"),
-    ("&lobrk;", "\u{27E6}"),
-    ("&longmapsto;", "\u{27FC}"),
-    ("&longleftrightarrow;", "\u{27F7}"),
-    ("&looparrowleft;", "\u{21AB}"),
-    ("&longrightarrow;", "\u{27F6}"),
-    ("&lopar;", "\u{2985}"),
-    ("&looparrowright;", "\u{21AC}"),
-    ("&loplus;", "\u{2A2D}"),
-    ("&lopf;", "\u{1D55D}"),
-    ("&lowast;", "\u{2217}"),
-    ("&lotimes;", "\u{2A34}"),
-    ("&loz;", "\u{25CA}"),
-    ("&lowbar;", "\u{005F}"),
-    ("&lozf;", "\u{29EB}"),
-    ("&lozenge;", "\u{25CA}"),
-    ("&lparlt;", "\u{2993}"),
-    ("&lpar;", "\u{0028}"),
-    ("&lrcorner;", "\u{231F}"),
-    ("&lrarr;", "\u{21C6}"),
-    ("&lrhard;", "\u{296D}"),
-    ("&lrhar;", "\u{21CB}"),
-    ("&lrtri;", "\u{22BF}"),
-    ("&lrm;", "\u{200E}"),
-    ("&lscr;", "\u{1D4C1}"),
-    ("&lsaquo;", "\u{2039}"),
-    ("&lsim;", "\u{2272}"),
-    ("&lsh;", "\u{21B0}"),
-    ("&lsimg;", "\u{2A8F}"),
-    ("&lsime;", "\u{2A8D}"),
-    ("&lsquo;", "\u{2018}"),
-    ("&lsqb;", "\u{005B}"),
-    ("&lstrok;", "\u{0142}"),
-    ("&lsquor;", "\u{201A}"),
-    ("&lt;", "\u{003C}"),
-    ("&ltcir;", "\u{2A79}"),
-    ("&ltcc;", "\u{2AA6}"),
-    ("&lthree;", "\u{22CB}"),
-    ("&ltdot;", "\u{22D6}"),
-    ("&ltlarr;", "\u{2976}"),
-    ("&ltimes;", "\u{22C9}"),
-    ("&ltrPar;", "\u{2996}"),
-    ("&ltquest;", "\u{2A7B}"),
-    ("&ltrie;", "\u{22B4}"),
-    ("&ltri;", "\u{25C3}"),
-    ("&lurdshar;", "\u{294A}"),
-    ("&ltrif;", "\u{25C2}"),
-    ("&lvertneqq;", "\u{2268}\u{FE00}"),
-    ("&luruhar;", "\u{2966}"),
-    ("&mDDot;", "\u{223A}"),
-    ("&lvnE;", "\u{2268}\u{FE00}"),
-    ("&macr;", "\u{00AF}"),
-    ("&malt;", "\u{2720}"),
-    ("&male;", "\u{2642}"),
-    ("&map;", "\u{21A6}"),
-    ("&maltese;", "\u{2720}"),
-    ("&mapstodown;", "\u{21A7}"),
-    ("&mapsto;", "\u{21A6}"),
-    ("&mapstoup;", "\u{21A5}"),
-    ("&mapstoleft;", "\u{21A4}"),
-    ("&mcomma;", "\u{2A29}"),
-    ("&marker;", "\u{25AE}"),
-    ("&mdash;", "\u{2014}"),
-    ("&mcy;", "\u{043C}"),
-    ("&mfr;", "\u{1D52A}"),
-    ("&measuredangle;", "\u{2221}"),
-    ("&micro;", "\u{00B5}"),
-    ("&mho;", "\u{2127}"),
-    ("&mid;", "\u{2223}"),
-    ("&midcir;", "\u{2AF0}"),
-    ("&midast;", "\u{002A}"),
-    ("&middot;", "\u{00B7}"),
-    ("&minusb;", "\u{229F}"),
-    ("&minus;", "\u{2212}"),
-    ("&minusdu;", "\u{2A2A}"),
-    ("&minusd;", "\u{2238}"),
-    ("&mldr;", "\u{2026}"),
-    ("&mlcp;", "\u{2ADB}"),
-    ("&models;", "\u{22A7}"),
-    ("&mnplus;", "\u{2213}"),
-    ("&mp;", "\u{2213}"),
-    ("&mopf;", "\u{1D55E}"),
-    ("&mstpos;", "\u{223E}"),
-    ("&mscr;", "\u{1D4C2}"),
-    ("&multimap;", "\u{22B8}"),
-    ("&mu;", "\u{03BC}"),
-    ("&nGg;", "\u{22D9}\u{0338}"),
-    ("&mumap;", "\u{22B8}"),
-    ("&nGtv;", "\u{226B}\u{0338}"),
-    ("&nGt;", "\u{226B}\u{20D2}"),
-    ("&nLeftrightarrow;", "\u{21CE}"),
-    ("&nLeftarrow;", "\u{21CD}"),
-    ("&nLt;", "\u{226A}\u{20D2}"),
-    ("&nLl;", "\u{22D8}\u{0338}"),
-    ("&nRightarrow;", "\u{21CF}"),
-    ("&nLtv;", "\u{226A}\u{0338}"),
-    ("&nVdash;", "\u{22AE}"),
-    ("&nVDash;", "\u{22AF}"),
-    ("&nacute;", "\u{0144}"),
-    ("&nabla;", "\u{2207}"),
-    ("&nap;", "\u{2249}"),
-    ("&nang;", "\u{2220}\u{20D2}"),
-    ("&napid;", "\u{224B}\u{0338}"),
-    ("&napE;", "\u{2A70}\u{0338}"),
-    ("&napprox;", "\u{2249}"),
-    ("&napos;", "\u{0149}"),
-    ("&natural;", "\u{266E}"),
-    ("&natur;", "\u{266E}"),
-    ("&nbsp;", "\u{00A0}"),
-    ("&naturals;", "\u{2115}"),
-    ("&nbump;", "\u{224E}\u{0338}"),
-    ("&ncap;", "\u{2A43}"),
-    ("&nbumpe;", "\u{224F}\u{0338}"),
-    ("&ncedil;", "\u{0146}"),
-    ("&ncaron;", "\u{0148}"),
-    ("&ncongdot;", "\u{2A6D}\u{0338}"),
-    ("&ncong;", "\u{2247}"),
-    ("&ncy;", "\u{043D}"),
-    ("&ncup;", "\u{2A42}"),
-    ("&ne;", "\u{2260}"),
-    ("&ndash;", "\u{2013}"),
-    ("&nearhk;", "\u{2924}"),
-    ("&neArr;", "\u{21D7}"),
-    ("&nearrow;", "\u{2197}"),
-    ("&nearr;", "\u{2197}"),
-    ("&nequiv;", "\u{2262}"),
-    ("&nedot;", "\u{2250}\u{0338}"),
-    ("&nesim;", "\u{2242}\u{0338}"),
-    ("&nesear;", "\u{2928}"),
-    ("&nexists;", "\u{2204}"),
-    ("&nexist;", "\u{2204}"),
-    ("&ngE;", "\u{2267}\u{0338}"),
-    ("&nfr;", "\u{1D52B}"),
-    ("&ngeq;", "\u{2271}"),
-    ("&nge;", "\u{2271}"),
-    ("&ngeqslant;", "\u{2A7E}\u{0338}"),
-    ("&ngeqq;", "\u{2267}\u{0338}"),
-    ("&ngsim;", "\u{2275}"),
-    ("&nges;", "\u{2A7E}\u{0338}"),
-    ("&ngtr;", "\u{226F}"),
-    ("&ngt;", "\u{226F}"),
-    ("&nharr;", "\u{21AE}"),
-    ("&nhArr;", "\u{21CE}"),
-    ("&ni;", "\u{220B}"),
-    ("&nhpar;", "\u{2AF2}"),
-    ("&nisd;", "\u{22FA}"),
-    ("&nis;", "\u{22FC}"),
-    ("&njcy;", "\u{045A}"),
-    ("&niv;", "\u{220B}"),
-    ("&nlE;", "\u{2266}\u{0338}"),
-    ("&nlArr;", "\u{21CD}"),
-    ("&nldr;", "\u{2025}"),
-    ("&nlarr;", "\u{219A}"),
-    ("&nleftarrow;", "\u{219A}"),
-    ("&nle;", "\u{2270}"),
-    ("&nleq;", "\u{2270}"),
-    ("&nleftrightarrow;", "\u{21AE}"),
-    ("&nleqslant;", "\u{2A7D}\u{0338}"),
-    ("&nleqq;", "\u{2266}\u{0338}"),
-    ("&nless;", "\u{226E}"),
-    ("&nles;", "\u{2A7D}\u{0338}"),
-    ("&nlt;", "\u{226E}"),
-    ("&nlsim;", "\u{2274}"),
-    ("&nltrie;", "\u{22EC}"),
-    ("&nltri;", "\u{22EA}"),
-    ("&nopf;", "\u{1D55F}"),
-    ("&nmid;", "\u{2224}"),
-    ("&not;", "\u{00AC}"),
-    ("&not", "\u{00AC}"),
-    ("&notinE;", "\u{22F9}\u{0338}"),
-    ("&notin;", "\u{2209}"),
-    ("&notinva;", "\u{2209}"),
-    ("&notindot;", "\u{22F5}\u{0338}"),
-    ("&notinvc;", "\u{22F6}"),
-    ("&notinvb;", "\u{22F7}"),
-    ("&notniva;", "\u{220C}"),
-    ("&notni;", "\u{220C}"),
-    ("&notnivc;", "\u{22FD}"),
-    ("&notnivb;", "\u{22FE}"),
-    ("&nparallel;", "\u{2226}"),
-    ("&npar;", "\u{2226}"),
-    ("&npart;", "\u{2202}\u{0338}"),
-    ("&nparsl;", "\u{2AFD}\u{20E5}"),
-    ("&npr;", "\u{2280}"),
-    ("&npolint;", "\u{2A14}"),
-    ("&npre;", "\u{2AAF}\u{0338}"),
-    ("&nprcue;", "\u{22E0}"),
-    ("&npreceq;", "\u{2AAF}\u{0338}"),
-    ("&nprec;", "\u{2280}"),
-    ("&nrarr;", "\u{219B}"),
-    ("&nrArr;", "\u{21CF}"),
-    ("&nrarrw;", "\u{219D}\u{0338}"),
-    ("&nrarrc;", "\u{2933}\u{0338}"),
-    ("&nrtri;", "\u{22EB}"),
-    ("&nrightarrow;", "\u{219B}"),
-    ("&nsc;", "\u{2281}"),
-    ("&nrtrie;", "\u{22ED}"),
-    ("&nsce;", "\u{2AB0}\u{0338}"),
-    ("&nsccue;", "\u{22E1}"),
-    ("&nshortmid;", "\u{2224}"),
-    ("&nscr;", "\u{1D4C3}"),
-    ("&nsim;", "\u{2241}"),
-    ("&nshortparallel;", "\u{2226}"),
-    ("&nsimeq;", "\u{2244}"),
-    ("&nsime;", "\u{2244}"),
-    ("&nspar;", "\u{2226}"),
-    ("&nsmid;", "\u{2224}"),
-    ("&nsqsupe;", "\u{22E3}"),
-    ("&nsqsube;", "\u{22E2}"),
-    ("&nsubE;", "\u{2AC5}\u{0338}"),
-    ("&nsub;", "\u{2284}"),
-    ("&nsubset;", "\u{2282}\u{20D2}"),
-    ("&nsube;", "\u{2288}"),
-    ("&nsubseteqq;", "\u{2AC5}\u{0338}"),
-    ("&nsubseteq;", "\u{2288}"),
-    ("&nsucceq;", "\u{2AB0}\u{0338}"),
-    ("&nsucc;", "\u{2281}"),
-    ("&nsupE;", "\u{2AC6}\u{0338}"),
-    ("&nsup;", "\u{2285}"),
-    ("&nsupset;", "\u{2283}\u{20D2}"),
-    ("&nsupe;", "\u{2289}"),
-    ("&nsupseteqq;", "\u{2AC6}\u{0338}"),
-    ("&ntilde", "\u{00F1}"),
-    ("&ntgl;", "\u{2279}"),
-    ("&ntlg;", "\u{2278}"),
-    ("&ntilde;", "\u{00F1}"),
-    ("&ntrianglelefteq;", "\u{22EC}"),
-    ("&ntriangleleft;", "\u{22EA}"),
-    ("&ntrianglerighteq;", "\u{22ED}"),
-    ("&ntriangleright;", "\u{22EB}"),
-    ("&num;", "\u{0023}"),
-    ("&nu;", "\u{03BD}"),
-    ("&numsp;", "\u{2007}"),
-    ("&numero;", "\u{2116}"),
-    ("&nvHarr;", "\u{2904}"),
-    ("&nvDash;", "\u{22AD}"),
-    ("&nvdash;", "\u{22AC}"),
-    ("&nvap;", "\u{224D}\u{20D2}"),
-    ("&nvgt;", "\u{003E}\u{20D2}"),
-    ("&nvge;", "\u{2265}\u{20D2}"),
-    ("&nvlArr;", "\u{2902}"),
-    ("&nvinfin;", "\u{29DE}"),
-    ("&nvlt;", "\u{003C}\u{20D2}"),
-    ("&nvle;", "\u{2264}\u{20D2}"),
-    ("&nvrArr;", "\u{2903}"),
-    ("&nvltrie;", "\u{22B4}\u{20D2}"),
-    ("&nvsim;", "\u{223C}\u{20D2}"),
-    ("&nvrtrie;", "\u{22B5}\u{20D2}"),
-    ("&nwarhk;", "\u{2923}"),
-    ("&nwArr;", "\u{21D6}"),
-    ("&nwarrow;", "\u{2196}"),
-    ("&nwarr;", "\u{2196}"),
-    ("&oS;", "\u{24C8}"),
-    ("&nwnear;", "\u{2927}"),
-    ("&oacute;", "\u{00F3}"),
-    ("&oacute", "\u{00F3}"),
-    ("&ocir;", "\u{229A}"),
-    ("&oast;", "\u{229B}"),
-    ("&ocirc;", "\u{00F4}"),
-    ("&ocirc", "\u{00F4}"),
-    ("&odash;", "\u{229D}"),
-    ("&ocy;", "\u{043E}"),
-    ("&odiv;", "\u{2A38}"),
-    ("&odblac;", "\u{0151}"),
-    ("&odsold;", "\u{29BC}"),
-    ("&odot;", "\u{2299}"),
-    ("&ofcir;", "\u{29BF}"),
-    ("&oelig;", "\u{0153}"),
-    ("&ogon;", "\u{02DB}"),
-    ("&ofr;", "\u{1D52C}"),
-    ("&ograve;", "\u{00F2}"),
-    ("&ograve", "\u{00F2}"),
-    ("&ohbar;", "\u{29B5}"),
-    ("&ogt;", "\u{29C1}"),
-    ("&oint;", "\u{222E}"),
-    ("&ohm;", "\u{03A9}"),
-    ("&olcir;", "\u{29BE}"),
-    ("&olarr;", "\u{21BA}"),
-    ("&oline;", "\u{203E}"),
-    ("&olcross;", "\u{29BB}"),
-    ("&omacr;", "\u{014D}"),
-    ("&olt;", "\u{29C0}"),
-    ("&omicron;", "\u{03BF}"),
-    ("&omega;", "\u{03C9}"),
-    ("&ominus;", "\u{2296}"),
-    ("&omid;", "\u{29B6}"),
-    ("&opar;", "\u{29B7}"),
-    ("&oopf;", "\u{1D560}"),
-    ("&oplus;", "\u{2295}"),
-    ("&operp;", "\u{29B9}"),
-    ("&orarr;", "\u{21BB}"),
-    ("&or;", "\u{2228}"),
-    ("&order;", "\u{2134}"),
-    ("&ord;", "\u{2A5D}"),
-    ("&ordf", "\u{00AA}"),
-    ("&orderof;", "\u{2134}"),
-    ("&ordm", "\u{00BA}"),
-    ("&ordf;", "\u{00AA}"),
-    ("&origof;", "\u{22B6}"),
-    ("&ordm;", "\u{00BA}"),
-    ("&orslope;", "\u{2A57}"),
-    ("&oror;", "\u{2A56}"),
-    ("&oscr;", "\u{2134}"),
-    ("&orv;", "\u{2A5B}"),
-    ("&oslash;", "\u{00F8}"),
-    ("&oslash", "\u{00F8}"),
-    ("&otilde", "\u{00F5}"),
-    ("&osol;", "\u{2298}"),
-    ("&otimes;", "\u{2297}"),
-    ("&otilde;", "\u{00F5}"),
-    ("&ouml", "\u{00F6}"),
-    ("&otimesas;", "\u{2A36}"),
-    ("&ovbar;", "\u{233D}"),
-    ("&ouml;", "\u{00F6}"),
-    ("&para", "\u{00B6}"),
-    ("&par;", "\u{2225}"),
-    ("&parallel;", "\u{2225}"),
-    ("&para;", "\u{00B6}"),
-    ("&parsl;", "\u{2AFD}"),
-    ("&parsim;", "\u{2AF3}"),
-    ("&pcy;", "\u{043F}"),
-    ("&part;", "\u{2202}"),
-    ("&period;", "\u{002E}"),
-    ("&percnt;", "\u{0025}"),
-    ("&perp;", "\u{22A5}"),
-    ("&permil;", "\u{2030}"),
-    ("&pfr;", "\u{1D52D}"),
-    ("&pertenk;", "\u{2031}"),
-    ("&phiv;", "\u{03D5}"),
-    ("&phi;", "\u{03C6}"),
-    ("&phone;", "\u{260E}"),
-    ("&phmmat;", "\u{2133}"),
-    ("&pitchfork;", "\u{22D4}"),
-    ("&pi;", "\u{03C0}"),
-    ("&planck;", "\u{210F}"),
-    ("&piv;", "\u{03D6}"),
-    ("&plankv;", "\u{210F}"),
-    ("&planckh;", "\u{210E}"),
-    ("&plusacir;", "\u{2A23}"),
-    ("&plus;", "\u{002B}"),
-    ("&pluscir;", "\u{2A22}"),
-    ("&plusb;", "\u{229E}"),
-    ("&plusdu;", "\u{2A25}"),
-    ("&plusdo;", "\u{2214}"),
-    ("&plusmn", "\u{00B1}"),
-    ("&pluse;", "\u{2A72}"),
-    ("&plussim;", "\u{2A26}"),
-    ("&plusmn;", "\u{00B1}"),
-    ("&pm;", "\u{00B1}"),
-    ("&plustwo;", "\u{2A27}"),
-    ("&popf;", "\u{1D561}"),
-    ("&pointint;", "\u{2A15}"),
-    ("&pound;", "\u{00A3}"),
-    ("&pound", "\u{00A3}"),
-    ("&prE;", "\u{2AB3}"),
-    ("&pr;", "\u{227A}"),
-    ("&prcue;", "\u{227C}"),
-    ("&prap;", "\u{2AB7}"),
-    ("&prec;", "\u{227A}"),
-    ("&pre;", "\u{2AAF}"),
-    ("&preccurlyeq;", "\u{227C}"),
-    ("&precapprox;", "\u{2AB7}"),
-    ("&precnapprox;", "\u{2AB9}"),
-    ("&preceq;", "\u{2AAF}"),
-    ("&precnsim;", "\u{22E8}"),
-    ("&precneqq;", "\u{2AB5}"),
-    ("&prime;", "\u{2032}"),
-    ("&precsim;", "\u{227E}"),
-    ("&prnE;", "\u{2AB5}"),
-    ("&primes;", "\u{2119}"),
-    ("&prnsim;", "\u{22E8}"),
-    ("&prnap;", "\u{2AB9}"),
-    ("&profalar;", "\u{232E}"),
-    ("&prod;", "\u{220F}"),
-    ("&profsurf;", "\u{2313}"),
-    ("&profline;", "\u{2312}"),
-    ("&propto;", "\u{221D}"),
-    ("&prop;", "\u{221D}"),
-    ("&prurel;", "\u{22B0}"),
-    ("&prsim;", "\u{227E}"),
-    ("&psi;", "\u{03C8}"),
-    ("&pscr;", "\u{1D4C5}"),
-    ("&qfr;", "\u{1D52E}"),
-    ("&puncsp;", "\u{2008}"),
-    ("&qopf;", "\u{1D562}"),
-    ("&qint;", "\u{2A0C}"),
-    ("&qscr;", "\u{1D4C6}"),
-    ("&qprime;", "\u{2057}"),
-    ("&quatint;", "\u{2A16}"),
-    ("&quaternions;", "\u{210D}"),
-    ("&questeq;", "\u{225F}"),
-    ("&quest;", "\u{003F}"),
-    ("&quot;", "\u{0022}"),
-    ("&quot", "\u{0022}"),
-    ("&rArr;", "\u{21D2}"),
-    ("&rAarr;", "\u{21DB}"),
-    ("&rBarr;", "\u{290F}"),
-    ("&rAtail;", "\u{291C}"),
-    ("&race;", "\u{223D}\u{0331}"),
-    ("&rHar;", "\u{2964}"),
-    ("&radic;", "\u{221A}"),
-    ("&racute;", "\u{0155}"),
-    ("&rang;", "\u{27E9}"),
-    ("&raemptyv;", "\u{29B3}"),
-    ("&range;", "\u{29A5}"),
-    ("&rangd;", "\u{2992}"),
-    ("&raquo", "\u{00BB}"),
-    ("&rangle;", "\u{27E9}"),
-    ("&rarr;", "\u{2192}"),
-    ("&raquo;", "\u{00BB}"),
-    ("&rarrb;", "\u{21E5}"),
-    ("&rarrap;", "\u{2975}"),
-    ("&rarrc;", "\u{2933}"),
-    ("&rarrbfs;", "\u{2920}"),
-    ("&rarrhk;", "\u{21AA}"),
-    ("&rarrfs;", "\u{291E}"),
-    ("&rarrpl;", "\u{2945}"),
-    ("&rarrlp;", "\u{21AC}"),
-    ("&rarrtl;", "\u{21A3}"),
-    ("&rarrsim;", "\u{2974}"),
-    ("&ratail;", "\u{291A}"),
-    ("&rarrw;", "\u{219D}"),
-    ("&rationals;", "\u{211A}"),
-    ("&ratio;", "\u{2236}"),
-    ("&rbbrk;", "\u{2773}"),
-    ("&rbarr;", "\u{290D}"),
-    ("&rbrack;", "\u{005D}"),
-    ("&rbrace;", "\u{007D}"),
-    ("&rbrksld;", "\u{298E}"),
-    ("&rbrke;", "\u{298C}"),
-    ("&rcaron;", "\u{0159}"),
-    ("&rbrkslu;", "\u{2990}"),
-    ("&rceil;", "\u{2309}"),
-    ("&rcedil;", "\u{0157}"),
-    ("&rcy;", "\u{0440}"),
-    ("&rcub;", "\u{007D}"),
-    ("&rdldhar;", "\u{2969}"),
-    ("&rdca;", "\u{2937}"),
-    ("&rdquor;", "\u{201D}"),
-    ("&rdquo;", "\u{201D}"),
-    ("&real;", "\u{211C}"),
-    ("&rdsh;", "\u{21B3}"),
-    ("&realpart;", "\u{211C}"),
-    ("&realine;", "\u{211B}"),
-    ("&rect;", "\u{25AD}"),
-    ("&reals;", "\u{211D}"),
-    ("&reg;", "\u{00AE}"),
-    ("&reg", "\u{00AE}"),
-    ("&rfloor;", "\u{230B}"),
-    ("&rfisht;", "\u{297D}"),
-    ("&rhard;", "\u{21C1}"),
-    ("&rfr;", "\u{1D52F}"),
-    ("&rharul;", "\u{296C}"),
-    ("&rharu;", "\u{21C0}"),
-    ("&rhov;", "\u{03F1}"),
-    ("&rho;", "\u{03C1}"),
-    ("&rightarrowtail;", "\u{21A3}"),
-    ("&rightarrow;", "\u{2192}"),
-    ("&rightharpoonup;", "\u{21C0}"),
-    ("&rightharpoondown;", "\u{21C1}"),
-    ("&rightleftharpoons;", "\u{21CC}"),
-    ("&rightleftarrows;", "\u{21C4}"),
-    ("&rightsquigarrow;", "\u{219D}"),
-    ("&rightrightarrows;", "\u{21C9}"),
-    ("&ring;", "\u{02DA}"),
-    ("&rightthreetimes;", "\u{22CC}"),
-    ("&rlarr;", "\u{21C4}"),
-    ("&risingdotseq;", "\u{2253}"),
-    ("&rlm;", "\u{200F}"),
-    ("&rlhar;", "\u{21CC}"),
-    ("&rmoustache;", "\u{23B1}"),
-    ("&rmoust;", "\u{23B1}"),
-    ("&roang;", "\u{27ED}"),
-    ("&rnmid;", "\u{2AEE}"),
-    ("&robrk;", "\u{27E7}"),
-    ("&roarr;", "\u{21FE}"),
-    ("&ropf;", "\u{1D563}"),
-    ("&ropar;", "\u{2986}"),
-    ("&rotimes;", "\u{2A35}"),
-    ("&roplus;", "\u{2A2E}"),
-    ("&rpargt;", "\u{2994}"),
-    ("&rpar;", "\u{0029}"),
-    ("&rrarr;", "\u{21C9}"),
-    ("&rppolint;", "\u{2A12}"),
-    ("&rscr;", "\u{1D4C7}"),
-    ("&rsaquo;", "\u{203A}"),
-    ("&rsqb;", "\u{005D}"),
-    ("&rsh;", "\u{21B1}"),
-    ("&rsquor;", "\u{2019}"),
-    ("&rsquo;", "\u{2019}"),
-    ("&rtimes;", "\u{22CA}"),
-    ("&rthree;", "\u{22CC}"),
-    ("&rtrie;", "\u{22B5}"),
-    ("&rtri;", "\u{25B9}"),
-    ("&rtriltri;", "\u{29CE}"),
-    ("&rtrif;", "\u{25B8}"),
-    ("&rx;", "\u{211E}"),
-    ("&ruluhar;", "\u{2968}"),
-    ("&sbquo;", "\u{201A}"),
-    ("&sacute;", "\u{015B}"),
-    ("&scE;", "\u{2AB4}"),
-    ("&sc;", "\u{227B}"),
-    ("&scaron;", "\u{0161}"),
-    ("&scap;", "\u{2AB8}"),
-    ("&sce;", "\u{2AB0}"),
-    ("&sccue;", "\u{227D}"),
-    ("&scirc;", "\u{015D}"),
-    ("&scedil;", "\u{015F}"),
-    ("&scnap;", "\u{2ABA}"),
-    ("&scnE;", "\u{2AB6}"),
-    ("&scpolint;", "\u{2A13}"),
-    ("&scnsim;", "\u{22E9}"),
-    ("&scy;", "\u{0441}"),
-    ("&scsim;", "\u{227F}"),
-    ("&sdotb;", "\u{22A1}"),
-    ("&sdot;", "\u{22C5}"),
-    ("&seArr;", "\u{21D8}"),
-    ("&sdote;", "\u{2A66}"),
-    ("&searr;", "\u{2198}"),
-    ("&searhk;", "\u{2925}"),
-    ("&sect", "\u{00A7}"),
-    ("&searrow;", "\u{2198}"),
-    ("&semi;", "\u{003B}"),
-    ("&sect;", "\u{00A7}"),
-    ("&setminus;", "\u{2216}"),
-    ("&seswar;", "\u{2929}"),
-    ("&sext;", "\u{2736}"),
-    ("&setmn;", "\u{2216}"),
-    ("&sfrown;", "\u{2322}"),
-    ("&sfr;", "\u{1D530}"),
-    ("&shchcy;", "\u{0449}"),
-    ("&sharp;", "\u{266F}"),
-    ("&shortmid;", "\u{2223}"),
-    ("&shcy;", "\u{0448}"),
-    ("&shy", "\u{00AD}"),
-    ("&shortparallel;", "\u{2225}"),
-    ("&sigma;", "\u{03C3}"),
-    ("&shy;", "\u{00AD}"),
-    ("&sigmav;", "\u{03C2}"),
-    ("&sigmaf;", "\u{03C2}"),
-    ("&simdot;", "\u{2A6A}"),
-    ("&sim;", "\u{223C}"),
-    ("&simeq;", "\u{2243}"),
-    ("&sime;", "\u{2243}"),
-    ("&simgE;", "\u{2AA0}"),
-    ("&simg;", "\u{2A9E}"),
-    ("&simlE;", "\u{2A9F}"),
-    ("&siml;", "\u{2A9D}"),
-    ("&simplus;", "\u{2A24}"),
-    ("&simne;", "\u{2246}"),
-    ("&slarr;", "\u{2190}"),
-    ("&simrarr;", "\u{2972}"),
-    ("&smashp;", "\u{2A33}"),
-    ("&smallsetminus;", "\u{2216}"),
-    ("&smid;", "\u{2223}"),
-    ("&smeparsl;", "\u{29E4}"),
-    ("&smt;", "\u{2AAA}"),
-    ("&smile;", "\u{2323}"),
-    ("&smtes;", "\u{2AAC}\u{FE00}"),
-    ("&smte;", "\u{2AAC}"),
-    ("&sol;", "\u{002F}"),
-    ("&softcy;", "\u{044C}"),
-    ("&solbar;", "\u{233F}"),
-    ("&solb;", "\u{29C4}"),
-    ("&spades;", "\u{2660}"),
-    ("&sopf;", "\u{1D564}"),
-    ("&spar;", "\u{2225}"),
-    ("&spadesuit;", "\u{2660}"),
-    ("&sqcaps;", "\u{2293}\u{FE00}"),
-    ("&sqcap;", "\u{2293}"),
-    ("&sqcups;", "\u{2294}\u{FE00}"),
-    ("&sqcup;", "\u{2294}"),
-    ("&sqsube;", "\u{2291}"),
-    ("&sqsub;", "\u{228F}"),
-    ("&sqsubseteq;", "\u{2291}"),
-    ("&sqsubset;", "\u{228F}"),
-    ("&sqsupe;", "\u{2292}"),
-    ("&sqsup;", "\u{2290}"),
-    ("&sqsupseteq;", "\u{2292}"),
-    ("&sqsupset;", "\u{2290}"),
-    ("&square;", "\u{25A1}"),
-    ("&squ;", "\u{25A1}"),
-    ("&squf;", "\u{25AA}"),
-    ("&squarf;", "\u{25AA}"),
-    ("&sscr;", "\u{1D4C8}"),
-    ("&srarr;", "\u{2192}"),
-    ("&ssmile;", "\u{2323}"),
-    ("&ssetmn;", "\u{2216}"),
-    ("&star;", "\u{2606}"),
-    ("&sstarf;", "\u{22C6}"),
-    ("&straightepsilon;", "\u{03F5}"),
-    ("&starf;", "\u{2605}"),
-    ("&strns;", "\u{00AF}"),
-    ("&straightphi;", "\u{03D5}"),
-    ("&subE;", "\u{2AC5}"),
-    ("&sub;", "\u{2282}"),
-    ("&sube;", "\u{2286}"),
-    ("&subdot;", "\u{2ABD}"),
-    ("&submult;", "\u{2AC1}"),
-    ("&subedot;", "\u{2AC3}"),
-    ("&subne;", "\u{228A}"),
-    ("&subnE;", "\u{2ACB}"),
-    ("&subrarr;", "\u{2979}"),
-    ("&subplus;", "\u{2ABF}"),
-    ("&subseteq;", "\u{2286}"),
-    ("&subset;", "\u{2282}"),
-    ("&subsetneq;", "\u{228A}"),
-    ("&subseteqq;", "\u{2AC5}"),
-    ("&subsim;", "\u{2AC7}"),
-    ("&subsetneqq;", "\u{2ACB}"),
-    ("&subsup;", "\u{2AD3}"),
-    ("&subsub;", "\u{2AD5}"),
-    ("&succapprox;", "\u{2AB8}"),
-    ("&succ;", "\u{227B}"),
-    ("&succeq;", "\u{2AB0}"),
-    ("&succcurlyeq;", "\u{227D}"),
-    ("&succneqq;", "\u{2AB6}"),
-    ("&succnapprox;", "\u{2ABA}"),
-    ("&succsim;", "\u{227F}"),
-    ("&succnsim;", "\u{22E9}"),
-    ("&sung;", "\u{266A}"),
-    ("&sum;", "\u{2211}"),
-    ("&sup1;", "\u{00B9}"),
-    ("&sup2;", "\u{00B2}"),
-    ("&sup3;", "\u{00B3}"),
-    ("&supE;", "\u{2AC6}"),
-    ("&sup;", "\u{2283}"),
-    ("&supdsub;", "\u{2AD8}"),
-    ("&supdot;", "\u{2ABE}"),
-    ("&supedot;", "\u{2AC4}"),
-    ("&supe;", "\u{2287}"),
-    ("&suphsub;", "\u{2AD7}"),
-    ("&suphsol;", "\u{27C9}"),
-    ("&supmult;", "\u{2AC2}"),
-    ("&suplarr;", "\u{297B}"),
-    ("&supne;", "\u{228B}"),
-    ("&supnE;", "\u{2ACC}"),
-    ("&supset;", "\u{2283}"),
-    ("&supplus;", "\u{2AC0}"),
-    ("&supseteqq;", "\u{2AC6}"),
-    ("&supseteq;", "\u{2287}"),
-    ("&supsetneqq;", "\u{2ACC}"),
-    ("&supsetneq;", "\u{228B}"),
-    ("&supsub;", "\u{2AD4}"),
-    ("&supsim;", "\u{2AC8}"),
-    ("&swArr;", "\u{21D9}"),
-    ("&supsup;", "\u{2AD6}"),
-    ("&swarr;", "\u{2199}"),
-    ("&swarhk;", "\u{2926}"),
-    ("&swnwar;", "\u{292A}"),
-    ("&swarrow;", "\u{2199}"),
-    ("&szlig;", "\u{00DF}"),
-    ("&tau;", "\u{03C4}"),
-    ("&target;", "\u{2316}"),
-    ("&tcaron;", "\u{0165}"),
-    ("&tbrk;", "\u{23B4}"),
-    ("&tcy;", "\u{0442}"),
-    ("&tcedil;", "\u{0163}"),
-    ("&telrec;", "\u{2315}"),
-    ("&tdot;", "\u{20DB}"),
-    ("&there4;", "\u{2234}"),
-    ("&tfr;", "\u{1D531}"),
-    ("&theta;", "\u{03B8}"),
-    ("&therefore;", "\u{2234}"),
-    ("&thetav;", "\u{03D1}"),
-    ("&thetasym;", "\u{03D1}"),
-    ("&thicksim;", "\u{223C}"),
-    ("&thickapprox;", "\u{2248}"),
-    ("&thkap;", "\u{2248}"),
-    ("&thinsp;", "\u{2009}"),
-    ("&thorn", "\u{00FE}"),
-    ("&thksim;", "\u{223C}"),
-    ("&tilde;", "\u{02DC}"),
-    ("&thorn;", "\u{00FE}"),
-    ("&times;", "\u{00D7}"),
-    ("&times", "\u{00D7}"),
-    ("&timesbar;", "\u{2A31}"),
-    ("&timesb;", "\u{22A0}"),
-    ("&tint;", "\u{222D}"),
-    ("&timesd;", "\u{2A30}"),
-    ("&top;", "\u{22A4}"),
-    ("&toea;", "\u{2928}"),
-    ("&topcir;", "\u{2AF1}"),
-    ("&topbot;", "\u{2336}"),
-    ("&topfork;", "\u{2ADA}"),
-    ("&topf;", "\u{1D565}"),
-    ("&tprime;", "\u{2034}"),
-    ("&tosa;", "\u{2929}"),
-    ("&triangle;", "\u{25B5}"),
-    ("&trade;", "\u{2122}"),
-    ("&triangleleft;", "\u{25C3}"),
-    ("&triangledown;", "\u{25BF}"),
-    ("&triangleq;", "\u{225C}"),
-    ("&trianglelefteq;", "\u{22B4}"),
-    ("&trianglerighteq;", "\u{22B5}"),
-    ("&triangleright;", "\u{25B9}"),
-    ("&trie;", "\u{225C}"),
-    ("&tridot;", "\u{25EC}"),
-    ("&triplus;", "\u{2A39}"),
-    ("&triminus;", "\u{2A3A}"),
-    ("&tritime;", "\u{2A3B}"),
-    ("&trisb;", "\u{29CD}"),
-    ("&tscr;", "\u{1D4C9}"),
-    ("&trpezium;", "\u{23E2}"),
-    ("&tshcy;", "\u{045B}"),
-    ("&tscy;", "\u{0446}"),
-    ("&twixt;", "\u{226C}"),
-    ("&tstrok;", "\u{0167}"),
-    ("&twoheadrightarrow;", "\u{21A0}"),
-    ("&twoheadleftarrow;", "\u{219E}"),
-    ("&uHar;", "\u{2963}"),
-    ("&uArr;", "\u{21D1}"),
-    ("&uacute;", "\u{00FA}"),
-    ("&uacute", "\u{00FA}"),
-    ("&ubrcy;", "\u{045E}"),
-    ("&uarr;", "\u{2191}"),
-    ("&ucirc", "\u{00FB}"),
-    ("&ubreve;", "\u{016D}"),
-    ("&ucy;", "\u{0443}"),
-    ("&ucirc;", "\u{00FB}"),
-    ("&udblac;", "\u{0171}"),
-    ("&udarr;", "\u{21C5}"),
-    ("&ufisht;", "\u{297E}"),
-    ("&udhar;", "\u{296E}"),
-    ("&ugrave", "\u{00F9}"),
-    ("&ufr;", "\u{1D532}"),
-    ("&uharl;", "\u{21BF}"),
-    ("&ugrave;", "\u{00F9}"),
-    ("&uhblk;", "\u{2580}"),
-    ("&uharr;", "\u{21BE}"),
-    ("&ulcorner;", "\u{231C}"),
-    ("&ulcorn;", "\u{231C}"),
-    ("&ultri;", "\u{25F8}"),
-    ("&ulcrop;", "\u{230F}"),
-    ("&uml", "\u{00A8}"),
-    ("&umacr;", "\u{016B}"),
-    ("&uogon;", "\u{0173}"),
-    ("&uml;", "\u{00A8}"),
-    ("&uparrow;", "\u{2191}"),
-    ("&uopf;", "\u{1D566}"),
-    ("&upharpoonleft;", "\u{21BF}"),
-    ("&updownarrow;", "\u{2195}"),
-    ("&uplus;", "\u{228E}"),
-    ("&upharpoonright;", "\u{21BE}"),
-    ("&upsih;", "\u{03D2}"),
-    ("&upsi;", "\u{03C5}"),
-    ("&upuparrows;", "\u{21C8}"),
-    ("&upsilon;", "\u{03C5}"),
-    ("&urcorner;", "\u{231D}"),
-    ("&urcorn;", "\u{231D}"),
-    ("&uring;", "\u{016F}"),
-    ("&urcrop;", "\u{230E}"),
-    ("&uscr;", "\u{1D4CA}"),
-    ("&urtri;", "\u{25F9}"),
-    ("&utilde;", "\u{0169}"),
-    ("&utdot;", "\u{22F0}"),
-    ("&utrif;", "\u{25B4}"),
-    ("&utri;", "\u{25B5}"),
-    ("&uuml", "\u{00FC}"),
-    ("&uuarr;", "\u{21C8}"),
-    ("&uwangle;", "\u{29A7}"),
-    ("&uuml;", "\u{00FC}"),
-    ("&vBar;", "\u{2AE8}"),
-    ("&vArr;", "\u{21D5}"),
-    ("&vDash;", "\u{22A8}"),
-    ("&vBarv;", "\u{2AE9}"),
-    ("&varepsilon;", "\u{03F5}"),
-    ("&vangrt;", "\u{299C}"),
-    ("&varnothing;", "\u{2205}"),
-    ("&varkappa;", "\u{03F0}"),
-    ("&varpi;", "\u{03D6}"),
-    ("&varphi;", "\u{03D5}"),
-    ("&varr;", "\u{2195}"),
-    ("&varpropto;", "\u{221D}"),
-    ("&varsigma;", "\u{03C2}"),
-    ("&varrho;", "\u{03F1}"),
-    ("&varsubsetneqq;", "\u{2ACB}\u{FE00}"),
-    ("&varsubsetneq;", "\u{228A}\u{FE00}"),
-    ("&varsupsetneqq;", "\u{2ACC}\u{FE00}"),
-    ("&varsupsetneq;", "\u{228B}\u{FE00}"),
-    ("&vartriangleleft;", "\u{22B2}"),
-    ("&vartheta;", "\u{03D1}"),
-    ("&vcy;", "\u{0432}"),
-    ("&vartriangleright;", "\u{22B3}"),
-    ("&vee;", "\u{2228}"),
-    ("&vdash;", "\u{22A2}"),
-    ("&veeeq;", "\u{225A}"),
-    ("&veebar;", "\u{22BB}"),
-    ("&verbar;", "\u{007C}"),
-    ("&vellip;", "\u{22EE}"),
-    ("&vfr;", "\u{1D533}"),
-    ("&vert;", "\u{007C}"),
-    ("&vnsub;", "\u{2282}\u{20D2}"),
-    ("&vltri;", "\u{22B2}"),
-    ("&vopf;", "\u{1D567}"),
-    ("&vnsup;", "\u{2283}\u{20D2}"),
-    ("&vrtri;", "\u{22B3}"),
-    ("&vprop;", "\u{221D}"),
-    ("&vsubnE;", "\u{2ACB}\u{FE00}"),
-    ("&vscr;", "\u{1D4CB}"),
-    ("&vsupnE;", "\u{2ACC}\u{FE00}"),
-    ("&vsubne;", "\u{228A}\u{FE00}"),
-    ("&vzigzag;", "\u{299A}"),
-    ("&vsupne;", "\u{228B}\u{FE00}"),
-    ("&wedbar;", "\u{2A5F}"),
-    ("&wcirc;", "\u{0175}"),
-    ("&wedgeq;", "\u{2259}"),
-    ("&wedge;", "\u{2227}"),
-    ("&wfr;", "\u{1D534}"),
-    ("&weierp;", "\u{2118}"),
-    ("&wp;", "\u{2118}"),
-    ("&wopf;", "\u{1D568}"),
-    ("&wreath;", "\u{2240}"),
-    ("&wr;", "\u{2240}"),
-    ("&xcap;", "\u{22C2}"),
-    ("&wscr;", "\u{1D4CC}"),
-    ("&xcup;", "\u{22C3}"),
-    ("&xcirc;", "\u{25EF}"),
-    ("&xfr;", "\u{1D535}"),
-    ("&xdtri;", "\u{25BD}"),
-    ("&xharr;", "\u{27F7}"),
-    ("&xhArr;", "\u{27FA}"),
-    ("&xlArr;", "\u{27F8}"),
-    ("&xi;", "\u{03BE}"),
-    ("&xmap;", "\u{27FC}"),
-    ("&xlarr;", "\u{27F5}"),
-    ("&xodot;", "\u{2A00}"),
-    ("&xnis;", "\u{22FB}"),
-    ("&xoplus;", "\u{2A01}"),
-    ("&xopf;", "\u{1D569}"),
-    ("&xrArr;", "\u{27F9}"),
-    ("&xotime;", "\u{2A02}"),
-    ("&xscr;", "\u{1D4CD}"),
-    ("&xrarr;", "\u{27F6}"),
-    ("&xuplus;", "\u{2A04}"),
-    ("&xsqcup;", "\u{2A06}"),
-    ("&xvee;", "\u{22C1}"),
-    ("&xutri;", "\u{25B3}"),
-    ("&yacute", "\u{00FD}"),
-    ("&xwedge;", "\u{22C0}"),
-    ("&yacy;", "\u{044F}"),
-    ("&yacute;", "\u{00FD}"),
-    ("&ycy;", "\u{044B}"),
-    ("&ycirc;", "\u{0177}"),
-    ("&yen;", "\u{00A5}"),
-    ("&yen", "\u{00A5}"),
-    ("&yicy;", "\u{0457}"),
-    ("&yfr;", "\u{1D536}"),
-    ("&yscr;", "\u{1D4CE}"),
-    ("&yopf;", "\u{1D56A}"),
-    ("&yuml", "\u{00FF}"),
-    ("&yucy;", "\u{044E}"),
-    ("&zacute;", "\u{017A}"),
-    ("&yuml;", "\u{00FF}"),
-    ("&zcy;", "\u{0437}"),
-    ("&zcaron;", "\u{017E}"),
-    ("&zeetrf;", "\u{2128}"),
-    ("&zdot;", "\u{017C}"),
-    ("&zfr;", "\u{1D537}"),
-    ("&zeta;", "\u{03B6}"),
-    ("&zigrarr;", "\u{21DD}"),
-    ("&zhcy;", "\u{0436}"),
-    ("&zscr;", "\u{1D4CF}"),
-    ("&zopf;", "\u{1D56B}"),
-    ("&zwnj;", "\u{200C}"),
-    ("&zwj;", "\u{200D}"),
-    ]);
-}
+// https://html.spec.whatwg.org/#named-character-references
+// Generated by build.rs from data/entities.tsv.
+include!(concat!(env!("OUT_DIR"), "/entities.rs"));
 
 pub fn get_entity(name: &str) -> Option<&'static str> {
-    ENTITIES.get(name).map(|s| *s)
+    ENTITIES.get(name).copied()
+}
+
+// Decodes the character references in `text` the way the tokenizer does. Inside attribute
+// values, a named reference without a semicolon followed by `=` or an alphanumeric is left
+// as is, so `?a=1&copy=2` keeps its `&copy`. Returns `text` unchanged when there is nothing to
+// decode.
+// https://html.spec.whatwg.org/#character-reference-state
+pub(crate) fn decode_character_references(text: &str, in_attribute: bool) -> Cow<'_, str> {
+    let Some(first) = text.find('&') else {
+        return Cow::Borrowed(text);
+    };
+
+    let mut decoded = String::with_capacity(text.len());
+    decoded.push_str(&text[..first]);
+    let mut rest = &text[first..];
+
+    while let Some(reference) = rest.strip_prefix('&') {
+        let (replacement, len) = match reference.strip_prefix('#') {
+            Some(number) => {
+                decode_numeric_reference(number).map(|(c, len)| (c.to_string(), len + 2))
+            }
+            None => decode_named_reference(reference, in_attribute)
+                .map(|(value, len)| (value.to_string(), len + 1)),
+        }
+        .unwrap_or_else(|| ("&".to_string(), 1));
+
+        decoded.push_str(&replacement);
+        rest = &rest[len..];
+
+        let next = rest.find('&').unwrap_or(rest.len());
+        decoded.push_str(&rest[..next]);
+        rest = &rest[next..];
+    }
+
+    Cow::Owned(decoded)
+}
+
+// Returns the character and the length of the reference after `&#`, including the
+// semicolon if there is one.
+// https://html.spec.whatwg.org/#numeric-character-reference-state
+fn decode_numeric_reference(reference: &str) -> Option<(char, usize)> {
+    let (digits, radix, prefix_len) = match reference.strip_prefix(['x', 'X']) {
+        Some(hex) => (hex, 16, 1),
+        None => (reference, 10, 0),
+    };
+
+    let digits_len = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+    if digits_len == 0 {
+        return None;
+    }
+
+    let code = digits[..digits_len].chars().fold(0u32, |code, c| {
+        code.saturating_mul(radix)
+            .saturating_add(c.to_digit(radix).unwrap_or(0))
+    });
+    let semicolon = usize::from(digits[digits_len..].starts_with(';'));
+
+    // https://html.spec.whatwg.org/#numeric-character-reference-end-state
+    let c = if code == 0 || code > 0x10FFFF || is_numeric_surrogate(code) {
+        char::REPLACEMENT_CHARACTER
+    } else {
+        char::from_u32(replace_control(code)).unwrap_or(char::REPLACEMENT_CHARACTER)
+    };
+
+    Some((c, prefix_len + digits_len + semicolon))
+}
+
+// Returns the longest named reference at the start of `reference` (the text after `&`) and
+// its length.
+// https://html.spec.whatwg.org/#named-character-reference-state
+fn decode_named_reference(reference: &str, in_attribute: bool) -> Option<(&'static str, usize)> {
+    let name_len = reference
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(reference.len())
+        .min(MAX_NUMBER_OF_CHARACTERS_POSSIBLE - 2);
+    let name = &reference[..name_len];
+
+    if reference[name_len..].starts_with(';') {
+        if let Some(value) = get_entity(&format!("&{};", name)) {
+            return Some((value, name_len + 1));
+        }
+    }
+
+    // References without a semicolon are all short, so only their lengths need trying.
+    (1..=name_len.min(MAX_NUMBER_OF_CHARACTERS_WITHOUT_SEMICOLON))
+        .rev()
+        .find_map(|len| {
+            let value = get_entity(&format!("&{}", &name[..len]))?;
+            let next = reference[len..].chars().next();
+
+            if in_attribute && next.is_some_and(|c| c == '=' || c.is_ascii_alphanumeric()) {
+                None
+            } else {
+                Some((value, len))
+            }
+        })
 }
 
 pub fn is_numeric_surrogate(character_reference_code: u32) -> bool {
-    character_reference_code >= 0xD800 && character_reference_code <= 0xDFFF
+    (0xD800..=0xDFFF).contains(&character_reference_code)
 }
 
 pub fn is_numeric_control(character_reference_code: u32) -> bool {
-    character_reference_code <= 0x1F
-        || (character_reference_code >= 0x7F && character_reference_code <= 0x9F)
+    character_reference_code <= 0x1F || (0x7F..=0x9F).contains(&character_reference_code)
 }
 
 pub fn is_numeric_whitespace(character_reference_code: u32) -> bool {
@@ -2255,24 +127,24 @@ pub fn is_numeric_whitespace(character_reference_code: u32) -> bool {
 }
 
 pub fn is_numeric_noncharacter(character_reference_code: u32) -> bool {
-    (character_reference_code >= 0xFDD0 && character_reference_code <= 0xFDEF)
+    (0xFDD0..=0xFDEF).contains(&character_reference_code)
         || (character_reference_code & 0xFFFE) == 0xFFFE
-        || (character_reference_code >= 0x1FFFE && character_reference_code <= 0x1FFFF)
-        || (character_reference_code >= 0x2FFFE && character_reference_code <= 0x2FFFF)
-        || (character_reference_code >= 0x3FFFE && character_reference_code <= 0x3FFFF)
-        || (character_reference_code >= 0x4FFFE && character_reference_code <= 0x4FFFF)
-        || (character_reference_code >= 0x5FFFE && character_reference_code <= 0x5FFFF)
-        || (character_reference_code >= 0x6FFFE && character_reference_code <= 0x6FFFF)
-        || (character_reference_code >= 0x7FFFE && character_reference_code <= 0x7FFFF)
-        || (character_reference_code >= 0x8FFFE && character_reference_code <= 0x8FFFF)
-        || (character_reference_code >= 0x9FFFE && character_reference_code <= 0x9FFFF)
-        || (character_reference_code >= 0xAFFFE && character_reference_code <= 0xAFFFF)
-        || (character_reference_code >= 0xBFFFE && character_reference_code <= 0xBFFFF)
-        || (character_reference_code >= 0xCFFFE && character_reference_code <= 0xCFFFF)
-        || (character_reference_code >= 0xDFFFE && character_reference_code <= 0xDFFFF)
-        || (character_reference_code >= 0xEFFFE && character_reference_code <= 0xEFFFF)
-        || (character_reference_code >= 0xFFFFE && character_reference_code <= 0xFFFFF)
-        || (character_reference_code >= 0x10FFFE && character_reference_code <= 0x10FFFF)
+        || (0x1FFFE..=0x1FFFF).contains(&character_reference_code)
+        || (0x2FFFE..=0x2FFFF).contains(&character_reference_code)
+        || (0x3FFFE..=0x3FFFF).contains(&character_reference_code)
+        || (0x4FFFE..=0x4FFFF).contains(&character_reference_code)
+        || (0x5FFFE..=0x5FFFF).contains(&character_reference_code)
+        || (0x6FFFE..=0x6FFFF).contains(&character_reference_code)
+        || (0x7FFFE..=0x7FFFF).contains(&character_reference_code)
+        || (0x8FFFE..=0x8FFFF).contains(&character_reference_code)
+        || (0x9FFFE..=0x9FFFF).contains(&character_reference_code)
+        || (0xAFFFE..=0xAFFFF).contains(&character_reference_code)
+        || (0xBFFFE..=0xBFFFF).contains(&character_reference_code)
+        || (0xCFFFE..=0xCFFFF).contains(&character_reference_code)
+        || (0xDFFFE..=0xDFFFF).contains(&character_reference_code)
+        || (0xEFFFE..=0xEFFFF).contains(&character_reference_code)
+        || (0xFFFFE..=0xFFFFF).contains(&character_reference_code)
+        || (0x10FFFE..=0x10FFFF).contains(&character_reference_code)
 }
 
 pub fn replace_control(character_reference_code: u32) -> u32 {
@@ -2301,3 +173,48 @@ pub fn replace_control(character_reference_code: u32) -> u32 {
         _ => character_reference_code,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_references() {
+        let tests = [
+            ("&amp;", "&"),
+            ("&amp", "&"),
+            ("&AMP;", "&"),
+            ("&lt;", "<"),
+            ("&lt", "<"),
+            ("&gt;", ">"),
+            ("&quot;", "\""),
+            ("&apos;", "'"),
+            ("&nbsp;", "\u{A0}"),
+            ("&nbsp", "\u{A0}"),
+            ("&copy;", "\u{A9}"),
+            ("&szlig", "\u{DF}"),
+            ("&eacute;", "\u{E9}"),
+            ("&Alpha;", "\u{391}"),
+            ("&hellip;", "\u{2026}"),
+            ("&rarr;", "\u{2192}"),
+            ("&Afr;", "\u{1D504}"),
+            ("&nsupseteq;", "\u{2289}"),
+            ("&CounterClockwiseContourIntegral;", "\u{2233}"),
+            ("&nGt;", "\u{226B}\u{20D2}"),
+            ("&NotEqualTilde;", "\u{2242}\u{338}"),
+            ("&bne;", "=\u{20E5}"),
+            ("&fjlig;", "fj"),
+            ("&ThickSpace;", "\u{205F}\u{200A}"),
+            ("&lates;", "\u{2AAD}\u{FE00}"),
+        ];
+
+        for (name, value) in tests {
+            assert_eq!(get_entity(name), Some(value), "{name}");
+        }
+
+        assert_eq!(get_entity("&apos"), None);
+        assert_eq!(get_entity("&AMp;"), None);
+        assert_eq!(get_entity("&"), None);
+        assert_eq!(ENTITIES.len(), 2231);
+    }
+}
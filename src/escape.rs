@@ -0,0 +1,78 @@
+use crate::entity::decode_character_references;
+
+// Escapes the characters that are markup in text, plus both quotes, so the result can go in
+// text or in a quoted attribute value.
+pub fn encode_html_text(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        push_encoded(&mut encoded, c);
+    }
+
+    encoded
+}
+
+// Like `encode_html_text`, but also escapes carriage returns, which newline normalization would
+// otherwise turn into line feeds when the value is parsed again.
+// https://html.spec.whatwg.org/#preprocessing-the-input-stream
+pub fn encode_html_attribute(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '\r' => encoded.push_str("&#xD;"),
+            c => push_encoded(&mut encoded, c),
+        }
+    }
+
+    encoded
+}
+
+fn push_encoded(encoded: &mut String, c: char) {
+    match c {
+        '&' => encoded.push_str("&amp;"),
+        '<' => encoded.push_str("&lt;"),
+        '>' => encoded.push_str("&gt;"),
+        '"' => encoded.push_str("&quot;"),
+        '\'' => encoded.push_str("&#x27;"),
+        c => encoded.push(c),
+    }
+}
+
+// Decodes every named and numeric character reference, as the tokenizer would in text. This
+// stays linear in the length of `s`, since a reference is matched with a bounded lookahead.
+pub fn decode_html_text(s: &str) -> String {
+    decode_character_references(s, false).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(
+            encode_html_text(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#x27;s&lt;/a&gt;"
+        );
+        assert_eq!(encode_html_text("a\nb"), "a\nb");
+        assert_eq!(
+            encode_html_attribute("a\r\n\tb \"c\""),
+            "a&#xD;\n\tb &quot;c&quot;"
+        );
+        assert_eq!(encode_html_text("plain é"), "plain é");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            decode_html_text("&lt;p&gt; &amp;amp; &quot;&#x27;&#39;&#xA;&#xD;&#x9;"),
+            "<p> &amp; \"''\n\r\t"
+        );
+        assert_eq!(
+            decode_html_text("&copy 2024 &mdash; &#8364;"),
+            "\u{A9} 2024 \u{2014} \u{20AC}"
+        );
+        assert_eq!(decode_html_text("a & b &unknown;"), "a & b &unknown;");
+    }
+}
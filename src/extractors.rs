@@ -0,0 +1,1079 @@
+use std::collections::HashMap;
+
+use crate::tokenizer::{Tag, Token, Tokenizer};
+
+// Twitter Card metadata lives in `<meta name="twitter:*" content="...">` tags.
+// The returned map is keyed by the name with the `twitter:` prefix removed.
+pub fn extract_twitter_card_metadata(html: &str) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    for token in Tokenizer::new(html) {
+        if let Token::Tag(tag) = token {
+            if tag.is_end_tag || tag.tag_name != "meta" {
+                continue;
+            }
+
+            if let (Some(name), Some(content)) =
+                (tag.get_attribute("name"), tag.get_attribute("content"))
+            {
+                if let Some(key) = name.to_ascii_lowercase().strip_prefix("twitter:") {
+                    metadata.insert(key.to_string(), content.to_string());
+                }
+            }
+        }
+    }
+
+    metadata
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct HeadInfo {
+    pub title: Option<String>,
+    pub charset: Option<String>,
+    pub description: Option<String>,
+    pub canonical: Option<String>,
+    pub stylesheets: Vec<String>,
+    pub scripts: Vec<String>,
+}
+
+// Reads the metadata in the `<head>`, tokenizing only up to `</head>` or `<body>`, so the size
+// of the body doesn't matter. The first title, charset, description and canonical URL win.
+pub fn scan_head(html: &str) -> HeadInfo {
+    let mut info = HeadInfo::default();
+    // The title being read, if inside `<title>`.
+    let mut title: Option<String> = None;
+
+    for token in Tokenizer::new(html) {
+        let tag = match token {
+            Token::Text(text) => {
+                if let Some(title) = &mut title {
+                    title.push_str(&text);
+                }
+                continue;
+            }
+            Token::Tag(tag) => tag,
+            _ => continue,
+        };
+
+        match (tag.tag_name.as_str(), tag.is_end_tag) {
+            ("head", true) | ("body", false) => break,
+            ("title", false) if info.title.is_none() => title = Some(String::new()),
+            ("title", true) => {
+                if let Some(title) = title.take() {
+                    info.title = Some(title.split_ascii_whitespace().collect::<Vec<_>>().join(" "));
+                }
+            }
+            ("meta", false) => {
+                if info.charset.is_none() {
+                    info.charset = meta_charset(&tag);
+                }
+                let is_description = tag
+                    .get_attribute("name")
+                    .is_some_and(|name| name.eq_ignore_ascii_case("description"));
+                if is_description && info.description.is_none() {
+                    info.description = tag.get_attribute("content").map(str::to_string);
+                }
+            }
+            ("link", false) => {
+                let rel = tag
+                    .get_attribute("rel")
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                let has_rel = |value: &str| rel.split_ascii_whitespace().any(|r| r == value);
+
+                if has_rel("stylesheet") {
+                    push_attribute(&mut info.stylesheets, &tag, "href");
+                }
+                if has_rel("canonical") && info.canonical.is_none() {
+                    info.canonical = tag.get_attribute("href").map(str::to_string);
+                }
+            }
+            ("script", false) => push_attribute(&mut info.scripts, &tag, "src"),
+            _ => {}
+        }
+    }
+
+    info
+}
+
+// https://ogp.me/#metadata
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct OpenGraphData {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub url: Option<String>,
+    pub type_: Option<String>,
+    pub site_name: Option<String>,
+}
+
+// Reads `<meta property="og:*" content="...">` tags. If a property repeats, the first one
+// wins.
+pub fn extract_open_graph(tokens: &[Token]) -> OpenGraphData {
+    let mut data = OpenGraphData::default();
+
+    for (property, content) in meta_properties(tokens) {
+        let field = match property.strip_prefix("og:") {
+            Some("title") => &mut data.title,
+            Some("description") => &mut data.description,
+            Some("image") => &mut data.image,
+            Some("url") => &mut data.url,
+            Some("type") => &mut data.type_,
+            Some("site_name") => &mut data.site_name,
+            _ => continue,
+        };
+        field.get_or_insert_with(|| content.to_string());
+    }
+
+    data
+}
+
+// https://developer.x.com/en/docs/twitter-for-websites/cards/overview/markup
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct TwitterCardData {
+    pub card: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+}
+
+// Like `extract_twitter_card_metadata`, but only the common fields, from tokens.
+pub fn extract_twitter_card(tokens: &[Token]) -> TwitterCardData {
+    let mut data = TwitterCardData::default();
+
+    for (name, content) in meta_properties(tokens) {
+        let field = match name.strip_prefix("twitter:") {
+            Some("card") => &mut data.card,
+            Some("title") => &mut data.title,
+            Some("description") => &mut data.description,
+            Some("image") => &mut data.image,
+            _ => continue,
+        };
+        field.get_or_insert_with(|| content.to_string());
+    }
+
+    data
+}
+
+// The lowercased `property` (or else `name`) and the `content` of every `<meta>` tag that has
+// both, up to the end of the input. Pages use either attribute for both vocabularies.
+fn meta_properties(tokens: &[Token]) -> impl Iterator<Item = (String, &str)> {
+    start_tags(until_eof(tokens))
+        .filter(|tag| tag.tag_name.eq_ignore_ascii_case("meta"))
+        .filter_map(|tag| {
+            let property = tag
+                .get_attribute("property")
+                .or_else(|| tag.get_attribute("name"))?;
+
+            Some((property.to_ascii_lowercase(), tag.get_attribute("content")?))
+        })
+}
+
+// The text of every `<script type="application/ld+json">`, as written.
+// https://html.spec.whatwg.org/#data-block
+pub fn extract_schema_org_json_ld(tokens: &[Token]) -> Vec<String> {
+    let mut blocks = Vec::new();
+    // The block being read, if inside one.
+    let mut block: Option<String> = None;
+
+    for token in until_eof(tokens) {
+        match token {
+            Token::Tag(tag) if tag.tag_name == "script" && !tag.is_end_tag => {
+                let is_json_ld = tag.type_attr().is_some_and(|type_attr| {
+                    type_attr.trim().eq_ignore_ascii_case("application/ld+json")
+                });
+                if is_json_ld && !tag.self_closing {
+                    block = Some(String::new());
+                }
+            }
+            Token::Tag(tag) if tag.tag_name == "script" => blocks.extend(block.take()),
+            Token::Text(content) => {
+                if let Some(text) = &mut block {
+                    text.push_str(content);
+                }
+            }
+            Token::Char(c) => {
+                if let Some(text) = &mut block {
+                    text.push(*c);
+                }
+            }
+            _ => {}
+        }
+    }
+    // A script left open at the end of the input still ends there.
+    blocks.extend(block);
+
+    blocks
+}
+
+fn until_eof(tokens: &[Token]) -> &[Token] {
+    let end = tokens
+        .iter()
+        .position(Token::is_eof)
+        .unwrap_or(tokens.len());
+
+    &tokens[..end]
+}
+
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct ResourceSummary {
+    pub scripts: Vec<String>,
+    pub stylesheets: Vec<String>,
+    pub images: Vec<String>,
+    pub fonts: Vec<String>,
+    pub iframes: Vec<String>,
+    pub other: Vec<String>,
+}
+
+// Collects the URLs of everything the page would load. Navigational links
+// (`<a href>`, `<area href>`) are not resources and are skipped.
+pub fn count_external_resources(html: &str) -> ResourceSummary {
+    let mut summary = ResourceSummary::default();
+    for token in Tokenizer::new(html) {
+        let tag = match token {
+            Token::Tag(tag) if !tag.is_end_tag => tag,
+            _ => continue,
+        };
+
+        match tag.tag_name.as_str() {
+            "script" => push_attribute(&mut summary.scripts, &tag, "src"),
+            "img" => push_attribute(&mut summary.images, &tag, "src"),
+            "iframe" => push_attribute(&mut summary.iframes, &tag, "src"),
+            "link" => {
+                let rel = tag
+                    .get_attribute("rel")
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                let has_rel = |value: &str| rel.split_ascii_whitespace().any(|r| r == value);
+
+                if has_rel("stylesheet") {
+                    push_attribute(&mut summary.stylesheets, &tag, "href");
+                } else if has_rel("preload")
+                    && tag
+                        .get_attribute("as")
+                        .is_some_and(|value| value.eq_ignore_ascii_case("font"))
+                {
+                    push_attribute(&mut summary.fonts, &tag, "href");
+                } else {
+                    push_attribute(&mut summary.other, &tag, "href");
+                }
+            }
+            "a" | "area" => {}
+            _ => {
+                push_attribute(&mut summary.other, &tag, "src");
+                push_attribute(&mut summary.other, &tag, "href");
+            }
+        }
+    }
+
+    summary
+}
+
+pub fn total_resource_count(summary: &ResourceSummary) -> usize {
+    summary.scripts.len()
+        + summary.stylesheets.len()
+        + summary.images.len()
+        + summary.fonts.len()
+        + summary.iframes.len()
+        + summary.other.len()
+}
+
+// Maps `id` attribute values to the start tags carrying them. When an id is repeated
+// the last tag wins.
+pub fn build_id_map(tokens: &[Token]) -> HashMap<String, &Tag> {
+    let mut ids = HashMap::new();
+
+    for token in tokens {
+        if let Token::Tag(tag) = token {
+            if tag.is_end_tag {
+                continue;
+            }
+
+            if let Some(id) = tag.get_attribute("id") {
+                ids.insert(id.to_string(), tag);
+            }
+        }
+    }
+
+    ids
+}
+
+pub fn find_by_id<'a>(tokens: &'a [Token], id: &str) -> Option<&'a Tag> {
+    build_id_map(tokens).remove(id)
+}
+
+// How many start tags there are of each name.
+pub fn count_elements(tokens: &[Token]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+
+    for tag in start_tags(tokens) {
+        *counts.entry(tag.tag_name.to_string()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+// How many start tags carry each attribute, by qualified name, e.g. `xlink:href`.
+pub fn count_attribute_usage(tokens: &[Token]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+
+    for attr in start_tags(tokens).flat_map(|tag| &tag.attributes) {
+        *counts
+            .entry(attr.qualified_name().into_owned())
+            .or_insert(0) += 1;
+    }
+
+    counts
+}
+
+// The `n` most frequent element names with their counts, most frequent first. Names with the
+// same count are in alphabetical order, so the result doesn't depend on hashing.
+pub fn most_common_elements(tokens: &[Token], n: usize) -> Vec<(String, usize)> {
+    let mut counts = count_elements(tokens).into_iter().collect::<Vec<_>>();
+    counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+    counts.truncate(n);
+
+    counts
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LinkInfo {
+    pub href: String,
+    pub rel: Option<String>,
+    // The tag the link came from, e.g. `a` or `link`.
+    pub tag: String,
+}
+
+// Collects the URLs of `<a>`, `<link>`, `<area>`, `<base>` and `<source>` start tags, from
+// `href` or, failing that, `src`.
+pub fn extract_links(tokens: &[Token]) -> Vec<LinkInfo> {
+    start_tags(tokens)
+        .filter(|tag| {
+            matches!(
+                tag.tag_name.as_str(),
+                "a" | "link" | "area" | "base" | "source"
+            )
+        })
+        .filter_map(|tag| {
+            let href = tag.href().or_else(|| tag.src())?;
+
+            Some(LinkInfo {
+                href: href.to_string(),
+                rel: tag.get_attribute("rel").map(str::to_string),
+                tag: tag.tag_name.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ImageInfo {
+    pub src: Option<String>,
+    pub srcset: Option<String>,
+    pub alt: Option<String>,
+    // `img`, or `source` for the sources of a `<picture>`.
+    pub tag: String,
+}
+
+// Collects `<img>` tags and the `<source>` tags inside `<picture>` elements that have a `src`
+// or a `srcset`.
+pub fn extract_images(tokens: &[Token]) -> Vec<ImageInfo> {
+    let mut images = Vec::new();
+    let mut picture_depth = 0usize;
+
+    for token in tokens {
+        let Token::Tag(tag) = token else {
+            continue;
+        };
+
+        match tag.tag_name.as_str() {
+            "picture" if tag.is_end_tag => picture_depth = picture_depth.saturating_sub(1),
+            "picture" if !tag.self_closing => picture_depth += 1,
+            "img" if !tag.is_end_tag => {}
+            "source" if !tag.is_end_tag && picture_depth > 0 => {}
+            _ => continue,
+        }
+
+        let src = tag.src().map(str::to_string);
+        let srcset = tag.get_attribute("srcset").map(str::to_string);
+        if src.is_none() && srcset.is_none() {
+            continue;
+        }
+
+        images.push(ImageInfo {
+            src,
+            srcset,
+            alt: tag.alt().map(str::to_string),
+            tag: tag.tag_name.to_string(),
+        });
+    }
+
+    images
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ScriptInfo {
+    pub src: Option<String>,
+    // The contents of a script without a `src`; browsers ignore them otherwise.
+    pub inline: Option<String>,
+}
+
+// Collects external script URLs and the text of inline scripts.
+pub fn extract_scripts(tokens: &[Token]) -> Vec<ScriptInfo> {
+    let mut scripts = Vec::new();
+    // The inline script being read, if inside one.
+    let mut inline: Option<String> = None;
+
+    for token in tokens {
+        match token {
+            Token::Tag(tag) if tag.tag_name == "script" && !tag.is_end_tag => match tag.src() {
+                Some(src) => scripts.push(ScriptInfo {
+                    src: Some(src.to_string()),
+                    inline: None,
+                }),
+                None if !tag.self_closing => inline = Some(String::new()),
+                None => {}
+            },
+            Token::Tag(tag) if tag.tag_name == "script" => {
+                if let Some(text) = inline.take() {
+                    scripts.push(ScriptInfo {
+                        src: None,
+                        inline: Some(text),
+                    });
+                }
+            }
+            Token::Text(content) => {
+                if let Some(text) = &mut inline {
+                    text.push_str(content);
+                }
+            }
+            Token::Char(c) => {
+                if let Some(text) = &mut inline {
+                    text.push(*c);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    scripts
+}
+
+// The language from `<html lang>`, or else from a `<meta http-equiv="Content-Language">`
+// pragma, which is read up to its first comma. An empty declaration counts as none.
+// https://html.spec.whatwg.org/#the-lang-and-xml:lang-attributes
+// https://html.spec.whatwg.org/#attr-meta-http-equiv-content-language
+pub fn detect_language(tokens: &[Token]) -> Option<String> {
+    let declared = |value: &str| Some(value.trim().to_string()).filter(|value| !value.is_empty());
+
+    if let Some(lang) = root_element(tokens).and_then(|html| html.get_attribute("lang")) {
+        return declared(lang);
+    }
+
+    start_tags(until_eof(tokens))
+        .filter(|tag| {
+            tag.tag_name.eq_ignore_ascii_case("meta")
+                && tag
+                    .get_attribute("http-equiv")
+                    .is_some_and(|value| value.eq_ignore_ascii_case("content-language"))
+        })
+        .find_map(|tag| {
+            let content = tag.get_attribute("content")?;
+            declared(content.split(',').next().unwrap_or_default())
+        })
+}
+
+// The `dir` attribute of the `<html>` element, lowercased, if it is `ltr`, `rtl` or `auto`.
+// https://html.spec.whatwg.org/#the-dir-attribute
+pub fn detect_direction(tokens: &[Token]) -> Option<String> {
+    let dir = root_element(tokens)?
+        .get_attribute("dir")?
+        .to_ascii_lowercase();
+    matches!(dir.as_str(), "ltr" | "rtl" | "auto").then_some(dir)
+}
+
+// The properties of a `<meta name="viewport">` tag. Those the content leaves out, or gives a
+// value that doesn't parse, are `None`.
+// https://drafts.csswg.org/css-viewport/#viewport-meta
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct ViewportMeta {
+    // A length in pixels or `device-width`, as written.
+    pub width: Option<String>,
+    pub height: Option<String>,
+    pub initial_scale: Option<f32>,
+    pub minimum_scale: Option<f32>,
+    pub maximum_scale: Option<f32>,
+    pub user_scalable: Option<bool>,
+}
+
+// Reads the first `<meta name="viewport">`, e.g. `width=device-width, initial-scale=1`.
+// Properties are separated by commas or semicolons, and names are matched ASCII
+// case-insensitively.
+pub fn detect_viewport(tokens: &[Token]) -> Option<ViewportMeta> {
+    let content = meta_properties(tokens)
+        .find(|(name, _)| name == "viewport")
+        .map(|(_, content)| content)?;
+    let mut viewport = ViewportMeta::default();
+
+    for property in content.split([',', ';']) {
+        let Some((name, value)) = property.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let scale = || value.parse::<f32>().ok();
+
+        match name.trim().to_ascii_lowercase().as_str() {
+            "width" => viewport.width = Some(value.to_string()),
+            "height" => viewport.height = Some(value.to_string()),
+            "initial-scale" => viewport.initial_scale = scale(),
+            "minimum-scale" => viewport.minimum_scale = scale(),
+            "maximum-scale" => viewport.maximum_scale = scale(),
+            "user-scalable" => {
+                viewport.user_scalable = match value.to_ascii_lowercase().as_str() {
+                    "yes" | "1" => Some(true),
+                    "no" | "0" => Some(false),
+                    _ => None,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(viewport)
+}
+
+// The first `<html>` start tag, which may be left out.
+fn root_element(tokens: &[Token]) -> Option<&Tag> {
+    start_tags(until_eof(tokens)).find(|tag| tag.tag_name.eq_ignore_ascii_case("html"))
+}
+
+fn start_tags(tokens: &[Token]) -> impl Iterator<Item = &Tag> {
+    tokens.iter().filter_map(|token| match token {
+        Token::Tag(tag) if !tag.is_end_tag => Some(tag),
+        _ => None,
+    })
+}
+
+// The encoding pre-scan only looks this far into the document.
+// https://html.spec.whatwg.org/#prescan-a-byte-stream-to-determine-its-encoding
+const PRESCAN_LENGTH: usize = 1024;
+
+// Looks for `<meta charset="...">` or `<meta http-equiv="Content-Type" content="...; charset=...">`.
+pub(crate) fn find_meta_charset(html: &str) -> Option<String> {
+    Tokenizer::new(html).find_map(|token| token.as_tag().and_then(meta_charset))
+}
+
+// Like `find_meta_charset`, but only looks at tokens that start within the first 1024
+// characters of their serialized form, and stops at `</head>`.
+pub fn extract_meta_charset(tokens: &[Token]) -> Option<String> {
+    let mut length = 0;
+
+    for token in tokens {
+        if length >= PRESCAN_LENGTH || token.is_end_tag() && token.tag_name() == Some("head") {
+            break;
+        }
+        if let Some(charset) = token.as_tag().and_then(meta_charset) {
+            return Some(charset);
+        }
+        length += token.to_string().chars().count();
+    }
+
+    None
+}
+
+// Finds the declared charset in the first 1024 bytes of `html`, the way a browser does before
+// it starts parsing.
+pub fn prescan_charset(html: &str) -> Option<String> {
+    let end = (0..=html.len().min(PRESCAN_LENGTH))
+        .rev()
+        .find(|&end| html.is_char_boundary(end))
+        .unwrap_or_default();
+    let tokens = Tokenizer::new(&html[..end]).collect::<Vec<_>>();
+
+    extract_meta_charset(&tokens)
+}
+
+fn meta_charset(tag: &Tag) -> Option<String> {
+    tag.effective_charset().map(str::to_string)
+}
+
+// https://html.spec.whatwg.org/#algorithm-for-extracting-a-character-encoding-from-a-meta-element
+pub(crate) fn extract_charset_from_content(content: &str) -> Option<&str> {
+    let lower = content.to_ascii_lowercase();
+    let mut position = 0;
+
+    loop {
+        position += lower[position..].find("charset")? + "charset".len();
+
+        let rest = content[position..].trim_start_matches(|c: char| c.is_ascii_whitespace());
+
+        if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start_matches(|c: char| c.is_ascii_whitespace());
+
+            return match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let value = &value[1..];
+                    value.find(quote).map(|end| &value[..end])
+                }
+                Some(_) => {
+                    let end = value
+                        .find(|c: char| c == ';' || c.is_ascii_whitespace())
+                        .unwrap_or(value.len());
+                    Some(&value[..end])
+                }
+                None => None,
+            };
+        }
+    }
+}
+
+fn push_attribute(urls: &mut Vec<String>, tag: &Tag, name: &str) {
+    if let Some(value) = tag.get_attribute(name) {
+        urls.push(value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The document from `test_basic_html` in the tokenizer, plus a few attributes.
+    const BASIC_HTML: &str = "<!DOCTYPE html><html lang=en><head><title>Test</title></head>\
+         <body class=home><h1 class=title id=top>Hello World</h1><p>a<p class=x>b</p></body></html>";
+
+    #[test]
+    fn test_count_elements() {
+        let tokens = Tokenizer::new(BASIC_HTML).collect::<Vec<_>>();
+        let counts = count_elements(&tokens);
+
+        assert_eq!(counts.len(), 6);
+        assert_eq!(counts["p"], 2);
+        assert!(["html", "head", "title", "body", "h1"]
+            .iter()
+            .all(|name| counts[*name] == 1));
+
+        assert_eq!(
+            count_attribute_usage(&tokens),
+            HashMap::from([
+                ("lang".to_string(), 1),
+                ("class".to_string(), 3),
+                ("id".to_string(), 1),
+            ])
+        );
+
+        assert_eq!(
+            most_common_elements(&tokens, 3),
+            [
+                ("p".to_string(), 2),
+                ("body".to_string(), 1),
+                ("h1".to_string(), 1),
+            ]
+        );
+        assert_eq!(most_common_elements(&tokens, 10).len(), 6);
+        assert!(most_common_elements(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_twitter_card_metadata() {
+        let html = r#"<meta name="twitter:card" content="summary">"#;
+        let metadata = extract_twitter_card_metadata(html);
+
+        assert_eq!(
+            metadata,
+            HashMap::from([("card".to_string(), "summary".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_twitter_card_metadata_ignores_other_meta() {
+        let html = r#"<head>
+            <meta charset="utf-8">
+            <meta name="description" content="A page">
+            <meta property="og:title" content="Open Graph">
+            <meta name="twitter:title" content="Title">
+            <meta name="twitter:site" content="@example" />
+        </head>"#;
+        let metadata = extract_twitter_card_metadata(html);
+
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata.get("title").map(String::as_str), Some("Title"));
+        assert_eq!(metadata.get("site").map(String::as_str), Some("@example"));
+    }
+
+    #[test]
+    fn test_scan_head() {
+        let html = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="UTF-8">
+    <meta charset="iso-8859-1">
+    <title>
+      Breaking:   news &amp; more
+    </title>
+    <meta name="Description" content="What happened today">
+    <link rel="canonical" href="https://example.com/news/1">
+    <link rel="Stylesheet preload" href="/main.css">
+    <link rel="icon" href="/favicon.ico">
+    <script src="/app.js" defer></script>
+    <script>inline();</script>
+  </head>
+  <body>
+    <title>Not the title</title>
+    <link rel="stylesheet" href="/late.css">
+    <script src="/late.js"></script>
+  </body>
+</html>"#;
+
+        assert_eq!(
+            scan_head(html),
+            HeadInfo {
+                title: Some("Breaking: news & more".to_string()),
+                charset: Some("UTF-8".to_string()),
+                description: Some("What happened today".to_string()),
+                canonical: Some("https://example.com/news/1".to_string()),
+                stylesheets: vec!["/main.css".to_string()],
+                scripts: vec!["/app.js".to_string()],
+            }
+        );
+
+        // Without a `</head>`, the `<body>` start tag ends the head.
+        let info =
+            scan_head("<title>T</title><link rel=stylesheet href=a.css><body><script src=b.js>");
+        assert_eq!(info.title.as_deref(), Some("T"));
+        assert_eq!(info.stylesheets, vec!["a.css"]);
+        assert!(info.scripts.is_empty());
+        assert_eq!(scan_head(""), HeadInfo::default());
+    }
+
+    #[test]
+    fn test_open_graph_and_twitter_card() {
+        let html = r#"<head>
+            <meta property="og:title" content="Release notes">
+            <meta property="OG:Description" content="What changed" />
+            <meta property="og:image" content="/first.png"/>
+            <meta property="og:image" content="/second.png">
+            <meta property="og:url" content="https://example.com/notes">
+            <meta name="og:type" content="article">
+            <meta property="og:locale" content="en_US">
+            <meta property="og:site_name">
+            <meta name="twitter:card" content="summary_large_image" />
+            <meta name="twitter:title" content="Notes">
+            <meta property="twitter:image" content="/card.png">
+        </head>"#;
+        let tokens: Vec<Token> = Tokenizer::new(html).collect();
+
+        assert_eq!(
+            extract_open_graph(&tokens),
+            OpenGraphData {
+                title: Some("Release notes".to_string()),
+                description: Some("What changed".to_string()),
+                image: Some("/first.png".to_string()),
+                url: Some("https://example.com/notes".to_string()),
+                type_: Some("article".to_string()),
+                site_name: None,
+            }
+        );
+        assert_eq!(
+            extract_twitter_card(&tokens),
+            TwitterCardData {
+                card: Some("summary_large_image".to_string()),
+                title: Some("Notes".to_string()),
+                description: None,
+                image: Some("/card.png".to_string()),
+            }
+        );
+
+        // Nothing after the end of the input is read.
+        let mut tokens = tokens;
+        tokens.insert(0, Token::EOF);
+        assert_eq!(extract_open_graph(&tokens), OpenGraphData::default());
+        assert_eq!(extract_twitter_card(&tokens), TwitterCardData::default());
+    }
+
+    #[test]
+    fn test_detect_language() {
+        let language = |html: &str| detect_language(&Tokenizer::new(html).collect::<Vec<_>>());
+
+        assert_eq!(
+            language(r#"<html lang=" en-GB "><meta http-equiv="content-language" content="fr">"#),
+            Some("en-GB".to_string())
+        );
+        assert_eq!(
+            language(r#"<head><meta http-equiv="Content-Language" content="de, en"></head>"#),
+            Some("de".to_string())
+        );
+        // An empty `lang` declares the language unknown, whatever the pragma says.
+        assert_eq!(
+            language(r#"<html lang=""><meta http-equiv="content-language" content="fr">"#),
+            None
+        );
+        assert_eq!(
+            language(r#"<meta name="content-language" content="fr">"#),
+            None
+        );
+        assert_eq!(language(r#"<p lang="en">"#), None);
+    }
+
+    #[test]
+    fn test_detect_direction() {
+        let direction = |html: &str| detect_direction(&Tokenizer::new(html).collect::<Vec<_>>());
+
+        assert_eq!(direction(r#"<html dir="RTL">"#), Some("rtl".to_string()));
+        assert_eq!(direction(r#"<html dir=auto>"#), Some("auto".to_string()));
+        assert_eq!(direction(r#"<html dir="sideways">"#), None);
+        assert_eq!(direction(r#"<html><body dir="rtl">"#), None);
+    }
+
+    #[test]
+    fn test_detect_viewport() {
+        let viewport = |html: &str| detect_viewport(&Tokenizer::new(html).collect::<Vec<_>>());
+
+        assert_eq!(
+            viewport(
+                r#"<meta name="Viewport" content="width=device-width; Initial-Scale = 1.5, maximum-scale=x, user-scalable=no">"#
+            ),
+            Some(ViewportMeta {
+                width: Some("device-width".to_string()),
+                initial_scale: Some(1.5),
+                user_scalable: Some(false),
+                ..ViewportMeta::default()
+            })
+        );
+        assert_eq!(
+            viewport(r#"<meta name="viewport" content="">"#),
+            Some(ViewportMeta::default())
+        );
+        assert_eq!(viewport(r#"<meta name="description" content="x">"#), None);
+    }
+
+    #[test]
+    fn test_schema_org_json_ld() {
+        let html = r#"<script type="application/ld+json">{"@type": "Article"}</script>
+            <script>ignored();</script>
+            <script type=" Application/LD+JSON ">{"@type": "Person", "name": "<b>"}</script>
+            <script type="application/ld+json" />
+            <script type="application/ld+json">{"unterminated": true}"#;
+        let tokens: Vec<Token> = Tokenizer::new(html).collect();
+
+        assert_eq!(
+            extract_schema_org_json_ld(&tokens),
+            vec![
+                r#"{"@type": "Article"}"#,
+                r#"{"@type": "Person", "name": "<b>"}"#,
+                r#"{"unterminated": true}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_external_resources() {
+        let html = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <link rel="stylesheet" href="/main.css">
+    <link rel="preload" as="font" href="/font.woff2" crossorigin>
+    <link rel="icon" href="/favicon.ico">
+    <script src="/app.js"></script>
+    <script>inline();</script>
+  </head>
+  <body>
+    <a href="/about">About</a>
+    <img src="/logo.png" alt="Logo">
+    <img src="/hero.jpg">
+    <iframe src="https://example.com/embed"></iframe>
+    <video src="/intro.mp4"></video>
+  </body>
+</html>"#;
+        let summary = count_external_resources(html);
+
+        assert_eq!(summary.scripts, vec!["/app.js"]);
+        assert_eq!(summary.stylesheets, vec!["/main.css"]);
+        assert_eq!(summary.images, vec!["/logo.png", "/hero.jpg"]);
+        assert_eq!(summary.fonts, vec!["/font.woff2"]);
+        assert_eq!(summary.iframes, vec!["https://example.com/embed"]);
+        assert_eq!(summary.other, vec!["/favicon.ico", "/intro.mp4"]);
+        assert_eq!(total_resource_count(&summary), 8);
+    }
+
+    #[test]
+    fn test_build_id_map() {
+        let html = r#"<header id="top"><h1 id="title">Hi</h1></header><p>text</p><footer id="bottom"></footer>"#;
+        let tokens: Vec<Token> = Tokenizer::new(html).collect();
+
+        let ids = build_id_map(&tokens);
+
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids["top"].tag_name, "header");
+        assert_eq!(ids["title"].tag_name, "h1");
+        assert_eq!(ids["bottom"].tag_name, "footer");
+
+        assert_eq!(
+            find_by_id(&tokens, "title").map(|tag| tag.tag_name.as_str()),
+            Some("h1")
+        );
+        assert_eq!(find_by_id(&tokens, "missing"), None);
+    }
+
+    #[test]
+    fn test_build_id_map_duplicate_ids() {
+        let html = r#"<div id="dup"></div><span id="dup"></span>"#;
+        let tokens: Vec<Token> = Tokenizer::new(html).collect();
+
+        let ids = build_id_map(&tokens);
+
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids["dup"].tag_name, "span");
+    }
+
+    #[test]
+    fn test_extract_links() {
+        let html = r#"<base href="https://example.com/"><link rel="stylesheet" href="a.css">
+            <a href="/about" rel="nofollow">About</a></a><a name="x">No href</a>
+            <map><area href="/map" alt=""></map><video><source src="v.mp4"></video>"#;
+        let tokens: Vec<Token> = Tokenizer::new(html).collect();
+
+        let links = extract_links(&tokens)
+            .into_iter()
+            .map(|link| (link.tag, link.href, link.rel))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            vec![
+                ("base".to_string(), "https://example.com/".to_string(), None),
+                (
+                    "link".to_string(),
+                    "a.css".to_string(),
+                    Some("stylesheet".to_string())
+                ),
+                (
+                    "a".to_string(),
+                    "/about".to_string(),
+                    Some("nofollow".to_string())
+                ),
+                ("area".to_string(), "/map".to_string(), None),
+                ("source".to_string(), "v.mp4".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_images() {
+        let html = r#"<img src="a.png" alt="A"><img alt="no source">
+            <picture><source srcset="b.webp 1x, b2.webp 2x"/><img src="b.png"/></picture>
+            <audio><source src="c.mp3"></audio>"#;
+        let tokens: Vec<Token> = Tokenizer::new(html).collect();
+
+        assert_eq!(
+            extract_images(&tokens),
+            vec![
+                ImageInfo {
+                    src: Some("a.png".to_string()),
+                    srcset: None,
+                    alt: Some("A".to_string()),
+                    tag: "img".to_string(),
+                },
+                ImageInfo {
+                    src: None,
+                    srcset: Some("b.webp 1x, b2.webp 2x".to_string()),
+                    alt: None,
+                    tag: "source".to_string(),
+                },
+                ImageInfo {
+                    src: Some("b.png".to_string()),
+                    srcset: None,
+                    alt: None,
+                    tag: "img".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_scripts() {
+        let html = r#"<script src="/app.js"></script><script>let a = 1 < 2;</script><p>text</p><script></script>"#;
+        let tokens: Vec<Token> = Tokenizer::new(html).collect();
+
+        assert_eq!(
+            extract_scripts(&tokens),
+            vec![
+                ScriptInfo {
+                    src: Some("/app.js".to_string()),
+                    inline: None,
+                },
+                ScriptInfo {
+                    src: None,
+                    inline: Some("let a = 1 < 2;".to_string()),
+                },
+                ScriptInfo {
+                    src: None,
+                    inline: Some(String::new()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_meta_charset() {
+        assert_eq!(
+            find_meta_charset(r#"<meta charset="utf-8">"#),
+            Some("utf-8".to_string())
+        );
+        assert_eq!(
+            find_meta_charset(
+                r#"<meta http-equiv="Content-Type" content="text/html; charset=ISO-8859-1">"#
+            ),
+            Some("ISO-8859-1".to_string())
+        );
+        assert_eq!(find_meta_charset("<title>No charset</title>"), None);
+        assert_eq!(
+            extract_charset_from_content("text/html; charset = 'koi8-r'"),
+            Some("koi8-r")
+        );
+        assert_eq!(extract_charset_from_content("text/html"), None);
+    }
+
+    #[test]
+    fn test_extract_meta_charset() {
+        let tokens = |html: &str| Tokenizer::new(html).collect::<Vec<_>>();
+
+        assert_eq!(
+            extract_meta_charset(&tokens(r#"<head><META CharSet=" UTF-8 "/></head>"#)),
+            Some("UTF-8".to_string())
+        );
+        assert_eq!(
+            extract_meta_charset(&tokens(
+                r#"<meta HTTP-EQUIV="content-type" CONTENT="text/html;charset=windows-1252"/>"#
+            )),
+            Some("windows-1252".to_string())
+        );
+        assert_eq!(
+            extract_meta_charset(&tokens(
+                r#"<meta http-equiv="refresh" content="charset=x"/>"#
+            )),
+            None
+        );
+        assert_eq!(
+            extract_meta_charset(&tokens(r#"<head></head><meta charset="utf-8"/>"#)),
+            None
+        );
+        assert_eq!(extract_meta_charset(&tokens("<title>None</title>")), None);
+    }
+
+    #[test]
+    fn test_prescan_charset() {
+        let comment = format!("<!--{}-->", "é".repeat(600));
+
+        assert_eq!(
+            prescan_charset(r#"<!DOCTYPE html><meta charset="koi8-r"/>"#),
+            Some("koi8-r".to_string())
+        );
+        assert_eq!(
+            prescan_charset(&format!(r#"{}<meta charset="koi8-r"/>"#, comment)),
+            None
+        );
+        assert_eq!(
+            find_meta_charset(&format!(r#"{}<meta charset="koi8-r"/>"#, comment)),
+            Some("koi8-r".to_string())
+        );
+        assert_eq!(prescan_charset("<p>No declaration</p>"), None);
+    }
+}
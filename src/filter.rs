@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::sanitizer::normalize_url;
+use crate::tokenizer::{Tag, Token};
+
+// Removes tags and attributes from a token stream by rules added one at a time, e.g.
+// `TagFilter::new().blocking_tags(&["script"]).allowing_tags(&["p", "a"])`. Unlike
+// `sanitize`, a filter without rules keeps every tag. CDATA sections become text and processing
+// instructions are removed either way. Names are compared ASCII case-insensitively.
+#[derive(Debug, Default, Clone)]
+pub struct TagFilter {
+    // Every tag is allowed until some are listed.
+    allowed_tags: Option<HashSet<String>>,
+    blocked_tags: HashSet<String>,
+    // Tags not listed keep all their attributes.
+    allowed_attributes: HashMap<String, HashSet<String>>,
+    stripped_value_prefixes: Vec<String>,
+}
+
+impl TagFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Removes the tags of every other element, keeping what is inside them.
+    pub fn allowing_tags(mut self, tags: &[&str]) -> Self {
+        self.allowed_tags
+            .get_or_insert_with(HashSet::new)
+            .extend(tags.iter().map(|tag| tag.to_ascii_lowercase()));
+        self
+    }
+
+    // Removes these elements together with everything inside them, even if they are allowed.
+    pub fn blocking_tags(mut self, tags: &[&str]) -> Self {
+        self.blocked_tags
+            .extend(tags.iter().map(|tag| tag.to_ascii_lowercase()));
+        self
+    }
+
+    // Keeps only these attributes on `tag`.
+    pub fn allowing_attributes(mut self, tag: &str, attrs: &[&str]) -> Self {
+        self.allowed_attributes
+            .entry(tag.to_ascii_lowercase())
+            .or_default()
+            .extend(attrs.iter().map(|attr| attr.to_ascii_lowercase()));
+        self
+    }
+
+    // Removes attributes whose value starts with `pattern`, e.g. `javascript:`. Whitespace,
+    // control characters and case in the value are ignored, as browsers ignore them in URLs.
+    pub fn stripping_attribute_values_matching(mut self, pattern: &str) -> Self {
+        self.stripped_value_prefixes.push(normalize_url(pattern));
+        self
+    }
+
+    pub fn apply(&self, tokens: Vec<Token>) -> Vec<Token> {
+        let mut filtered = Vec::with_capacity(tokens.len());
+        // The blocked element being skipped and how many elements with its name are open
+        // inside it.
+        let mut blocked: Option<(String, usize)> = None;
+
+        for mut token in tokens {
+            if let Some((name, depth)) = &mut blocked {
+                match token.as_tag() {
+                    Some(tag) if tag.tag_name.eq_ignore_ascii_case(name) => {
+                        if !tag.is_end_tag {
+                            *depth += 1;
+                        } else if *depth == 0 {
+                            blocked = None;
+                        } else {
+                            *depth -= 1;
+                        }
+                    }
+                    _ => {}
+                }
+                if !token.is_eof() {
+                    continue;
+                }
+            }
+
+            match token {
+                // The serializer writes these out as they are, so markup inside them would come
+                // back as live markup once the output is parsed as HTML.
+                Token::CDATASection(data) => token = Token::Text(data),
+                Token::ProcessingInstruction { .. } => continue,
+                Token::Template(content) => token = Token::Template(self.apply(content)),
+                _ => {}
+            }
+
+            if let Token::Tag(tag) = &mut token {
+                let name = tag.tag_name.to_ascii_lowercase();
+
+                if self.blocked_tags.contains(&name) {
+                    if !tag.is_end_tag && !tag.self_closing && !tag.is_void_element() {
+                        blocked = Some((name, 0));
+                    }
+                    continue;
+                }
+                if !self.is_tag_allowed(&name) {
+                    continue;
+                }
+
+                self.filter_attributes(&name, tag);
+            }
+
+            filtered.push(token);
+        }
+
+        filtered
+    }
+
+    fn is_tag_allowed(&self, name: &str) -> bool {
+        self.allowed_tags
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(name))
+    }
+
+    fn filter_attributes(&self, name: &str, tag: &mut Tag) {
+        let allowed = self.allowed_attributes.get(name);
+
+        tag.attributes.retain(|attr| {
+            let value = normalize_url(&attr.value);
+
+            allowed
+                .is_none_or(|allowed| allowed.contains(&attr.qualified_name().to_ascii_lowercase()))
+                && !self
+                    .stripped_value_prefixes
+                    .iter()
+                    .any(|prefix| value.starts_with(prefix.as_str()))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{serialize, Tokenizer};
+
+    fn filter_html(html: &str, filter: &TagFilter) -> String {
+        serialize(&filter.apply(Tokenizer::new(html).collect()))
+    }
+
+    #[test]
+    fn test_no_rules() {
+        let html = r#"<div class="a"><script>x()</script><a href="javascript:x">y</a></div>"#;
+
+        assert_eq!(filter_html(html, &TagFilter::new()), html);
+    }
+
+    #[test]
+    fn test_allowing_tags() {
+        let filter = TagFilter::new().allowing_tags(&["P", "b"]);
+
+        assert_eq!(
+            filter_html(
+                "<div><p>Hello <b>big</b> <u>world</u></p><br>!</div>",
+                &filter
+            ),
+            "<p>Hello <b>big</b> world</p>!"
+        );
+    }
+
+    #[test]
+    fn test_blocking_tags() {
+        let filter = TagFilter::new()
+            .allowing_tags(&["p", "div", "aside"])
+            .blocking_tags(&["aside", "img"]);
+
+        assert_eq!(
+            filter_html(
+                "<p>a<aside>b<aside>c</aside>d</aside>e<img src=x>f<aside/>g</p>",
+                &filter
+            ),
+            "<p>aefg</p>"
+        );
+        // An element left open is removed up to the end of the input.
+        assert_eq!(
+            filter.apply(Tokenizer::new("<div><aside>x").collect()),
+            vec![
+                Token::Tag(Tag::new_start_tag_with_name("div".to_string())),
+                Token::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allowing_attributes() {
+        let filter = TagFilter::new()
+            .allowing_attributes("A", &["href"])
+            .allowing_attributes("a", &["Title"]);
+
+        assert_eq!(
+            filter_html(
+                r#"<a href="/x" title="t" onclick="go()"><span onclick="go()">x</span></a>"#,
+                &filter
+            ),
+            r#"<a href="/x" title="t"><span onclick="go()">x</span></a>"#
+        );
+    }
+
+    #[test]
+    fn test_stripping_attribute_values() {
+        let filter = TagFilter::new()
+            .stripping_attribute_values_matching("javascript:")
+            .stripping_attribute_values_matching("data:");
+
+        assert_eq!(
+            filter_html(
+                "<a href=\" Java\tScript:alert(1)\" title=\"javascript: a guide\">x</a>\
+                 <img src=\"data:image/png;base64,AA\" alt=\"/data:\">",
+                &filter
+            ),
+            r#"<a>x</a><img alt="/data:">"#
+        );
+    }
+
+    #[test]
+    fn test_markup_in_cdata_sections() {
+        let payload = "<p><![CDATA[><img src=x onerror=alert(1)>]]></p>";
+        let tokens = vec![
+            Token::Tag(Tag::new_start_tag_with_name("p".to_string())),
+            Token::CDATASection("><img src=x onerror=alert(1)>".to_string()),
+            Token::ProcessingInstruction {
+                target: "x".to_string(),
+                data: "<img src=x onerror=alert(1)>".to_string(),
+            },
+            Token::Template(vec![Token::CDATASection("<script>".to_string())]),
+            Token::Tag(Tag::new_end_tag_with_name("p".to_string())),
+        ];
+
+        let html = serialize(&TagFilter::new().apply(tokens));
+        assert!(
+            !html.contains("<img") && !html.contains("<script"),
+            "{}",
+            html
+        );
+        let html = filter_html(payload, &TagFilter::new().allowing_tags(&["p"]));
+        assert!(!html.contains("<img"), "{}", html);
+    }
+}
@@ -1,4 +1,62 @@
+#[cfg(feature = "tokio")]
+mod async_tokenizer;
+mod diff;
+mod document;
+mod elements;
+mod encoding;
 mod entity;
+mod escape;
+mod extractors;
+mod filter;
+mod minifier;
+mod namespace;
+mod node;
+mod optional_tags;
+mod rewriter;
+mod sanitizer;
+mod security;
+mod selector;
+mod serializer;
+mod text;
+mod token_stream;
 mod tokenizer;
+mod tree_builder;
+mod visitor;
+mod writer;
 
-pub use tokenizer::Tokenizer;
+#[cfg(feature = "tokio")]
+pub use async_tokenizer::AsyncTokenizer;
+pub use diff::{
+    apply_diff, canonicalize_token_stream, diff_summary, diff_token_streams, DiffSummary, TokenDiff,
+};
+pub use document::Document;
+pub use encoding::{check_character_encoding_consistency, EncodingConsistencyIssue};
+pub use escape::{decode_html_text, encode_html_attribute, encode_html_text};
+pub use extractors::{
+    build_id_map, count_attribute_usage, count_elements, count_external_resources,
+    detect_direction, detect_language, detect_viewport, extract_images, extract_links,
+    extract_meta_charset, extract_open_graph, extract_schema_org_json_ld, extract_scripts,
+    extract_twitter_card, extract_twitter_card_metadata, find_by_id, most_common_elements,
+    prescan_charset, scan_head, total_resource_count, HeadInfo, ImageInfo, LinkInfo, OpenGraphData,
+    ResourceSummary, ScriptInfo, TwitterCardData, ViewportMeta,
+};
+pub use filter::TagFilter;
+pub use minifier::{minify, MinifierOptions};
+pub use namespace::Namespace;
+pub use node::Node;
+pub use rewriter::Rewriter;
+pub use sanitizer::{sanitize, Allowlist};
+pub use security::{scan_for_xss, SecurityScanner, XssVector};
+pub use selector::SimpleSelector;
+pub use serializer::{serialize, Serializer, SerializerOptions};
+pub use text::{collect_text, collect_text_with_separator, html_to_accessible_text};
+pub use token_stream::{balanced_pairs, find_element_range, TokenStream};
+#[allow(deprecated)]
+pub use tokenizer::StrTokenizer;
+pub use tokenizer::{
+    is_boolean, Attribute, Doctype, ParseError, ParseErrorKind, PushTokenizer, ReadTokenizer, Span,
+    SpannedToken, State, Tag, TagName, Token, Tokenizer, TokenizerOptions, TokenizerStats,
+};
+pub use tree_builder::{InsertionMode, TreeBuilder};
+pub use visitor::{visit_tokens, TextExtractor, Visitor};
+pub use writer::{QuoteStyle, TokenWriter, WriterOptions};
@@ -0,0 +1,203 @@
+use crate::optional_tags::{is_end_tag_optional, is_one_of};
+use crate::tokenizer::Token;
+
+// Whitespace inside these elements is part of their content.
+const PRESERVE_WHITESPACE_ELEMENTS: [&str; 3] = ["pre", "script", "textarea"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct MinifierOptions {
+    pub remove_comments: bool,
+    // Replaces whitespace-only text between two tags with a single space, except inside
+    // `<pre>`, `<script>` and `<textarea>`.
+    pub collapse_whitespace: bool,
+    // Drops end tags the spec allows to be left out, such as `</li>` right before another
+    // `<li>`. Whitespace between the two keeps the end tag.
+    pub remove_optional_end_tags: bool,
+}
+
+impl Default for MinifierOptions {
+    fn default() -> Self {
+        MinifierOptions {
+            remove_comments: true,
+            collapse_whitespace: true,
+            remove_optional_end_tags: true,
+        }
+    }
+}
+
+pub fn minify(tokens: Vec<Token>, options: MinifierOptions) -> Vec<Token> {
+    let mut minified = Vec::with_capacity(tokens.len());
+    // The character tokens seen since the last other token.
+    let mut text = Vec::new();
+    // The number of open elements whose whitespace is kept.
+    let mut preserving = 0usize;
+
+    for token in tokens {
+        if options.remove_comments && token.is_comment() {
+            continue;
+        }
+        if matches!(token, Token::Char(_) | Token::Text(_)) {
+            text.push(token);
+            continue;
+        }
+
+        let between_tags = token.as_tag().is_some()
+            && minified
+                .last()
+                .is_some_and(|last: &Token| last.as_tag().is_some());
+        flush_text(
+            &mut minified,
+            &mut text,
+            options.collapse_whitespace && preserving == 0 && between_tags,
+        );
+
+        if let Token::Tag(tag) = &token {
+            if is_one_of(&tag.tag_name, &PRESERVE_WHITESPACE_ELEMENTS) {
+                if tag.is_end_tag {
+                    preserving = preserving.saturating_sub(1);
+                } else if !tag.self_closing {
+                    preserving += 1;
+                }
+            }
+        }
+        minified.push(token);
+    }
+    flush_text(&mut minified, &mut text, false);
+
+    if options.remove_optional_end_tags {
+        minified = remove_optional_end_tags(minified);
+    }
+    minified
+}
+
+fn flush_text(minified: &mut Vec<Token>, text: &mut Vec<Token>, collapse: bool) {
+    let is_whitespace = |token: &Token| match token {
+        Token::Char(c) => c.is_ascii_whitespace(),
+        Token::Text(text) => text.chars().all(|c| c.is_ascii_whitespace()),
+        _ => false,
+    };
+
+    if collapse && !text.is_empty() && text.iter().all(is_whitespace) {
+        text.clear();
+        minified.push(Token::Text(" ".to_string()));
+    } else {
+        minified.append(text);
+    }
+}
+
+fn remove_optional_end_tags(tokens: Vec<Token>) -> Vec<Token> {
+    let mut kept = Vec::with_capacity(tokens.len());
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        if let Token::Tag(tag) = &token {
+            // Without a stack of open elements, the end tag after this one is the best guess
+            // at the parent.
+            let next = tokens.peek();
+            let parent = next
+                .filter(|next| next.is_end_tag())
+                .and_then(Token::tag_name);
+            if tag.is_end_tag && is_end_tag_optional(&tag.tag_name, next, parent) {
+                continue;
+            }
+        }
+        kept.push(token);
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{serialize, Tokenizer};
+
+    fn minify_html(html: &str, options: MinifierOptions) -> String {
+        serialize(&minify(Tokenizer::new(html).collect(), options))
+    }
+
+    const ONLY_WHITESPACE: MinifierOptions = MinifierOptions {
+        remove_comments: false,
+        collapse_whitespace: true,
+        remove_optional_end_tags: false,
+    };
+
+    #[test]
+    fn test_collapse_whitespace() {
+        assert_eq!(
+            minify_html(
+                "<div>\n  <p> Hi </p>\n  <!-- x -->\n</div>\n<pre>\n  <b>x</b>\n</pre>",
+                ONLY_WHITESPACE
+            ),
+            "<div> <p> Hi </p>\n  <!-- x -->\n</div> <pre>\n  <b>x</b>\n</pre>"
+        );
+        assert_eq!(
+            minify_html("<span>a</span> \n <span>b</span>", ONLY_WHITESPACE),
+            "<span>a</span> <span>b</span>"
+        );
+        assert_eq!(
+            minify_html("<textarea>  </textarea><script> </script>", ONLY_WHITESPACE),
+            "<textarea>  </textarea><script> </script>"
+        );
+    }
+
+    #[test]
+    fn test_remove_comments() {
+        let options = MinifierOptions {
+            remove_optional_end_tags: false,
+            ..MinifierOptions::default()
+        };
+
+        assert_eq!(
+            minify_html("<p>a<!-- x -->b</p>\n<!-- y -->\n<p>c</p>", options),
+            "<p>ab</p> <p>c</p>"
+        );
+    }
+
+    #[test]
+    fn test_remove_optional_end_tags() {
+        let options = MinifierOptions {
+            remove_comments: false,
+            collapse_whitespace: false,
+            remove_optional_end_tags: true,
+        };
+
+        assert_eq!(
+            minify_html("<ul><li>a</li><li>b</li></ul>", options),
+            "<ul><li>a<li>b</ul>"
+        );
+        assert_eq!(
+            minify_html("<div><p>a</p><p>b</p></div><p>c</p> ", options),
+            "<div><p>a<p>b</div><p>c</p> "
+        );
+        assert_eq!(minify_html("<a><p>a</p></a>", options), "<a><p>a</p></a>");
+        assert_eq!(
+            minify_html("<dl><dt>a</dt><dd>b</dd></dl>", options),
+            "<dl><dt>a<dd>b</dl>"
+        );
+        assert_eq!(
+            minify_html(
+                "<table><thead><tr><th>a</th></tr></thead><tbody><tr><td>b</td></tr></tbody></table>",
+                options
+            ),
+            "<table><thead><tr><th>a<tbody><tr><td>b</table>"
+        );
+    }
+
+    #[test]
+    fn test_fixture() {
+        let html = include_str!("../tests/fixtures/page.html");
+        let minified = minify_html(html, MinifierOptions::default());
+
+        // The fixture is mostly indentation, so at least a quarter of it should go.
+        assert!(
+            minified.len() * 4 < html.len() * 3,
+            "{} of {} bytes left",
+            minified.len(),
+            html.len()
+        );
+        assert!(minified.contains("<pre><code>[dependencies]\nexample = \"2.4\"\n</code></pre>"));
+        assert!(minified
+            .contains("<textarea name=\"comment\" rows=\"4\">\n  Tell us more...\n</textarea>"));
+        assert!(!minified.contains("<!--"));
+    }
+}
@@ -0,0 +1,32 @@
+// The namespace of an element. Elements inside `<svg>` and `<math>` are not HTML elements and
+// are parsed as foreign content.
+// https://infra.spec.whatwg.org/#namespaces
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub enum Namespace {
+    #[default]
+    Html,
+    Svg,
+    MathML,
+    Other(String),
+}
+
+impl Namespace {
+    pub fn uri(&self) -> &str {
+        match self {
+            Namespace::Html => "http://www.w3.org/1999/xhtml",
+            Namespace::Svg => "http://www.w3.org/2000/svg",
+            Namespace::MathML => "http://www.w3.org/1998/Math/MathML",
+            Namespace::Other(uri) => uri,
+        }
+    }
+
+    // The namespace an `<svg>` or `<math>` start tag in HTML content opens, if `tag_name` is
+    // one of them.
+    pub(crate) fn opened_by(tag_name: &str) -> Option<Self> {
+        match tag_name {
+            "svg" => Some(Namespace::Svg),
+            "math" => Some(Namespace::MathML),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use crate::namespace::Namespace;
+use crate::serializer::{serialize_children, serialize_node};
+use crate::tokenizer::{Attribute, Doctype};
+
+// A node of the tree built by `TreeBuilder`.
+// https://dom.spec.whatwg.org/#nodes
+#[derive(Debug, PartialEq, Clone)]
+pub enum Node {
+    Element {
+        tag_name: String,
+        attributes: Vec<Attribute>,
+        children: Vec<Node>,
+        namespace: Namespace,
+    },
+    Text(String),
+    Comment(String),
+    Doctype(Doctype),
+}
+
+impl Node {
+    pub fn new_element(tag_name: &str, attributes: Vec<Attribute>) -> Self {
+        Node::new_element_in(tag_name, attributes, Namespace::Html)
+    }
+
+    pub fn new_element_in(
+        tag_name: &str,
+        attributes: Vec<Attribute>,
+        namespace: Namespace,
+    ) -> Self {
+        Node::Element {
+            tag_name: tag_name.to_string(),
+            attributes,
+            children: Vec::new(),
+            namespace,
+        }
+    }
+
+    pub fn tag_name(&self) -> Option<&str> {
+        match self {
+            Node::Element { tag_name, .. } => Some(tag_name),
+            _ => None,
+        }
+    }
+
+    pub fn namespace(&self) -> Option<&Namespace> {
+        match self {
+            Node::Element { namespace, .. } => Some(namespace),
+            _ => None,
+        }
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        match self {
+            Node::Element { attributes, .. } => attributes,
+            _ => &[],
+        }
+    }
+
+    pub fn children(&self) -> &[Node] {
+        match self {
+            Node::Element { children, .. } => children,
+            _ => &[],
+        }
+    }
+
+    pub fn element_children(&self) -> impl Iterator<Item = &Node> {
+        self.children().iter().filter(|node| node.is_element())
+    }
+
+    // Every node inside this one, depth first in document order.
+    pub fn descendants(&self) -> impl Iterator<Item = &Node> {
+        let mut stack = self.children().iter().rev().collect::<Vec<_>>();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children().iter().rev());
+            Some(node)
+        })
+    }
+
+    pub fn is_element(&self) -> bool {
+        matches!(self, Node::Element { .. })
+    }
+
+    // Compares attribute names ASCII case-insensitively, like `Tag::get_attribute`.
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes()
+            .iter()
+            .find(|attr| attr.qualified_name().eq_ignore_ascii_case(name))
+            .map(|attr| attr.value.as_str())
+    }
+
+    // The first element in this subtree, in document order, whose `id` is `id`.
+    pub fn find_by_id(&self, id: &str) -> Option<&Node> {
+        self.find(&|node| node.get_attribute("id") == Some(id))
+    }
+
+    // Every element in this subtree with the given tag name, compared ASCII case-insensitively.
+    pub fn find_all_by_tag(&self, name: &str) -> Vec<&Node> {
+        self.find_all(&|node| {
+            node.tag_name()
+                .is_some_and(|tag_name| tag_name.eq_ignore_ascii_case(name))
+        })
+    }
+
+    // Every element in this subtree with `class` among the names in its `class` attribute.
+    pub fn find_all_by_class(&self, class: &str) -> Vec<&Node> {
+        self.find_all(&|node| {
+            node.get_attribute("class")
+                .is_some_and(|classes| classes.split_ascii_whitespace().any(|name| name == class))
+        })
+    }
+
+    // The text of every text node in this subtree, concatenated in document order.
+    // https://dom.spec.whatwg.org/#dom-node-textcontent
+    pub fn text_content(&self) -> String {
+        let mut text = String::new();
+        self.collect_text(&mut text);
+        text
+    }
+
+    // The HTML of this node, including its own tags. Text inside elements such as `<script>`
+    // is not escaped, and void elements get no end tag.
+    // https://html.spec.whatwg.org/#serialising-html-fragments
+    pub fn serialize(&self) -> String {
+        let mut html = String::new();
+        serialize_node(&mut html, self, None);
+        html
+    }
+
+    // The HTML of this node's children, or of the node itself if it has none.
+    pub fn inner_html(&self) -> String {
+        if !self.is_element() {
+            return self.serialize();
+        }
+
+        let mut html = String::new();
+        serialize_children(&mut html, self);
+        html
+    }
+
+    pub fn outer_html(&self) -> String {
+        self.serialize()
+    }
+
+    fn collect_text(&self, text: &mut String) {
+        match self {
+            Node::Text(content) => text.push_str(content),
+            Node::Element { children, .. } => {
+                for child in children {
+                    child.collect_text(text);
+                }
+            }
+            Node::Comment(_) | Node::Doctype(_) => {}
+        }
+    }
+
+    // The first element in this subtree, in document order, for which `predicate` holds.
+    pub(crate) fn find(&self, predicate: &impl Fn(&Node) -> bool) -> Option<&Node> {
+        if !self.is_element() {
+            return None;
+        }
+
+        if predicate(self) {
+            return Some(self);
+        }
+        self.children()
+            .iter()
+            .find_map(|child| child.find(predicate))
+    }
+
+    pub(crate) fn find_all(&self, predicate: &impl Fn(&Node) -> bool) -> Vec<&Node> {
+        let mut found = Vec::new();
+        self.collect_matching(predicate, &mut found);
+        found
+    }
+
+    fn collect_matching<'a>(
+        &'a self,
+        predicate: &impl Fn(&Node) -> bool,
+        found: &mut Vec<&'a Node>,
+    ) {
+        if !self.is_element() {
+            return;
+        }
+
+        if predicate(self) {
+            found.push(self);
+        }
+        for child in self.children() {
+            child.collect_matching(predicate, found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tokenizer, TreeBuilder};
+
+    fn body(html: &str) -> Node {
+        let mut builder = TreeBuilder::new();
+        for token in Tokenizer::new(html) {
+            builder.process(token);
+        }
+        builder.finish().body().unwrap().clone()
+    }
+
+    const PAGE: &str = r#"<div id="main" class="page wide">
+        <h1>Title</h1>
+        <section class="wide"><p id="intro">Hello, <em>world</em>!</p><!-- note --></section>
+        <SECTION><P class="pagex">Bye</P></SECTION>
+    </div>"#;
+
+    #[test]
+    fn test_find_by_id() {
+        let body = body(PAGE);
+
+        assert_eq!(
+            body.find_by_id("main").and_then(Node::tag_name),
+            Some("div")
+        );
+        assert_eq!(
+            body.find_by_id("intro").map(Node::text_content),
+            Some("Hello, world!".to_string())
+        );
+        assert_eq!(body.find_by_id("INTRO"), None);
+        assert_eq!(body.find_by_id("missing"), None);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let body = body(PAGE);
+
+        let sections = body.find_all_by_tag("Section");
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[1].text_content(), "Bye");
+        assert_eq!(body.find_all_by_tag("p").len(), 2);
+        assert!(body.find_all_by_tag("table").is_empty());
+
+        let wide = body
+            .find_all_by_class("wide")
+            .into_iter()
+            .filter_map(Node::tag_name)
+            .collect::<Vec<_>>();
+        assert_eq!(wide, vec!["div", "section"]);
+        assert_eq!(body.find_all_by_class("page").len(), 1);
+        assert!(body.find_all_by_class("Wide").is_empty());
+    }
+
+    #[test]
+    fn test_html() {
+        let body = body(
+            r#"<div class="a &amp; b"><p>1 &lt; 2<br/>3</p><script>if (a < b) {}</script><!--x--></div>"#,
+        );
+        let div = &body.children()[0];
+
+        assert_eq!(
+            div.outer_html(),
+            r#"<div class="a &amp; b"><p>1 &lt; 2<br>3</p><script>if (a < b) {}</script><!--x--></div>"#
+        );
+        assert_eq!(
+            div.inner_html(),
+            r#"<p>1 &lt; 2<br>3</p><script>if (a < b) {}</script><!--x-->"#
+        );
+        assert_eq!(
+            div.find_all_by_tag("script")[0].inner_html(),
+            "if (a < b) {}"
+        );
+
+        let text = Node::Text("a < b & c".to_string());
+        assert_eq!(text.inner_html(), "a &lt; b &amp; c");
+        assert_eq!(text.outer_html(), "a &lt; b &amp; c");
+
+        let comment = Node::Comment(" note ".to_string());
+        assert_eq!(comment.inner_html(), "<!-- note -->");
+        assert_eq!(comment.outer_html(), "<!-- note -->");
+    }
+
+    #[test]
+    fn test_serialize() {
+        let body = body(
+            "<p title='a \"b\" &amp; c'>x&nbsp;&lt;y&gt;<input disabled value=''><br></p>\
+             <style>p > a { content: \"&\" }</style><xmp><b></xmp><textarea><b></textarea>",
+        );
+
+        assert_eq!(
+            body.serialize(),
+            "<body><p title=\"a &quot;b&quot; &amp; c\">x&nbsp;&lt;y&gt;<input disabled value=\"\">\
+             <br></p><style>p > a { content: \"&\" }</style><xmp><b></xmp>\
+             <textarea>&lt;b&gt;</textarea></body>"
+        );
+    }
+
+    #[test]
+    fn test_html_round_trip() {
+        let body = body(PAGE);
+        let reparsed = self::body(&body.outer_html());
+
+        assert_eq!(reparsed, body);
+    }
+
+    #[test]
+    fn test_text_content() {
+        let body = body("<p>a<b>b<i>c</i></b><!-- x -->d</p>");
+
+        assert_eq!(body.text_content(), "abcd");
+        assert_eq!(Node::Text("x".to_string()).text_content(), "x");
+        assert_eq!(Node::Comment("x".to_string()).text_content(), "");
+    }
+}
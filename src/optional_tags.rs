@@ -0,0 +1,124 @@
+use crate::tokenizer::Token;
+
+// The end tag rules shared by `TokenWriter` and `minify`.
+// https://html.spec.whatwg.org/#optional-tags
+
+// A `</p>` may be omitted when followed by a start tag for one of these elements.
+const P_END_TAG_OMITTING_ELEMENTS: [&str; 32] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "search",
+    "section",
+    "table",
+    "ul",
+];
+
+// A `</p>` that ends one of these elements' content must be kept.
+const P_END_TAG_REQUIRED_PARENTS: [&str; 7] =
+    ["a", "audio", "del", "ins", "map", "noscript", "video"];
+
+// Whether the end tag for `name` can be left out when `next` comes right after it, `None`
+// being the end of the input, inside the element `parent`, if it is known.
+pub(crate) fn is_end_tag_optional(name: &str, next: Option<&Token>, parent: Option<&str>) -> bool {
+    let followed_by = |names: &[&str]| {
+        next.is_some_and(|token| {
+            token.is_start_tag() && token.tag_name().is_some_and(|name| is_one_of(name, names))
+        })
+    };
+    // Nothing follows inside the parent element.
+    let parent_ends = next.is_none_or(|token| token.is_end_tag() || token.is_eof());
+    let starts_with_space_or_comment = match next {
+        Some(Token::Text(text)) => text.starts_with(|c: char| c.is_ascii_whitespace()),
+        Some(Token::Char(c)) => c.is_ascii_whitespace(),
+        Some(Token::Comment(_)) => true,
+        _ => false,
+    };
+
+    let lowercase;
+    let name = if name.bytes().any(|b| b.is_ascii_uppercase()) {
+        lowercase = name.to_ascii_lowercase();
+        lowercase.as_str()
+    } else {
+        name
+    };
+
+    match name {
+        "html" | "body" => !next.is_some_and(Token::is_comment),
+        "head" | "colgroup" | "caption" => !starts_with_space_or_comment,
+        "li" => followed_by(&["li"]) || parent_ends,
+        "dt" => followed_by(&["dd", "dt"]),
+        "dd" => followed_by(&["dd", "dt"]) || parent_ends,
+        "p" => {
+            followed_by(&P_END_TAG_OMITTING_ELEMENTS)
+                || parent_ends
+                    && !parent.is_some_and(|parent| is_one_of(parent, &P_END_TAG_REQUIRED_PARENTS))
+        }
+        "rp" | "rt" => followed_by(&["rp", "rt"]) || parent_ends,
+        "optgroup" => followed_by(&["hr", "optgroup"]) || parent_ends,
+        "option" => followed_by(&["hr", "optgroup", "option"]) || parent_ends,
+        "thead" => followed_by(&["tbody", "tfoot"]),
+        "tbody" => followed_by(&["tbody", "tfoot"]) || parent_ends,
+        "tfoot" => parent_ends,
+        "tr" => followed_by(&["tr"]) || parent_ends,
+        "td" | "th" => followed_by(&["td", "th"]) || parent_ends,
+        _ => false,
+    }
+}
+
+pub(crate) fn is_one_of(name: &str, names: &[&str]) -> bool {
+    names.iter().any(|other| name.eq_ignore_ascii_case(other))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{minify, serialize, MinifierOptions, TokenWriter, Tokenizer, WriterOptions};
+
+    #[test]
+    fn test_writer_and_minifier_agree() {
+        let tokens = Tokenizer::new(
+            "<html><head><title>x</title></head><body><p>a</p><search>b</search>\
+             <ul><li>c</li><li>d</li></ul><p>e</p></body></html>",
+        )
+        .collect::<Vec<_>>();
+
+        let mut writer = TokenWriter::new(WriterOptions {
+            omit_optional_end_tags: true,
+            ..WriterOptions::default()
+        });
+        tokens.iter().for_each(|token| writer.write_token(token));
+        let expected =
+            "<html><head><title>x</title><body><p>a<search>b</search><ul><li>c<li>d</ul><p>e";
+
+        assert_eq!(writer.finish(), expected);
+        assert_eq!(
+            serialize(&minify(tokens, MinifierOptions::default())),
+            expected
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use crate::serializer::serialize;
+use crate::tokenizer::{Tag, Token, Tokenizer};
+
+type TagHandler = Box<dyn Fn(&mut Tag)>;
+type TextHandler = Box<dyn Fn(&mut String)>;
+
+// Rewrites HTML one token at a time: handlers registered for a tag name, text or comments may
+// change the tokens they are given before everything is serialized back out. Tag names are
+// compared ASCII case-insensitively.
+#[derive(Default)]
+pub struct Rewriter {
+    start_tag_handlers: HashMap<String, Vec<TagHandler>>,
+    end_tag_handlers: HashMap<String, Vec<TagHandler>>,
+    text_handlers: Vec<TextHandler>,
+    comment_handlers: Vec<TextHandler>,
+}
+
+impl Rewriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_start_tag(mut self, tag: &str, f: impl Fn(&mut Tag) + 'static) -> Self {
+        self.start_tag_handlers
+            .entry(tag.to_ascii_lowercase())
+            .or_default()
+            .push(Box::new(f));
+        self
+    }
+
+    pub fn on_end_tag(mut self, tag: &str, f: impl Fn(&mut Tag) + 'static) -> Self {
+        self.end_tag_handlers
+            .entry(tag.to_ascii_lowercase())
+            .or_default()
+            .push(Box::new(f));
+        self
+    }
+
+    // Renames both the start and end tags of an element, e.g. `<div>` to `<section>`.
+    pub fn rename_tag(self, tag: &str, new_name: &str) -> Self {
+        let start_name = new_name.to_string();
+        let end_name = new_name.to_string();
+
+        self.on_start_tag(tag, move |tag| tag.rename(&start_name))
+            .on_end_tag(tag, move |tag| tag.rename(&end_name))
+    }
+
+    pub fn on_text(mut self, f: impl Fn(&mut String) + 'static) -> Self {
+        self.text_handlers.push(Box::new(f));
+        self
+    }
+
+    pub fn on_comment(mut self, f: impl Fn(&mut String) + 'static) -> Self {
+        self.comment_handlers.push(Box::new(f));
+        self
+    }
+
+    pub fn rewrite(&self, input: &str) -> String {
+        let tokens = Tokenizer::new(input)
+            .map(|mut token| {
+                self.handle(&mut token);
+                token
+            })
+            .collect::<Vec<_>>();
+
+        serialize(&tokens)
+    }
+
+    fn handle(&self, token: &mut Token) {
+        match token {
+            Token::Tag(tag) => {
+                let handlers = if tag.is_end_tag {
+                    &self.end_tag_handlers
+                } else {
+                    &self.start_tag_handlers
+                };
+
+                if let Some(handlers) = handlers.get(&tag.tag_name.to_ascii_lowercase()) {
+                    for handler in handlers {
+                        handler(tag);
+                    }
+                }
+            }
+            Token::Text(text) => {
+                for handler in &self.text_handlers {
+                    handler(text);
+                }
+            }
+            Token::Char(c) => {
+                if !self.text_handlers.is_empty() {
+                    let mut text = c.to_string();
+                    for handler in &self.text_handlers {
+                        handler(&mut text);
+                    }
+                    *token = Token::Text(text);
+                }
+            }
+            Token::Comment(comment) => {
+                for handler in &self.comment_handlers {
+                    handler(comment);
+                }
+            }
+            Token::Template(content) => content.iter_mut().for_each(|token| self.handle(token)),
+            Token::DOCTYPE(_)
+            | Token::CDATASection(_)
+            | Token::ProcessingInstruction { .. }
+            | Token::EOF => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absolute_links() {
+        let rewriter = Rewriter::new().on_start_tag("a", |tag| {
+            if let Some(href) = tag.get_attribute("href") {
+                if href.starts_with('/') {
+                    let absolute = format!("https://example.com{}", href);
+                    tag.set_attribute("href", &absolute);
+                }
+            }
+        });
+
+        assert_eq!(
+            rewriter
+                .rewrite(r#"<p><A HREF="/docs">Docs</A> or <a href="https://x.org/">x</a></p>"#),
+            r#"<p><a href="https://example.com/docs">Docs</a> or <a href="https://x.org/">x</a></p>"#
+        );
+    }
+
+    #[test]
+    fn test_noopener_links() {
+        let rewriter = Rewriter::new().on_start_tag("a", |tag| {
+            if tag.get_attribute("target") == Some("_blank") {
+                tag.set_attribute("rel", "noopener noreferrer");
+            }
+        });
+
+        assert_eq!(
+            rewriter.rewrite(
+                r#"<a href="/a">a</a><a href="/b" target="_blank">b</a><a target=_blank rel=nofollow>c</a>"#
+            ),
+            r#"<a href="/a">a</a><a href="/b" target="_blank" rel="noopener noreferrer">b</a><a target="_blank" rel="noopener noreferrer">c</a>"#
+        );
+    }
+
+    #[test]
+    fn test_text_and_comments() {
+        let rewriter = Rewriter::new()
+            .on_text(|text| *text = text.to_uppercase())
+            .on_comment(|comment| comment.clear())
+            .rename_tag("b", "strong");
+
+        assert_eq!(
+            rewriter.rewrite("<!-- note --><b>hi &amp; bye</b>"),
+            "<!----><strong>HI &amp; BYE</strong>"
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::tokenizer::Token;
+
+// Disallowed elements whose contents are not text and are removed along with them.
+const DROP_CONTENT_ELEMENTS: [&str; 6] =
+    ["iframe", "noembed", "noframes", "script", "style", "xmp"];
+
+// URL schemes that run script when followed.
+const DANGEROUS_PREFIXES: [&str; 3] = ["javascript:", "vbscript:", "data:text/html"];
+
+// The tags and per-tag attributes that survive `sanitize`. Names are compared ASCII
+// case-insensitively.
+#[derive(Debug, Default, Clone)]
+pub struct Allowlist {
+    tags: HashSet<String>,
+    attributes: HashMap<String, HashSet<String>>,
+}
+
+impl Allowlist {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow_tag(mut self, name: &str) -> Self {
+        self.tags.insert(name.to_ascii_lowercase());
+        self
+    }
+
+    pub fn allow_attribute(mut self, tag: &str, attr: &str) -> Self {
+        self.attributes
+            .entry(tag.to_ascii_lowercase())
+            .or_default()
+            .insert(attr.to_ascii_lowercase());
+        self
+    }
+
+    fn is_tag_allowed(&self, name: &str) -> bool {
+        self.tags.contains(&name.to_ascii_lowercase())
+    }
+
+    fn is_attribute_allowed(&self, tag: &str, attr: &str) -> bool {
+        self.attributes
+            .get(&tag.to_ascii_lowercase())
+            .is_some_and(|attributes| attributes.contains(&attr.to_ascii_lowercase()))
+    }
+}
+
+// Removes every tag not in the allowlist, keeping the text inside it (except for elements such
+// as `<script>` whose contents are dropped too), and strips attributes the allowlist does not
+// permit for their tag. Attributes holding `javascript:` or similar URLs are always removed.
+// CDATA sections become text and processing instructions are removed.
+pub fn sanitize(tokens: Vec<Token>, allowlist: &Allowlist) -> Vec<Token> {
+    let mut sanitized = Vec::with_capacity(tokens.len());
+    // Set while skipping the contents of a removed element.
+    let mut dropping: Option<String> = None;
+
+    for mut token in tokens {
+        if let Some(name) = &dropping {
+            if token.is_end_tag() && token.tag_name() == Some(name.as_str()) {
+                dropping = None;
+            }
+            if !token.is_eof() {
+                continue;
+            }
+        }
+
+        match token {
+            // The serializer writes these out as they are, so markup inside them would come
+            // back as live markup once the output is parsed as HTML.
+            Token::CDATASection(data) => token = Token::Text(data),
+            Token::ProcessingInstruction { .. } => continue,
+            Token::Template(content) => token = Token::Template(sanitize(content, allowlist)),
+            _ => {}
+        }
+
+        if let Token::Tag(tag) = &mut token {
+            if !allowlist.is_tag_allowed(&tag.tag_name) {
+                if !tag.is_end_tag
+                    && !tag.self_closing
+                    && DROP_CONTENT_ELEMENTS.contains(&tag.tag_name.as_str())
+                {
+                    dropping = Some(tag.tag_name.to_string());
+                }
+                continue;
+            }
+
+            let name = tag.tag_name.clone();
+            tag.attributes.retain(|attr| {
+                allowlist.is_attribute_allowed(&name, &attr.qualified_name())
+                    && !is_dangerous(&attr.value)
+            });
+        }
+
+        sanitized.push(token);
+    }
+
+    sanitized
+}
+
+// Browsers ignore whitespace and control characters inside URLs, so `java\tscript:` still runs
+// script. Strips them and lowercases the rest so the scheme can be compared.
+pub(crate) fn normalize_url(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+fn is_dangerous(value: &str) -> bool {
+    let value = normalize_url(value);
+
+    DANGEROUS_PREFIXES
+        .iter()
+        .any(|prefix| value.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{serialize, Tokenizer};
+
+    fn sanitize_html(html: &str, allowlist: &Allowlist) -> String {
+        serialize(&sanitize(Tokenizer::new(html).collect(), allowlist))
+    }
+
+    #[test]
+    fn test_script_is_removed() {
+        let allowlist = Allowlist::new().allow_tag("p");
+
+        assert_eq!(sanitize_html("<script>alert(1)</script>", &allowlist), "");
+        assert_eq!(
+            sanitize_html("<p>a<style>p {}</style>b</p>", &allowlist),
+            "<p>ab</p>"
+        );
+    }
+
+    #[test]
+    fn test_markup_in_cdata_sections() {
+        let allowlist = Allowlist::new().allow_tag("p");
+        let tokens = vec![
+            Token::CDATASection("><img src=x onerror=alert(1)>".to_string()),
+            Token::ProcessingInstruction {
+                target: "x".to_string(),
+                data: "><img src=x onerror=alert(1)>".to_string(),
+            },
+        ];
+
+        let html = serialize(&sanitize(tokens, &allowlist));
+        assert!(!html.contains("<img"), "{}", html);
+
+        let html = sanitize_html(
+            "<p><![CDATA[><img src=x onerror=alert(1)>]]></p>",
+            &allowlist,
+        );
+        assert!(!html.contains("<img"), "{}", html);
+    }
+
+    #[test]
+    fn test_dangerous_urls() {
+        let allowlist = Allowlist::new()
+            .allow_tag("a")
+            .allow_tag("img")
+            .allow_attribute("a", "href")
+            .allow_attribute("img", "src");
+
+        assert_eq!(
+            sanitize_html(r#"<a href="javascript:void(0)">x</a>"#, &allowlist),
+            "<a>x</a>"
+        );
+        assert_eq!(
+            sanitize_html(
+                "<img src=\" JAVA\tSCRIPT:alert(1)\"><img src=\"data:text/html,<b>\"><a href=\"/ok\">y</a>",
+                &allowlist
+            ),
+            r#"<img><img><a href="/ok">y</a>"#
+        );
+    }
+
+    #[test]
+    fn test_disallowed_tags_keep_text() {
+        let allowlist = Allowlist::new()
+            .allow_tag("P")
+            .allow_tag("b")
+            .allow_attribute("p", "Class");
+
+        assert_eq!(
+            sanitize_html(
+                r#"<div id="x"><p class="a" onclick="go()">Hello <b>big</b> <u>world</u></p></div>"#,
+                &allowlist
+            ),
+            r#"<p class="a">Hello <b>big</b> world</p>"#
+        );
+    }
+}
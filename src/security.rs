@@ -0,0 +1,207 @@
+use crate::sanitizer::normalize_url;
+use crate::tokenizer::{Tag, Token};
+use crate::visitor::Visitor;
+
+// Attributes holding a URL that is loaded or followed.
+const URL_ATTRIBUTES: [&str; 5] = ["action", "formaction", "href", "src", "xlink:href"];
+
+// `data:` URLs of these types can run script when loaded as a document.
+const SCRIPTABLE_DATA_PREFIXES: [&str; 3] = [
+    "data:application/xhtml+xml",
+    "data:image/svg+xml",
+    "data:text/html",
+];
+
+// A way a start tag could run script, or markup hidden where a sanitizer might not look. Tag and
+// attribute names are lowercased.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum XssVector {
+    JavascriptUrl { tag: String, attribute: String },
+    DataUrl { tag: String, attribute: String },
+    InlineEventHandler { tag: String, attribute: String },
+    ScriptTag,
+    IframeTag,
+    MarkupInComment,
+    MarkupInCdataSection,
+}
+
+// Whether `data` holds a tag, or a `>` that would end the comment early if it is parsed as a
+// bogus comment, like a CDATA section in HTML content is.
+// https://html.spec.whatwg.org/#parse-error-cdata-in-html-content
+fn hides_markup(data: &str) -> bool {
+    data.contains('>')
+        || data.match_indices('<').any(|(index, _)| {
+            let rest = data[index + 1..]
+                .strip_prefix('/')
+                .unwrap_or(&data[index + 1..]);
+            rest.starts_with(|c: char| c.is_ascii_alphabetic())
+        })
+}
+
+// Collects the XSS vectors of every start tag it visits.
+#[derive(Debug, Default)]
+pub struct SecurityScanner {
+    pub vectors: Vec<XssVector>,
+}
+
+impl Visitor for SecurityScanner {
+    fn visit_start_tag(&mut self, tag: &Tag) {
+        let tag_name = tag.tag_name.to_ascii_lowercase();
+
+        match tag_name.as_str() {
+            "script" => self.vectors.push(XssVector::ScriptTag),
+            "iframe" => self.vectors.push(XssVector::IframeTag),
+            _ => {}
+        }
+
+        for attr in &tag.attributes {
+            let attribute = attr.qualified_name().to_ascii_lowercase();
+            let tag = tag_name.clone();
+
+            let vector = if attribute.len() > 2 && attribute.starts_with("on") {
+                Some(XssVector::InlineEventHandler { tag, attribute })
+            } else if URL_ATTRIBUTES.contains(&attribute.as_str()) {
+                let url = normalize_url(&attr.value);
+
+                if url.starts_with("javascript:") {
+                    Some(XssVector::JavascriptUrl { tag, attribute })
+                } else if SCRIPTABLE_DATA_PREFIXES
+                    .iter()
+                    .any(|prefix| url.starts_with(prefix))
+                {
+                    Some(XssVector::DataUrl { tag, attribute })
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            self.vectors.extend(vector);
+        }
+    }
+
+    fn visit_comment(&mut self, comment: &str) {
+        if hides_markup(comment) {
+            self.vectors.push(XssVector::MarkupInComment);
+        }
+    }
+}
+
+pub fn scan_for_xss(tokens: &[Token]) -> Vec<XssVector> {
+    let mut scanner = SecurityScanner::default();
+    scan_tokens(tokens, &mut scanner);
+    scanner.vectors
+}
+
+// `visit_tokens` reports CDATA sections as text, so the tokens are walked here instead.
+fn scan_tokens(tokens: &[Token], scanner: &mut SecurityScanner) {
+    for token in tokens {
+        match token {
+            Token::Tag(tag) if !tag.is_end_tag => scanner.visit_start_tag(tag),
+            Token::Comment(comment) => scanner.visit_comment(comment),
+            Token::CDATASection(data) if hides_markup(data) => {
+                scanner.vectors.push(XssVector::MarkupInCdataSection)
+            }
+            Token::Template(content) => scan_tokens(content, scanner),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tokenizer;
+
+    fn scan(html: &str) -> Vec<XssVector> {
+        scan_for_xss(&Tokenizer::new(html).collect::<Vec<_>>())
+    }
+
+    fn javascript_url(tag: &str, attribute: &str) -> XssVector {
+        XssVector::JavascriptUrl {
+            tag: tag.to_string(),
+            attribute: attribute.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_payloads() {
+        assert_eq!(scan("<script>alert(1)</script>"), [XssVector::ScriptTag]);
+        assert_eq!(
+            scan(r#"<img src=x onerror="alert(1)"/>"#),
+            [XssVector::InlineEventHandler {
+                tag: "img".to_string(),
+                attribute: "onerror".to_string(),
+            }]
+        );
+        assert_eq!(
+            scan(r#"<A HREF="  JaVaScRiPt:alert(1)">x</A>"#),
+            [javascript_url("a", "href")]
+        );
+        assert_eq!(
+            scan("<a href=\"java&#x09;script:alert(1)\">x</a>"),
+            [javascript_url("a", "href")]
+        );
+        assert_eq!(
+            scan(r#"<form action="javascript:alert(1)"><button formaction="javascript:x">"#),
+            [
+                javascript_url("form", "action"),
+                javascript_url("button", "formaction"),
+            ]
+        );
+        assert_eq!(
+            scan(r#"<iframe src="data:text/html;base64,PHNjcmlwdD4="></iframe>"#),
+            [
+                XssVector::IframeTag,
+                XssVector::DataUrl {
+                    tag: "iframe".to_string(),
+                    attribute: "src".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            scan(r#"<svg><a xlink:href="javascript:x">"#),
+            [javascript_url("a", "xlink:href")]
+        );
+    }
+
+    #[test]
+    fn test_safe_markup() {
+        assert_eq!(
+            scan(r#"<a href="/javascript:x">x</a><p on="x" data-onclick="y">javascript:x</p>"#),
+            []
+        );
+        assert_eq!(
+            scan(r#"<img src="data:image/png;base64,AAAA" alt="on"/>"#),
+            []
+        );
+        assert_eq!(scan("<!-- a < b --><svg><![CDATA[x<1]]></svg>"), []);
+    }
+
+    #[test]
+    fn test_markup_in_cdata_sections_and_comments() {
+        let payload = "><img src=x onerror=alert(1)>";
+        assert_eq!(
+            scan_for_xss(&[Token::CDATASection(payload.to_string())]),
+            [XssVector::MarkupInCdataSection]
+        );
+        assert_eq!(
+            scan_for_xss(&[Token::Template(vec![Token::Comment(
+                "<script>x</script>".to_string()
+            )])]),
+            [XssVector::MarkupInComment]
+        );
+
+        // Outside foreign content the CDATA section is a bogus comment that the `>` ends, so
+        // the `<img>` is live.
+        assert_eq!(
+            scan("<p><![CDATA[><img src=x onerror=alert(1)>]]></p>"),
+            [XssVector::InlineEventHandler {
+                tag: "img".to_string(),
+                attribute: "onerror".to_string(),
+            }]
+        );
+        assert_eq!(scan("<!--</p>-->"), [XssVector::MarkupInComment]);
+    }
+}
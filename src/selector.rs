@@ -0,0 +1,226 @@
+use crate::node::Node;
+use crate::tokenizer::Tag;
+
+// A compound selector: an optional type selector followed by any number of ID, class and
+// attribute selectors, e.g. `input[type="checkbox"].enabled`. Escapes, combinators and
+// pseudo-classes are not supported.
+// https://drafts.csswg.org/selectors/#compound
+#[derive(Debug, PartialEq, Clone)]
+pub struct SimpleSelector {
+    components: Vec<Component>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Component {
+    TagName(String),
+    Id(String),
+    Class(String),
+    Attribute { name: String, value: Option<String> },
+}
+
+impl SimpleSelector {
+    // Returns `None` if the selector is empty or outside the supported subset.
+    pub fn parse(selector: &str) -> Option<Self> {
+        let mut rest = selector.trim();
+        let mut components = Vec::new();
+
+        if let Some(after) = rest.strip_prefix('*') {
+            rest = after;
+        } else if let Some((name, after)) = take_ident(rest) {
+            components.push(Component::TagName(name.to_string()));
+            rest = after;
+        } else if rest.is_empty() {
+            return None;
+        }
+
+        while let Some(c) = rest.chars().next() {
+            let (component, after) = match c {
+                '#' => take_ident(&rest[1..])
+                    .map(|(id, after)| (Component::Id(id.to_string()), after))?,
+                '.' => take_ident(&rest[1..])
+                    .map(|(class, after)| (Component::Class(class.to_string()), after))?,
+                '[' => take_attribute(&rest[1..])?,
+                _ => return None,
+            };
+
+            components.push(component);
+            rest = after;
+        }
+
+        Some(SimpleSelector { components })
+    }
+
+    // Only start tags match; end tags don't stand for an element. Tag and attribute names are
+    // compared ASCII case-insensitively, IDs, classes and attribute values exactly.
+    pub fn matches(&self, tag: &Tag) -> bool {
+        !tag.is_end_tag && self.matches_element(&tag.tag_name, |name| tag.get_attribute(name))
+    }
+
+    // Like `matches`, for an element of a tree. Other nodes never match.
+    pub fn matches_node(&self, node: &Node) -> bool {
+        node.tag_name()
+            .is_some_and(|tag_name| self.matches_element(tag_name, |name| node.get_attribute(name)))
+    }
+
+    fn matches_element<'a>(
+        &self,
+        tag_name: &str,
+        get_attribute: impl Fn(&str) -> Option<&'a str>,
+    ) -> bool {
+        self.components.iter().all(|component| match component {
+            Component::TagName(name) => tag_name.eq_ignore_ascii_case(name),
+            Component::Id(id) => get_attribute("id") == Some(id),
+            Component::Class(class) => get_attribute("class")
+                .is_some_and(|classes| classes.split_ascii_whitespace().any(|name| name == class)),
+            Component::Attribute { name, value } => match value {
+                Some(value) => get_attribute(name) == Some(value),
+                None => get_attribute(name).is_some(),
+            },
+        })
+    }
+}
+
+// Splits a name made of letters, digits, `-`, `_` and non-ASCII characters off the start.
+// https://drafts.csswg.org/css-syntax/#ident-token-diagram
+fn take_ident(text: &str) -> Option<(&str, &str)> {
+    let len = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(text.len());
+
+    (len > 0).then(|| text.split_at(len))
+}
+
+// Parses what follows `[` in `[name]`, `[name=value]`, `[name="value"]` or `[name='value']`.
+fn take_attribute(text: &str) -> Option<(Component, &str)> {
+    let (name, rest) = take_ident(text.trim_start())?;
+    let rest = rest.trim_start();
+
+    let (value, rest) = match rest.strip_prefix('=') {
+        Some(rest) => {
+            let rest = rest.trim_start();
+            let (value, rest) = match rest.chars().next()? {
+                quote @ ('"' | '\'') => {
+                    let end = rest[1..].find(quote)? + 1;
+                    (&rest[1..end], &rest[end + 1..])
+                }
+                _ => take_ident(rest)?,
+            };
+            (Some(value.to_string()), rest.trim_start())
+        }
+        None => (None, rest),
+    };
+
+    let rest = rest.strip_prefix(']')?;
+    let component = Component::Attribute {
+        name: name.to_string(),
+        value,
+    };
+
+    Some((component, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Token, Tokenizer};
+
+    use super::*;
+
+    fn tag(html: &str) -> Tag {
+        match Tokenizer::new(html).next() {
+            Some(Token::Tag(tag)) => tag,
+            token => panic!("expected a tag, got {:?}", token),
+        }
+    }
+
+    #[test]
+    fn test_simple_selectors() {
+        let div = tag(r#"<DIV id="main" class="container wide" data-x="1">"#);
+
+        for selector in [
+            "div",
+            "DIV",
+            "*",
+            "#main",
+            ".container",
+            ".wide",
+            "[data-x]",
+            "[DATA-X]",
+            "[data-x=1]",
+            "[data-x=\"1\"]",
+            "[data-x='1']",
+            "[ data-x = \"1\" ]",
+            " div ",
+        ] {
+            assert!(div.matches_selector(selector), "{}", selector);
+        }
+
+        for selector in [
+            "span",
+            "#Main",
+            "#other",
+            ".Container",
+            ".contain",
+            "[href]",
+            "[data-x=2]",
+            "[data-x=\"\"]",
+        ] {
+            assert!(!div.matches_selector(selector), "{}", selector);
+        }
+    }
+
+    #[test]
+    fn test_compound_selectors() {
+        let checkbox = tag(r#"<input type="checkbox" class="enabled large" name=agree checked>"#);
+
+        for selector in [
+            r#"input[type="checkbox"].enabled"#,
+            "input.enabled.large",
+            ".large.enabled",
+            "*[name=agree][checked]",
+            "[type=checkbox][checked].enabled",
+        ] {
+            assert!(checkbox.matches_selector(selector), "{}", selector);
+        }
+
+        for selector in [
+            r#"input[type="text"].enabled"#,
+            "input.enabled.disabled",
+            "a[type=checkbox]",
+            "input#agree",
+        ] {
+            assert!(!checkbox.matches_selector(selector), "{}", selector);
+        }
+
+        let link = tag(r#"<a href="/docs" title="a ] b">"#);
+        assert!(link.matches_selector("a[href]"));
+        assert!(link.matches_selector("a[title=\"a ] b\"]"));
+        assert!(!tag("<a>").matches_selector("a[href]"));
+    }
+
+    #[test]
+    fn test_invalid_selectors() {
+        let div = tag("<div class=a>");
+
+        for selector in [
+            "",
+            " ",
+            "div p",
+            "div > p",
+            ".",
+            "#",
+            "[",
+            "[]",
+            "[a",
+            "[a=]",
+            "[a=\"b]",
+            "div:hover",
+            "a,b",
+            "**",
+        ] {
+            assert_eq!(SimpleSelector::parse(selector), None, "{:?}", selector);
+            assert!(!div.matches_selector(selector), "{:?}", selector);
+        }
+
+        assert!(!tag("</div>").matches_selector("div"));
+    }
+}
@@ -0,0 +1,352 @@
+use crate::elements::VOID_ELEMENTS;
+use crate::node::Node;
+use crate::tokenizer::{Doctype, Tag, Token};
+
+// https://html.spec.whatwg.org/#serialising-html-fragments
+// Text inside these elements is written out as is; escaping it would change its meaning.
+pub(crate) const LITERAL_TEXT_ELEMENTS: [&str; 7] = [
+    "iframe",
+    "noembed",
+    "noframes",
+    "plaintext",
+    "script",
+    "style",
+    "xmp",
+];
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SerializerOptions {
+    // Puts every tag on its own line, indented by nesting depth, and drops whitespace-only
+    // text. The output is easier to read but no longer re-tokenizes to the same tokens.
+    pub pretty: bool,
+    pub indent_width: usize,
+}
+
+impl Default for SerializerOptions {
+    fn default() -> Self {
+        SerializerOptions {
+            pretty: false,
+            indent_width: 2,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Serializer {
+    options: SerializerOptions,
+}
+
+impl Serializer {
+    pub fn new(options: SerializerOptions) -> Self {
+        Serializer { options }
+    }
+
+    pub fn serialize(&self, tokens: &[Token]) -> String {
+        let mut html = String::new();
+        self.write_tokens(&mut html, tokens, &mut 0);
+        html
+    }
+
+    fn write_tokens(&self, html: &mut String, tokens: &[Token], depth: &mut usize) {
+        // The element whose contents are written without escaping, if inside one.
+        let mut literal_text: Option<&str> = None;
+
+        for token in tokens {
+            match token {
+                Token::DOCTYPE(doctype) => {
+                    self.start_line(html, *depth);
+                    html.push_str(&doctype.to_string());
+                }
+                Token::Tag(tag) if tag.is_end_tag => {
+                    if VOID_ELEMENTS.contains(tag.tag_name.as_str()) {
+                        continue;
+                    }
+
+                    if literal_text.is_some_and(|name| name == tag.tag_name) {
+                        literal_text = None;
+                    }
+
+                    *depth = depth.saturating_sub(1);
+                    self.start_line(html, *depth);
+                    html.push_str("</");
+                    html.push_str(&tag.tag_name);
+                    html.push('>');
+                }
+                Token::Tag(tag) => {
+                    self.start_line(html, *depth);
+                    write_start_tag(html, tag);
+
+                    if !tag.self_closing && !tag.is_void_element() {
+                        *depth += 1;
+
+                        if LITERAL_TEXT_ELEMENTS.contains(&tag.tag_name.as_str()) {
+                            literal_text = Some(&tag.tag_name);
+                        }
+                    }
+                }
+                Token::Comment(comment) => {
+                    self.start_line(html, *depth);
+                    html.push_str("<!--");
+                    html.push_str(comment);
+                    html.push_str("-->");
+                }
+                Token::Char(c) => self.write_text(html, &c.to_string(), *depth, literal_text),
+                Token::Text(text) => self.write_text(html, text, *depth, literal_text),
+                Token::CDATASection(data) => {
+                    self.start_line(html, *depth);
+                    html.push_str("<![CDATA[");
+                    html.push_str(data);
+                    html.push_str("]]>");
+                }
+                Token::ProcessingInstruction { .. } => {
+                    self.start_line(html, *depth);
+                    html.push_str(&token.to_string());
+                }
+                Token::Template(content) => self.write_tokens(html, content, depth),
+                Token::EOF => {}
+            }
+        }
+    }
+
+    fn write_text(&self, html: &mut String, text: &str, depth: usize, literal: Option<&str>) {
+        let text = if self.options.pretty {
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                return;
+            }
+            self.start_line(html, depth);
+            trimmed
+        } else {
+            text
+        };
+
+        if literal.is_some() {
+            html.push_str(text);
+        } else {
+            escape_text(html, text);
+        }
+    }
+
+    fn start_line(&self, html: &mut String, depth: usize) {
+        if self.options.pretty {
+            if !html.is_empty() {
+                html.push('\n');
+            }
+            html.extend(std::iter::repeat_n(' ', depth * self.options.indent_width));
+        }
+    }
+}
+
+// Writes the tokens back out as HTML that tokenizes to the same tokens.
+pub fn serialize(tokens: &[Token]) -> String {
+    Serializer::default().serialize(tokens)
+}
+
+fn write_start_tag(html: &mut String, tag: &Tag) {
+    html.push('<');
+    html.push_str(&tag.tag_name);
+
+    for attr in &tag.attributes {
+        html.push(' ');
+        html.push_str(&attr.qualified_name());
+        // https://html.spec.whatwg.org/#boolean-attributes
+        if attr.is_boolean() && attr.value.is_empty() {
+            continue;
+        }
+        html.push('=');
+        escape_attribute(html, &attr.value, '"');
+    }
+
+    if tag.self_closing {
+        html.push('/');
+    }
+
+    html.push('>');
+}
+
+// Writes a node of the tree, working from the tree rather than from tokens. `parent` is the
+// tag name of the element the node is in, if any.
+// https://html.spec.whatwg.org/#serialising-html-fragments
+pub(crate) fn serialize_node(html: &mut String, node: &Node, parent: Option<&str>) {
+    match node {
+        Node::Element {
+            tag_name,
+            attributes,
+            ..
+        } => {
+            html.push('<');
+            html.push_str(tag_name);
+
+            for attr in attributes {
+                html.push(' ');
+                html.push_str(&attr.qualified_name());
+                if attr.is_boolean() && attr.value.is_empty() {
+                    continue;
+                }
+                html.push('=');
+                escape_attribute(html, &attr.value, '"');
+            }
+            html.push('>');
+
+            if VOID_ELEMENTS.contains(tag_name.as_str()) {
+                return;
+            }
+
+            serialize_children(html, node);
+            html.push_str("</");
+            html.push_str(tag_name);
+            html.push('>');
+        }
+        Node::Text(text) => {
+            if parent.is_some_and(|name| LITERAL_TEXT_ELEMENTS.contains(&name)) {
+                html.push_str(text);
+            } else {
+                escape_string(html, text);
+            }
+        }
+        Node::Comment(comment) => {
+            html.push_str("<!--");
+            html.push_str(comment);
+            html.push_str("-->");
+        }
+        Node::Doctype(doctype) => serialize_doctype(html, doctype),
+    }
+}
+
+pub(crate) fn serialize_children(html: &mut String, node: &Node) {
+    for child in node.children() {
+        serialize_node(html, child, node.tag_name());
+    }
+}
+
+// Only the name is kept; identifiers are dropped.
+pub(crate) fn serialize_doctype(html: &mut String, doctype: &Doctype) {
+    html.push_str("<!DOCTYPE ");
+    html.push_str(&doctype.name);
+    html.push('>');
+}
+
+// https://html.spec.whatwg.org/#escapingString
+fn escape_string(html: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '\u{A0}' => html.push_str("&nbsp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            _ => html.push(c),
+        }
+    }
+}
+
+pub(crate) fn escape_text(html: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            _ => html.push(c),
+        }
+    }
+}
+
+// Writes `value` as an attribute value wrapped in `quote`, which is either `"` or `'`.
+// https://html.spec.whatwg.org/#escapingString
+pub(crate) fn escape_attribute(html: &mut String, value: &str, quote: char) {
+    html.push(quote);
+    for c in value.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '\u{A0}' => html.push_str("&nbsp;"),
+            '"' if quote == '"' => html.push_str("&quot;"),
+            '\'' if quote == '\'' => html.push_str("&#39;"),
+            _ => html.push(c),
+        }
+    }
+    html.push(quote);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tokenizer;
+
+    fn round_trip(html: &str) {
+        let tokens: Vec<Token> = Tokenizer::new(html).collect();
+        let serialized = serialize(&tokens);
+
+        assert_eq!(
+            Tokenizer::new(&serialized).collect::<Vec<_>>(),
+            tokens,
+            "{}",
+            serialized
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let html = r#"<!DOCTYPE html><p class=intro title='say "hi"'>a &lt; b &amp;&amp; c<br/></p><!--note-->"#;
+        let tokens: Vec<Token> = Tokenizer::new(html).collect();
+
+        assert_eq!(
+            serialize(&tokens),
+            r#"<!DOCTYPE html><p class="intro" title="say &quot;hi&quot;">a &lt; b &amp;&amp; c<br/></p><!--note-->"#
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip(r#"<!DOCTYPE html><html lang="en"><head><title>a < b</title></head></html>"#);
+        round_trip(r#"<p hidden data-x='1 > 0'>x &gt; y<img src=a.png alt=""/></p>"#);
+        round_trip("<script>if (a < b && c) {}</script><style>p > a {}</style>");
+        round_trip("<svg><![CDATA[a < b]]></svg>");
+        round_trip(
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD HTML 4.01//EN" "http://www.w3.org/TR/html4/strict.dtd">"#,
+        );
+    }
+
+    #[test]
+    fn test_escape_attribute() {
+        let mut html = String::new();
+        escape_attribute(&mut html, "a&b \"c\" 'd'\u{A0}", '"');
+        escape_attribute(&mut html, "a&b \"c\" 'd'", '\'');
+
+        assert_eq!(
+            html,
+            "\"a&amp;b &quot;c&quot; 'd'&nbsp;\"'a&amp;b \"c\" &#39;d&#39;'"
+        );
+    }
+
+    #[test]
+    fn test_boolean_attributes() {
+        let tokens: Vec<Token> =
+            Tokenizer::new(r#"<input disabled checked=checked value="" required="">"#).collect();
+
+        assert_eq!(
+            serialize(&tokens),
+            r#"<input disabled checked="checked" value="" required>"#
+        );
+    }
+
+    #[test]
+    fn test_void_end_tags_are_dropped() {
+        let tokens: Vec<Token> = Tokenizer::new("<p>a<br></br></p>").collect();
+
+        assert_eq!(serialize(&tokens), "<p>a<br></p>");
+    }
+
+    #[test]
+    fn test_pretty() {
+        let tokens: Vec<Token> =
+            Tokenizer::new("<ul>\n<li>One</li>  <li>Two<br/></li></ul>").collect();
+        let serializer = Serializer::new(SerializerOptions {
+            pretty: true,
+            ..SerializerOptions::default()
+        });
+
+        assert_eq!(
+            serializer.serialize(&tokens),
+            "<ul>\n  <li>\n    One\n  </li>\n  <li>\n    Two\n    <br/>\n  </li>\n</ul>"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use crate::optional_tags::is_end_tag_optional;
+use crate::tokenizer::{Token, Tokenizer};
+
+// Elements that start a new line of text when rendered.
+const BLOCK_ELEMENTS: [&str; 27] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "p",
+    "section",
+    "tr",
+];
+
+// Landmark roles as a screen reader would announce them.
+fn landmark(tag_name: &str) -> Option<&'static str> {
+    match tag_name {
+        "nav" => Some("navigation"),
+        "header" => Some("header"),
+        "footer" => Some("footer"),
+        "main" => Some("main"),
+        "aside" => Some("complementary"),
+        "form" => Some("form"),
+        _ => None,
+    }
+}
+
+// Renders the document the way a screen reader would read it: image alt text and
+// `aria-label`s replace the content they describe, landmarks are announced, script and
+// style contents are dropped and whitespace is collapsed.
+pub fn html_to_accessible_text(html: &str) -> String {
+    let mut text = String::new();
+    // While set, the element whose content is being skipped and the elements open inside it.
+    let mut skipping: Option<(String, Vec<String>)> = None;
+
+    for token in Tokenizer::new(html) {
+        if let Some((name, open)) = &mut skipping {
+            // Whether the skipped element ended before `token` without an end tag of its own,
+            // e.g. `<li>` before another `<li>`. The token is then read as usual.
+            let implied_end = match &token {
+                Token::Tag(tag) if tag.is_end_tag => {
+                    if let Some(index) = open.iter().rposition(|open| *open == tag.tag_name) {
+                        open.truncate(index);
+                        false
+                    } else if tag.tag_name == *name {
+                        skipping = None;
+                        continue;
+                    } else {
+                        open.is_empty() && is_end_tag_optional(name, Some(&token), None)
+                    }
+                }
+                Token::Tag(tag) => {
+                    let implied_end =
+                        open.is_empty() && is_end_tag_optional(name, Some(&token), None);
+                    if !implied_end && !tag.self_closing && !tag.is_void_element() {
+                        open.push(tag.tag_name.to_string());
+                    }
+                    implied_end
+                }
+                Token::EOF => true,
+                _ => false,
+            };
+
+            if !implied_end {
+                continue;
+            }
+            skipping = None;
+        }
+
+        match token {
+            Token::Char(c) => text.push(c),
+            Token::Text(content) | Token::CDATASection(content) => text.push_str(&content),
+            Token::Tag(tag) if tag.is_end_tag => {
+                if BLOCK_ELEMENTS.contains(&tag.tag_name.as_str()) {
+                    text.push(' ');
+                }
+            }
+            Token::Tag(tag) => {
+                let name = tag.tag_name.as_str();
+                let label = tag.get_attribute("aria-label");
+
+                if BLOCK_ELEMENTS.contains(&name) {
+                    text.push(' ');
+                }
+
+                if let Some(landmark) = landmark(name) {
+                    text.push_str(landmark);
+                    text.push(' ');
+                }
+
+                let has_content = !tag.self_closing && !tag.is_void_element();
+
+                if let Some(label) = label {
+                    text.push_str(label);
+                    text.push(' ');
+
+                    if has_content {
+                        skipping = Some((tag.tag_name.to_string(), Vec::new()));
+                    }
+                } else if name == "img" {
+                    if let Some(alt) = tag.get_attribute("alt") {
+                        text.push(' ');
+                        text.push_str(alt);
+                        text.push(' ');
+                    }
+                } else if (name == "script" || name == "style") && has_content {
+                    skipping = Some((tag.tag_name.to_string(), Vec::new()));
+                }
+            }
+            // The contents of a template are not rendered.
+            Token::DOCTYPE(_)
+            | Token::Comment(_)
+            | Token::ProcessingInstruction { .. }
+            | Token::Template(_)
+            | Token::EOF => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// The text of the tokens, leaving out markup, comments and the contents of `<script>` and
+// `<style>` elements. Whitespace is kept as is.
+pub fn collect_text(tokens: &[Token]) -> String {
+    collect_text_with_separator(tokens, "")
+}
+
+// Like `collect_text`, but puts `block_separator` between the text of different block-level
+// elements, e.g. `"\n"` to get one line per paragraph. The separator is never repeated and
+// never starts or ends the text.
+pub fn collect_text_with_separator(tokens: &[Token], block_separator: &str) -> String {
+    let mut text = String::new();
+    // The number of open `<script>` and `<style>` elements.
+    let mut hidden = 0usize;
+    // Set when a block boundary was passed since the last text.
+    let mut at_boundary = false;
+
+    for token in tokens {
+        let content = match token {
+            Token::Char(c) => c.to_string(),
+            Token::Text(content) | Token::CDATASection(content) => content.clone(),
+            Token::Tag(tag) => {
+                let name = tag.tag_name.to_ascii_lowercase();
+
+                if name == "script" || name == "style" {
+                    if tag.is_end_tag {
+                        hidden = hidden.saturating_sub(1);
+                    } else if !tag.self_closing {
+                        hidden += 1;
+                    }
+                } else if BLOCK_ELEMENTS.contains(&name.as_str()) {
+                    at_boundary = true;
+                }
+                continue;
+            }
+            Token::DOCTYPE(_)
+            | Token::Comment(_)
+            | Token::ProcessingInstruction { .. }
+            | Token::Template(_)
+            | Token::EOF => continue,
+        };
+
+        if hidden > 0 || content.is_empty() {
+            continue;
+        }
+
+        if at_boundary && !text.is_empty() {
+            text.push_str(block_separator);
+        }
+        at_boundary = false;
+        text.push_str(&content);
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::Tag;
+
+    #[test]
+    fn test_collect_text() {
+        let tokens = Tokenizer::new(
+            "<h1>Title</h1><p>One <b>two</b></p><script>var x = '<p>';</script><style>p {}</style>\
+             <p>three<br/>four</p><!-- five -->",
+        )
+        .collect::<Vec<_>>();
+
+        assert_eq!(collect_text(&tokens), "TitleOne twothreefour");
+        assert_eq!(
+            collect_text_with_separator(&tokens, "\n"),
+            "Title\nOne two\nthree\nfour"
+        );
+    }
+
+    #[test]
+    fn test_collect_text_from_chars() {
+        let mut tokens = vec![Token::Tag(Tag::new_start_tag_with_name(
+            "script".to_string(),
+        ))];
+        tokens.extend("alert(1)".chars().map(Token::Char));
+        tokens.push(Token::Tag(Tag::new_end_tag_with_name("script".to_string())));
+        tokens.extend("hi".chars().map(Token::Char));
+
+        assert_eq!(collect_text(&tokens), "hi");
+    }
+
+    #[test]
+    fn test_image_alt_text() {
+        let html = r#"<p>Welcome to <img src="logo.png" alt="Company logo"> online.</p>"#;
+
+        assert_eq!(
+            html_to_accessible_text(html),
+            "Welcome to Company logo online."
+        );
+    }
+
+    #[test]
+    fn test_aria_label_replaces_content() {
+        let html = r#"<button aria-label="Close dialog"><span>×</span></button><p>Body</p>"#;
+
+        assert_eq!(html_to_accessible_text(html), "Close dialog Body");
+    }
+
+    #[test]
+    fn test_aria_label_on_element_without_end_tag() {
+        assert_eq!(
+            html_to_accessible_text(r#"<ul><li aria-label="a">x<li>y</ul><p>z"#),
+            "a y z"
+        );
+        assert_eq!(
+            html_to_accessible_text(r#"<div><p aria-label="a"><b>x</b>y</div>z"#),
+            "a z"
+        );
+    }
+
+    #[test]
+    fn test_landmarks_scripts_and_whitespace() {
+        let html = r#"<header><h1>Title</h1></header>
+            <nav><a href="/">Home</a>   <a href="/about">About</a></nav>
+            <script>var hidden = "<p>not read</p>";</script>
+            <style>p { color: red; }</style>
+            <main><p>First</p><p>Second</p></main>"#;
+
+        assert_eq!(
+            html_to_accessible_text(html),
+            "header Title navigation Home About main First Second"
+        );
+    }
+}
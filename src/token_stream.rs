@@ -0,0 +1,244 @@
+use std::ops::Range;
+
+use crate::selector::SimpleSelector;
+use crate::tokenizer::{Tag, Token, Tokenizer};
+
+// An owned sequence of tokens with helpers for the common queries.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct TokenStream {
+    tokens: Vec<Token>,
+}
+
+impl TokenStream {
+    pub fn new(tokens: Vec<Token>) -> Self {
+        TokenStream { tokens }
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    pub fn start_tags(&self) -> impl Iterator<Item = &Tag> {
+        self.tokens
+            .iter()
+            .filter_map(Token::as_tag)
+            .filter(|tag| !tag.is_end_tag)
+    }
+
+    pub fn end_tags_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Tag> {
+        self.tokens
+            .iter()
+            .filter_map(Token::as_tag)
+            .filter(move |tag| tag.is_end_tag && tag.tag_name.eq_ignore_ascii_case(name))
+    }
+
+    // The start tags matching a selector; see `SimpleSelector`. An invalid selector matches
+    // nothing.
+    pub fn select<'a>(&'a self, selector: &str) -> impl Iterator<Item = &'a Tag> {
+        let selector = SimpleSelector::parse(selector);
+
+        self.start_tags().filter(move |tag| {
+            selector
+                .as_ref()
+                .is_some_and(|selector| selector.matches(tag))
+        })
+    }
+
+    pub fn comments(&self) -> impl Iterator<Item = &str> {
+        self.tokens.iter().filter_map(Token::as_comment)
+    }
+
+    // All character data in document order, without any markup.
+    pub fn text_content(&self) -> String {
+        let mut text = String::new();
+
+        for token in &self.tokens {
+            match token {
+                Token::Char(c) => text.push(*c),
+                Token::Text(content) | Token::CDATASection(content) => text.push_str(content),
+                _ => {}
+            }
+        }
+
+        text
+    }
+
+    // Keeps only the start and end tags with the given name.
+    pub fn filter_by_tag(self, name: &str) -> Self {
+        self.retain(|token| {
+            token
+                .tag_name()
+                .is_some_and(|tag_name| tag_name.eq_ignore_ascii_case(name))
+        })
+    }
+
+    pub fn strip_comments(self) -> Self {
+        self.retain(|token| !token.is_comment())
+    }
+
+    pub fn balanced_pairs(&self) -> Vec<(usize, usize)> {
+        balanced_pairs(&self.tokens)
+    }
+
+    pub fn into_tokens(self) -> Vec<Token> {
+        self.tokens
+    }
+
+    fn retain(mut self, keep: impl FnMut(&Token) -> bool) -> Self {
+        self.tokens.retain(keep);
+        self
+    }
+}
+
+// The indices of every start tag and the end tag that closes it, ordered by start tag. An end
+// tag closes the innermost open element with its name, leaving any elements opened inside it
+// unpaired; end tags with no open element and start tags never closed get no pair. Void
+// elements and self-closing tags have no end tag.
+pub fn balanced_pairs(tokens: &[Token]) -> Vec<(usize, usize)> {
+    let mut open: Vec<(usize, &str)> = Vec::new();
+    let mut pairs = Vec::new();
+
+    for (index, tag) in tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(index, token)| Some((index, token.as_tag()?)))
+    {
+        if !tag.is_end_tag {
+            if !tag.self_closing && !tag.is_void_element() {
+                open.push((index, &tag.tag_name));
+            }
+            continue;
+        }
+
+        if let Some(position) = open
+            .iter()
+            .rposition(|(_, name)| name.eq_ignore_ascii_case(&tag.tag_name))
+        {
+            pairs.push((open[position].0, index));
+            open.truncate(position);
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+// The tokens of the `occurrence`-th (from zero) closed element named `tag_name`, from its start
+// tag through its end tag.
+pub fn find_element_range(
+    tokens: &[Token],
+    tag_name: &str,
+    occurrence: usize,
+) -> Option<Range<usize>> {
+    balanced_pairs(tokens)
+        .into_iter()
+        .filter(|&(start, _)| {
+            tokens[start]
+                .tag_name()
+                .is_some_and(|name| name.eq_ignore_ascii_case(tag_name))
+        })
+        .nth(occurrence)
+        .map(|(start, end)| start..end + 1)
+}
+
+impl<I: Iterator<Item = char>> From<Tokenizer<I>> for TokenStream {
+    fn from(tokenizer: Tokenizer<I>) -> Self {
+        tokenizer.into_token_stream()
+    }
+}
+
+impl From<Vec<Token>> for TokenStream {
+    fn from(tokens: Vec<Token>) -> Self {
+        TokenStream::new(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queries() {
+        let stream = TokenStream::from(Tokenizer::new(
+            "<p>hello <!-- comment --> world</p><br><p>again</p>",
+        ));
+
+        assert_eq!(
+            stream
+                .start_tags()
+                .map(|tag| tag.tag_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["p", "br", "p"]
+        );
+        assert_eq!(stream.end_tags_named("P").count(), 2);
+        assert_eq!(stream.comments().collect::<Vec<_>>(), vec![" comment "]);
+        assert_eq!(stream.text_content(), "hello  worldagain");
+    }
+
+    #[test]
+    fn test_select() {
+        let stream = TokenStream::from(Tokenizer::new(
+            r#"<div class="container"><a href="/a">a</a><a>b</a><a href="/c" class=ext>c</a></div>"#,
+        ));
+
+        assert_eq!(
+            stream
+                .select("a[href]")
+                .filter_map(|tag| tag.href())
+                .collect::<Vec<_>>(),
+            vec!["/a", "/c"]
+        );
+        assert_eq!(stream.select("div.container").count(), 1);
+        assert_eq!(stream.select("a.ext[href=\"/c\"]").count(), 1);
+        assert_eq!(stream.select("div a").count(), 0);
+    }
+
+    #[test]
+    fn test_text_content_with_chars() {
+        let stream = TokenStream::new(vec![
+            Token::Char('a'),
+            Token::Text("bc".to_string()),
+            Token::Comment("x".to_string()),
+            Token::Char('d'),
+            Token::CDATASection("<e>".to_string()),
+            Token::EOF,
+        ]);
+
+        assert_eq!(stream.text_content(), "abcd<e>");
+    }
+
+    #[test]
+    fn test_filters() {
+        let html = "<p>hello <!-- comment --> world</p><div><p>x</p></div>";
+
+        let paragraphs = TokenStream::from(Tokenizer::new(html)).filter_by_tag("p");
+        assert_eq!(paragraphs.tokens().len(), 4);
+        assert!(paragraphs
+            .tokens()
+            .iter()
+            .all(|token| token.tag_name() == Some("p")));
+
+        let stripped = TokenStream::from(Tokenizer::new(html)).strip_comments();
+        assert_eq!(stripped.comments().count(), 0);
+        assert_eq!(stripped.text_content(), "hello  worldx");
+        assert_eq!(stripped.into_tokens().last(), Some(&Token::EOF));
+    }
+
+    #[test]
+    fn test_balanced_pairs() {
+        let tokens: Vec<Token> =
+            Tokenizer::new("<div><div>a<br></div><p>b</div></span><div>c</div>").collect();
+
+        // The `<p>` is never closed and `</span>` closes nothing.
+        let pairs = balanced_pairs(&tokens);
+        assert_eq!(pairs, vec![(0, 7), (1, 4), (9, 11)]);
+        assert_eq!(TokenStream::new(tokens.clone()).balanced_pairs(), pairs);
+
+        assert_eq!(find_element_range(&tokens, "div", 0), Some(0..8));
+        assert_eq!(find_element_range(&tokens, "DIV", 1), Some(1..5));
+        assert_eq!(find_element_range(&tokens, "div", 2), Some(9..12));
+        assert_eq!(find_element_range(&tokens, "div", 3), None);
+        assert_eq!(find_element_range(&tokens, "p", 0), None);
+        assert_eq!(TokenStream::new(tokens[9..12].to_vec()).text_content(), "c");
+    }
+}
@@ -0,0 +1,98 @@
+// A parse error and the position of the input character the tokenizer was at when it found
+// it. Lines and columns start at 1, as in `Span`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, line: u32, column: u32) -> Self {
+        ParseError { kind, line, column }
+    }
+
+    pub fn position(&self) -> (u32, u32) {
+        (self.line, self.column)
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+// https://html.spec.whatwg.org/#parse-errors
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseErrorKind {
+    AbruptClosingOfEmptyComment,
+    AbruptDoctypePublicIdentifier,
+    AbruptDoctypeSystemIdentifier,
+    AbsenceOfDigitsInNumericCharacterReference,
+    CdataInHtmlContent,
+    CharacterReferenceOutsideUnicodeRange,
+    ControlCharacterInInputStream,
+    ControlCharacterReference,
+    DuplicateAttribute,
+    EndTagWithAttributes,
+    EndTagWithTrailingSolidus,
+    EofBeforeTagName,
+    EofInCdata,
+    EofInComment,
+    EofInDoctype,
+    EofInScriptHtmlCommentLikeText,
+    EofInTag,
+    IncorrectlyClosedComment,
+    IncorrectlyOpenedComment,
+    InvalidCharacterSequenceAfterDoctypeName,
+    InvalidFirstCharacterOfTagName,
+    MissingAttributeValue,
+    MissingDoctypeName,
+    MissingDoctypePublicIdentifier,
+    MissingDoctypeSystemIdentifier,
+    MissingEndTagName,
+    MissingQuoteBeforeDoctypePublicIdentifier,
+    MissingQuoteBeforeDoctypeSystemIdentifier,
+    MissingSemicolonAfterCharacterReference,
+    MissingWhitespaceAfterDoctypePublicKeyword,
+    MissingWhitespaceAfterDoctypeSystemKeyword,
+    MissingWhitespaceBeforeDoctypeName,
+    MissingWhitespaceBetweenAttributes,
+    MissingWhitespaceBetweenDoctypePublicAndSystemIdentifiers,
+    NestedComment,
+    NoncharacterCharacterReference,
+    NoncharacterInInputStream,
+    NullCharacterReference,
+    SurrogateCharacterReference,
+    SurrogateInInputStream,
+    UnexpectedCharacterAfterDoctypeSystemIdentifier,
+    UnexpectedCharacterInAttributeName,
+    UnexpectedCharacterInUnquotedAttributeValue,
+    UnexpectedEqualsSignBeforeAttributeName,
+    UnexpectedNullCharacter,
+    UnexpectedQuestionMarkInsteadOfTagName,
+    UnexpectedSolidusInTag,
+    UnknownNamedCharacterReference,
+}
+
+// The error's code in the spec, e.g. `eof-in-tag` for `EofInTag`.
+impl std::fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, c) in format!("{:?}", self).chars().enumerate() {
+            if c.is_ascii_uppercase() {
+                if i > 0 {
+                    f.write_str("-")?;
+                }
+                write!(f, "{}", c.to_ascii_lowercase())?;
+            } else {
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use super::State;
+
+// Settings for `Tokenizer::with_options`, built by chaining setters:
+// `TokenizerOptions::new().initial_state(State::RCDATA).scripting_enabled(false).build()`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TokenizerOptions {
+    pub initial_state: State,
+    // https://html.spec.whatwg.org/#scripting-flag
+    // When set, the contents of `<noscript>` are raw text rather than markup.
+    pub scripting_enabled: bool,
+    pub preserve_comments: bool,
+    // Attributes past this many on a single tag are dropped.
+    pub max_attribute_count: Option<usize>,
+    pub collect_parse_errors: bool,
+    // Tag names are shared between tags with the same name instead of each tag allocating its
+    // own. See `TagName`.
+    pub intern_tag_names: bool,
+    // `<?target data?>` becomes a `Token::ProcessingInstruction` instead of the bogus comment
+    // the spec calls for, for HTML that embeds XML processing instructions.
+    pub processing_instructions: bool,
+    // The tokens between `<template>` and `</template>` are emitted as one `Token::Template`
+    // instead of one by one, keeping the inert contents of a template apart from the page.
+    // https://html.spec.whatwg.org/#the-template-element
+    pub template_contents: bool,
+}
+
+impl TokenizerOptions {
+    pub fn new() -> Self {
+        TokenizerOptions {
+            initial_state: State::Data,
+            scripting_enabled: false,
+            preserve_comments: true,
+            max_attribute_count: None,
+            collect_parse_errors: true,
+            intern_tag_names: false,
+            processing_instructions: false,
+            template_contents: false,
+        }
+    }
+
+    pub fn initial_state(mut self, state: State) -> Self {
+        self.initial_state = state;
+        self
+    }
+
+    pub fn scripting_enabled(mut self, enabled: bool) -> Self {
+        self.scripting_enabled = enabled;
+        self
+    }
+
+    pub fn preserve_comments(mut self, preserve: bool) -> Self {
+        self.preserve_comments = preserve;
+        self
+    }
+
+    pub fn max_attribute_count(mut self, count: usize) -> Self {
+        self.max_attribute_count = Some(count);
+        self
+    }
+
+    pub fn collect_parse_errors(mut self, collect: bool) -> Self {
+        self.collect_parse_errors = collect;
+        self
+    }
+
+    pub fn intern_tag_names(mut self, intern: bool) -> Self {
+        self.intern_tag_names = intern;
+        self
+    }
+
+    pub fn processing_instructions(mut self, enabled: bool) -> Self {
+        self.processing_instructions = enabled;
+        self
+    }
+
+    pub fn template_contents(mut self, enabled: bool) -> Self {
+        self.template_contents = enabled;
+        self
+    }
+
+    pub fn build(self) -> Self {
+        self
+    }
+}
+
+impl Default for TokenizerOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
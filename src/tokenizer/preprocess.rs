@@ -0,0 +1,77 @@
+use std::{iter::Peekable, str::Chars};
+
+// https://html.spec.whatwg.org/#preprocessing-the-input-stream
+// Normalizes newlines: every U+000D CARRIAGE RETURN, alone or followed by a U+000A LINE FEED,
+// becomes a single U+000A LINE FEED.
+pub(crate) struct PreprocessedChars<I: Iterator<Item = char>> {
+    chars: Peekable<I>,
+    // Bytes of the source consumed so far.
+    offset: usize,
+}
+
+impl<'a> PreprocessedChars<Chars<'a>> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        Self::from_chars(source.chars())
+    }
+}
+
+impl<I: Iterator<Item = char>> PreprocessedChars<I> {
+    pub(crate) fn from_chars(chars: I) -> Self {
+        Self {
+            chars: chars.peekable(),
+            offset: 0,
+        }
+    }
+
+    pub(crate) fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for PreprocessedChars<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.offset += c.len_utf8();
+
+        if c == '\r' {
+            if self.chars.next_if_eq(&'\n').is_some() {
+                self.offset += 1;
+            }
+            return Some('\n');
+        }
+
+        Some(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preprocess(source: &str) -> String {
+        PreprocessedChars::new(source).collect()
+    }
+
+    #[test]
+    fn test_carriage_returns() {
+        assert_eq!(preprocess("a\rb"), "a\nb");
+        assert_eq!(preprocess("a\r\nb"), "a\nb");
+        assert_eq!(preprocess("a\r\r\nb"), "a\n\nb");
+        assert_eq!(preprocess("a\n\rb\r"), "a\n\nb\n");
+
+        let chars = PreprocessedChars::from_chars("a\r".chars().chain("\nb".chars()));
+        assert_eq!(chars.collect::<String>(), "a\nb");
+    }
+
+    #[test]
+    fn test_offset() {
+        let mut chars = PreprocessedChars::new("\r\né");
+
+        assert_eq!(chars.next(), Some('\n'));
+        assert_eq!(chars.offset(), 2);
+        assert_eq!(chars.next(), Some('é'));
+        assert_eq!(chars.offset(), 4);
+    }
+}
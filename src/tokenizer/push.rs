@@ -0,0 +1,257 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use super::{ParseError, State, Token, Tokenizer, TokenizerOptions};
+use crate::entity::MAX_NUMBER_OF_CHARACTERS_POSSIBLE;
+
+// Tokenizes input that arrives in chunks, e.g. from a socket or a file read piece by piece.
+//
+// A single tokenizer runs over all of the chunks and stops whenever the input pushed so far
+// could end in the middle of what its next step looks at, so a tag, comment or character
+// reference split across chunks comes out exactly as if the document had been passed to
+// `Tokenizer::new` in one piece. Every character is consumed once.
+pub struct PushTokenizer {
+    tokenizer: Tokenizer<PushedChars>,
+    // The other end of the tokenizer's input.
+    input: PushedChars,
+
+    // A carriage return at the end of the last chunk, held back because it may be the first
+    // half of a CRLF pair split across chunks.
+    carriage_return: bool,
+
+    // Whether any input has been pushed yet, i.e. whether a U+FEFF would be a byte order mark.
+    started: bool,
+}
+
+impl PushTokenizer {
+    pub fn new() -> Self {
+        Self::with_options(TokenizerOptions::new())
+    }
+
+    pub fn with_options(options: TokenizerOptions) -> Self {
+        let input = PushedChars::default();
+
+        Self {
+            tokenizer: Tokenizer::from_chars_with_options(input.clone(), options),
+            input,
+            carriage_return: false,
+            started: false,
+        }
+    }
+
+    pub fn push(&mut self, mut chunk: &str) {
+        if !self.started && !chunk.is_empty() {
+            self.started = true;
+            chunk = chunk.strip_prefix('\u{FEFF}').unwrap_or(chunk);
+        }
+        if chunk.is_empty() {
+            return;
+        }
+
+        if std::mem::take(&mut self.carriage_return) {
+            self.input.push('\r');
+        }
+        self.carriage_return = chunk.ends_with('\r');
+        chunk
+            .strip_suffix('\r')
+            .unwrap_or(chunk)
+            .chars()
+            .for_each(|c| self.input.push(c));
+
+        self.tokenize(false);
+    }
+
+    // Tokens completed by the chunks pushed so far.
+    pub fn drain_tokens(&mut self) -> impl Iterator<Item = Token> + '_ {
+        self.tokenizer.spans.clear();
+        self.tokenizer.tokens.drain(..)
+    }
+
+    // Signals the end of the input and returns every remaining token, ending with `Token::EOF`.
+    pub fn finish(&mut self) -> impl Iterator<Item = Token> + '_ {
+        if std::mem::take(&mut self.carriage_return) {
+            self.input.push('\r');
+        }
+
+        self.tokenize(true);
+        self.drain_tokens()
+    }
+
+    pub fn errors(&self) -> &[ParseError] {
+        self.tokenizer.errors()
+    }
+
+    fn tokenize(&mut self, at_eof: bool) {
+        while !self.tokenizer.reached_eof {
+            // Running out of input would look like the end of the document, so stop while the
+            // next step might get that far.
+            if !at_eof && self.input.available() < lookahead(self.tokenizer.current_state) {
+                break;
+            }
+
+            self.tokenizer.step();
+        }
+    }
+}
+
+impl Default for PushTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The most characters a single step in `state` can consume.
+fn lookahead(state: State) -> usize {
+    match state {
+        State::NamedCharacterReference => MAX_NUMBER_OF_CHARACTERS_POSSIBLE,
+        // "[CDATA[" and "DOCTYPE"
+        State::MarkupDeclarationOpen => 7,
+        // "PUBLIC" and "SYSTEM"
+        State::AfterDOCTYPEName => 6,
+        _ => 1,
+    }
+}
+
+// The input pushed but not yet consumed, shared between `PushTokenizer` and its tokenizer.
+#[derive(Clone, Default)]
+struct PushedChars(Rc<RefCell<PushedInput>>);
+
+#[derive(Default)]
+struct PushedInput {
+    chars: VecDeque<char>,
+    carriage_returns: usize,
+}
+
+impl PushedChars {
+    fn push(&self, c: char) {
+        let mut input = self.0.borrow_mut();
+        input.chars.push_back(c);
+        input.carriage_returns += usize::from(c == '\r');
+    }
+
+    // How many characters the tokenizer is sure to get before running out. A CRLF pair is two
+    // characters of input but only one for the tokenizer.
+    fn available(&self) -> usize {
+        let input = self.0.borrow();
+        input.chars.len() - input.carriage_returns
+    }
+}
+
+impl Iterator for PushedChars {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let mut input = self.0.borrow_mut();
+        let c = input.chars.pop_front()?;
+        input.carriage_returns -= usize::from(c == '\r');
+        Some(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_in_chunks(chunks: &[&str]) -> Vec<Token> {
+        let mut tokenizer = PushTokenizer::new();
+        let mut tokens = Vec::new();
+
+        for chunk in chunks {
+            tokenizer.push(chunk);
+            tokens.extend(tokenizer.drain_tokens());
+        }
+        tokens.extend(tokenizer.finish());
+
+        tokens
+    }
+
+    #[test]
+    fn test_chunks_match_whole_input() {
+        let chunks = [
+            "<!DOCTYPE ht",
+            "ml><p cla",
+            "ss=\"intro\">fish &am",
+            "p; chips<!-- a com",
+            "ment --></p>\r",
+            "\n<script>if (a </",
+            "b) {}</scr",
+            "ipt>done",
+        ];
+        let html = chunks.concat();
+
+        assert_eq!(
+            push_in_chunks(&chunks),
+            Tokenizer::new(&html).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_one_character_at_a_time() {
+        let html = "<!DOCTYPE html PUBLIC \"-//W3C//DTD HTML 4.01//EN\">\r\n\
+                    <p title='&notit; &amp=x'>&notin &noti; &#x41;\r\r\n<![CDATA[x]]>\
+                    <!-- a -- b --><textarea>&lt;/p></textarea><br/>";
+        let chunks = html
+            .char_indices()
+            .map(|(i, c)| &html[i..i + c.len_utf8()])
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            push_in_chunks(&chunks),
+            Tokenizer::new(html).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        let mut tokenizer =
+            PushTokenizer::with_options(TokenizerOptions::new().initial_state(State::RAWTEXT));
+
+        tokenizer.push("<b>&amp;</");
+        tokenizer.push("b>");
+
+        assert_eq!(
+            tokenizer.finish().collect::<Vec<_>>(),
+            vec![Token::Text("<b>&amp;</b>".to_string()), Token::EOF]
+        );
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let chunks = ["<p>a\r", "\n<!-", "--><b x=1 x", "=2>\n\n&#0", ";</b y>"];
+        let html = chunks.concat();
+
+        let mut pushed = PushTokenizer::new();
+        for chunk in chunks {
+            pushed.push(chunk);
+        }
+        pushed.finish().for_each(drop);
+        let mut whole = Tokenizer::new(&html);
+        whole.by_ref().for_each(drop);
+
+        assert_eq!(pushed.errors().len(), 4);
+        assert_eq!(pushed.errors(), whole.errors());
+    }
+
+    #[test]
+    fn test_byte_order_mark() {
+        assert_eq!(
+            push_in_chunks(&["", "\u{FEFF}", "a", "\u{FEFF}b"]),
+            vec![Token::Text("a\u{FEFF}b".to_string()), Token::EOF]
+        );
+    }
+
+    #[test]
+    fn test_tokens_are_available_before_finish() {
+        let mut tokenizer = PushTokenizer::new();
+
+        tokenizer.push("<p>hi</");
+        assert_eq!(tokenizer.drain_tokens().count(), 1);
+
+        tokenizer.push("p> there");
+        assert_eq!(tokenizer.drain_tokens().count(), 2);
+
+        assert_eq!(
+            tokenizer.finish().collect::<Vec<_>>(),
+            vec![Token::Text(" there".to_string()), Token::EOF]
+        );
+    }
+}
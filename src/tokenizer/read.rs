@@ -0,0 +1,335 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read},
+};
+
+use super::{PushTokenizer, Token};
+
+pub(crate) const CHUNK_SIZE: usize = 8 * 1024;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+// Turns the bytes of a document into text as they arrive. The input is UTF-8 unless it starts
+// with a UTF-16 byte order mark. Undecodable bytes are replaced with U+FFFD.
+#[derive(Debug, Default)]
+pub(crate) struct Decoder {
+    // The start of a character that was split across two reads.
+    incomplete: Vec<u8>,
+    // Known once the start of the input has been checked for a byte order mark.
+    encoding: Option<Encoding>,
+}
+
+impl Decoder {
+    // Decodes as much of the input so far as possible, leaving a trailing partial character
+    // for the next call.
+    pub(crate) fn decode(&mut self, bytes: &[u8]) -> String {
+        self.incomplete.extend_from_slice(bytes);
+        self.decode_incomplete(false)
+    }
+
+    // Decodes everything left at the end of the input.
+    pub(crate) fn finish(&mut self) -> String {
+        let mut text = self.decode_incomplete(true);
+
+        // Whatever is left is a truncated character.
+        if !self.incomplete.is_empty() {
+            match self.encoding {
+                Some(Encoding::Utf8) | None => {
+                    text.push_str(&String::from_utf8_lossy(&self.incomplete));
+                }
+                Some(Encoding::Utf16Le | Encoding::Utf16Be) => {
+                    text.push(char::REPLACEMENT_CHARACTER);
+                }
+            }
+            self.incomplete.clear();
+        }
+
+        text
+    }
+
+    fn decode_incomplete(&mut self, at_eof: bool) -> String {
+        let encoding = match self.encoding {
+            Some(encoding) => encoding,
+            None => match self.sniff_byte_order_mark(at_eof) {
+                Some(encoding) => encoding,
+                None => return String::new(),
+            },
+        };
+
+        match encoding {
+            Encoding::Utf8 => self.decode_utf8(),
+            Encoding::Utf16Le => self.decode_utf16(u16::from_le_bytes),
+            Encoding::Utf16Be => self.decode_utf16(u16::from_be_bytes),
+        }
+    }
+
+    // https://encoding.spec.whatwg.org/#bom-sniff
+    // Returns `None` while too few bytes have been read to tell.
+    fn sniff_byte_order_mark(&mut self, at_eof: bool) -> Option<Encoding> {
+        const BOMS: [(&[u8], Encoding); 3] = [
+            (&[0xEF, 0xBB, 0xBF], Encoding::Utf8),
+            (&[0xFF, 0xFE], Encoding::Utf16Le),
+            (&[0xFE, 0xFF], Encoding::Utf16Be),
+        ];
+
+        let mut encoding = Encoding::Utf8;
+
+        for (bom, bom_encoding) in BOMS {
+            // The byte order mark itself is left in and decodes to U+FEFF, which
+            // `PushTokenizer` removes, as `Tokenizer::new` would.
+            if self.incomplete.starts_with(bom) {
+                encoding = bom_encoding;
+                break;
+            }
+
+            if !at_eof && bom.starts_with(&self.incomplete) {
+                return None;
+            }
+        }
+
+        self.encoding = Some(encoding);
+        Some(encoding)
+    }
+
+    fn decode_utf16(&mut self, from_bytes: fn([u8; 2]) -> u16) -> String {
+        let mut units: Vec<u16> = self
+            .incomplete
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect();
+
+        // A leading surrogate at the end waits for its pair.
+        if units
+            .last()
+            .is_some_and(|unit| (0xD800..0xDC00).contains(unit))
+        {
+            units.pop();
+        }
+
+        self.incomplete.drain(..units.len() * 2);
+
+        char::decode_utf16(units)
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
+
+    fn decode_utf8(&mut self) -> String {
+        let mut text = String::new();
+        let mut rest = self.incomplete.as_slice();
+
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    text.push_str(valid);
+                    rest = &[];
+                    break;
+                }
+                Err(error) => {
+                    let (valid, invalid) = rest.split_at(error.valid_up_to());
+                    text.push_str(std::str::from_utf8(valid).unwrap_or_default());
+
+                    match error.error_len() {
+                        Some(len) => {
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            rest = &invalid[len..];
+                        }
+                        None => {
+                            rest = invalid;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.incomplete = rest.to_vec();
+        text
+    }
+}
+
+// Tokenizes a `std::io::Read` source, reading another chunk only once every token from the
+// previous ones has been handed out. The bytes are decoded by `Decoder`.
+//
+// A read error ends the input as if the reader had reached EOF; it can be inspected with
+// `error` afterwards.
+pub struct ReadTokenizer<R> {
+    reader: R,
+    tokenizer: PushTokenizer,
+    tokens: VecDeque<Token>,
+
+    chunk: Vec<u8>,
+    decoder: Decoder,
+
+    finished: bool,
+    error: Option<io::Error>,
+}
+
+impl<R: Read> ReadTokenizer<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            tokenizer: PushTokenizer::new(),
+            tokens: VecDeque::new(),
+            chunk: vec![0; CHUNK_SIZE],
+            decoder: Decoder::default(),
+            finished: false,
+            error: None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    fn read_chunk(&mut self) {
+        match self.reader.read(&mut self.chunk) {
+            Ok(0) => self.finish(),
+            Ok(len) => {
+                let text = self.decoder.decode(&self.chunk[..len]);
+                self.tokenizer.push(&text);
+                self.tokens.extend(self.tokenizer.drain_tokens());
+            }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => {
+                self.error = Some(error);
+                self.finish();
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        let text = self.decoder.finish();
+        self.tokenizer.push(&text);
+
+        self.tokens.extend(self.tokenizer.finish());
+        self.finished = true;
+    }
+}
+
+impl<R: Read> Iterator for ReadTokenizer<R> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        while self.tokens.is_empty() && !self.finished {
+            self.read_chunk();
+        }
+
+        self.tokens.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tokenizer;
+
+    // Hands out a few bytes per read so that characters and tags straddle reads.
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        reads: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.bytes.len().min(buf.len()).min(3);
+            buf[..len].copy_from_slice(&self.bytes[..len]);
+            self.bytes = &self.bytes[len..];
+            self.reads += 1;
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_matches_tokenizer() {
+        let html = r#"<p title="café">naïve &amp; ☕ <!-- über --></p>"#;
+        let reader = Trickle {
+            bytes: html.as_bytes(),
+            reads: 0,
+        };
+
+        assert_eq!(
+            ReadTokenizer::new(reader).collect::<Vec<_>>(),
+            Tokenizer::new(html).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_reads_lazily() {
+        let html = "<p>one</p><p>two</p><p>three</p>";
+        let mut tokenizer = ReadTokenizer::new(Trickle {
+            bytes: html.as_bytes(),
+            reads: 0,
+        });
+
+        assert_eq!(
+            tokenizer.next(),
+            Some(Token::Tag(crate::Tag::new_start_tag_with_name(
+                "p".to_string()
+            )))
+        );
+        assert_eq!(tokenizer.reader.reads, 1);
+    }
+
+    fn utf16(html: &str, to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+        html.encode_utf16().flat_map(to_bytes).collect()
+    }
+
+    #[test]
+    fn test_byte_order_marks() {
+        let html = "<p title=\"ça\">😀 €</p>";
+        let expected = Tokenizer::new(html).collect::<Vec<_>>();
+
+        for bytes in [
+            [b"\xEF\xBB\xBF".as_slice(), html.as_bytes()].concat(),
+            [b"\xFF\xFE".as_slice(), &utf16(html, u16::to_le_bytes)].concat(),
+            [b"\xFE\xFF".as_slice(), &utf16(html, u16::to_be_bytes)].concat(),
+        ] {
+            let reader = Trickle {
+                bytes: &bytes,
+                reads: 0,
+            };
+
+            assert_eq!(ReadTokenizer::new(reader).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_double_byte_order_mark() {
+        // Only the first one is a byte order mark; the second is part of the text.
+        let bytes = "\u{FEFF}\u{FEFF}a".as_bytes();
+
+        assert_eq!(
+            ReadTokenizer::new(bytes).collect::<Vec<_>>(),
+            vec![Token::Text("\u{FEFF}a".to_string()), Token::EOF]
+        );
+        assert_eq!(
+            ReadTokenizer::new(bytes).collect::<Vec<_>>(),
+            Tokenizer::new("\u{FEFF}\u{FEFF}a").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_short_input() {
+        let reader: &[u8] = b"\xEFa";
+
+        assert_eq!(
+            ReadTokenizer::new(reader).collect::<Vec<_>>(),
+            vec![Token::Text("\u{FFFD}a".to_string()), Token::EOF]
+        );
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let reader: &[u8] = b"a\xFFb\xE2\x98";
+
+        assert_eq!(
+            ReadTokenizer::new(reader).collect::<Vec<_>>(),
+            vec![Token::Text("a\u{FFFD}b\u{FFFD}".to_string()), Token::EOF]
+        );
+    }
+}
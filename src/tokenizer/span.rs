@@ -0,0 +1,42 @@
+use super::token::Token;
+
+// Position of a character in the source. Lines and columns both start at 1; every
+// character other than U+000A LINE FEED moves one column to the right. `offset` is the byte
+// offset into the original input, for editors and source maps.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Span {
+    pub line: u32,
+    pub column: u32,
+    pub offset: u32,
+}
+
+impl Span {
+    pub fn new(line: u32, column: u32, offset: u32) -> Self {
+        Span {
+            line,
+            column,
+            offset,
+        }
+    }
+
+    // `len` is the number of bytes `c` was read from; a CRLF pair is a single U+000A.
+    pub(super) fn advance(&mut self, c: char, len: u32) {
+        self.offset += len;
+
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+}
+
+// A token together with the position of its first character in the source: the `<` of
+// a tag, comment or DOCTYPE, the `&` of a character reference, and the character itself
+// for plain text.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub span: Span,
+}
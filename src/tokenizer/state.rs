@@ -1,4 +1,5 @@
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum State {
     // The Data State
     // https://html.spec.whatwg.org/#data-state
@@ -0,0 +1,26 @@
+// Counts of what a tokenizer has emitted so far. Character data is counted in characters,
+// whether it was emitted as text or inside a CDATA section; parse errors are counted even
+// when they are not collected.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct TokenizerStats {
+    pub start_tag_count: usize,
+    pub end_tag_count: usize,
+    pub char_count: usize,
+    pub comment_count: usize,
+    pub doctype_count: usize,
+    pub processing_instruction_count: usize,
+    pub parse_error_count: usize,
+}
+
+impl TokenizerStats {
+    // The sum of every count, parse errors included.
+    pub fn total_tokens(&self) -> usize {
+        self.start_tag_count
+            + self.end_tag_count
+            + self.char_count
+            + self.comment_count
+            + self.doctype_count
+            + self.processing_instruction_count
+            + self.parse_error_count
+    }
+}
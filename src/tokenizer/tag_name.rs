@@ -0,0 +1,242 @@
+use std::{
+    borrow::Borrow,
+    cell::RefCell,
+    collections::HashSet,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
+
+// The most names a thread keeps shared copies of. Names past that stay owned, so a stream of
+// made-up tag names can't grow the pool without limit.
+const MAX_INTERNED_NAMES: usize = 1024;
+
+thread_local! {
+    static INTERNED: RefCell<HashSet<Arc<str>>> = RefCell::new(HashSet::new());
+}
+
+// The name of a tag. Tokenizers created with `TokenizerOptions::intern_tag_names` share one
+// allocation between every tag with the same name on a thread, for up to
+// `MAX_INTERNED_NAMES` names; otherwise each tag owns its name. Either way it compares,
+// hashes and derefs like the `str` it holds.
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "String", into = "String")
+)]
+pub enum TagName {
+    Owned(String),
+    Interned(Arc<str>),
+}
+
+impl TagName {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TagName::Owned(name) => name,
+            TagName::Interned(name) => name,
+        }
+    }
+
+    pub fn is_interned(&self) -> bool {
+        matches!(self, TagName::Interned(_))
+    }
+
+    pub fn push(&mut self, c: char) {
+        match self {
+            TagName::Owned(name) => name.push(c),
+            TagName::Interned(name) => {
+                let mut name = name.to_string();
+                name.push(c);
+                *self = TagName::Owned(name);
+            }
+        }
+    }
+
+    // Replaces the name with the thread's shared copy of it, adding one if there is none yet.
+    // The name stays owned once the thread has `MAX_INTERNED_NAMES` names.
+    pub fn intern(&mut self) {
+        if let TagName::Owned(name) = self {
+            if let Some(shared) = Self::shared(name) {
+                *self = TagName::Interned(shared);
+            }
+        }
+    }
+
+    // The thread's shared copy of `name`. Only a name not seen before is allocated.
+    pub(crate) fn shared(name: &str) -> Option<Arc<str>> {
+        INTERNED.with(|interned| {
+            let mut interned = interned.borrow_mut();
+
+            if let Some(shared) = interned.get(name) {
+                return Some(Arc::clone(shared));
+            }
+            if interned.len() >= MAX_INTERNED_NAMES {
+                return None;
+            }
+
+            let shared: Arc<str> = Arc::from(name);
+            interned.insert(Arc::clone(&shared));
+            Some(shared)
+        })
+    }
+}
+
+impl Default for TagName {
+    fn default() -> Self {
+        TagName::Owned(String::new())
+    }
+}
+
+impl Deref for TagName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for TagName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for TagName {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for TagName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for TagName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for TagName {
+    fn from(name: String) -> Self {
+        TagName::Owned(name)
+    }
+}
+
+impl From<&str> for TagName {
+    fn from(name: &str) -> Self {
+        TagName::Owned(name.to_string())
+    }
+}
+
+impl From<TagName> for String {
+    fn from(name: TagName) -> Self {
+        match name {
+            TagName::Owned(name) => name,
+            TagName::Interned(name) => name.to_string(),
+        }
+    }
+}
+
+impl PartialEq for TagName {
+    fn eq(&self, other: &TagName) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for TagName {}
+
+impl Hash for TagName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialEq<str> for TagName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for TagName {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for TagName {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<TagName> for str {
+    fn eq(&self, other: &TagName) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<TagName> for &str {
+    fn eq(&self, other: &TagName) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<TagName> for String {
+    fn eq(&self, other: &TagName) -> bool {
+        self == other.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut first = TagName::from("div");
+        let mut second = TagName::from("div".to_string());
+        first.intern();
+        second.intern();
+
+        match (&first, &second) {
+            (TagName::Interned(first), TagName::Interned(second)) => {
+                assert!(Arc::ptr_eq(first, second))
+            }
+            _ => panic!("not interned: {:?} {:?}", first, second),
+        }
+        assert_eq!(first, TagName::from("div"));
+        assert_eq!(first, "div");
+        assert_eq!("div".to_string(), first);
+
+        first.push('x');
+        assert!(!first.is_interned());
+        assert_eq!(first, "divx");
+        assert_eq!(second, "div");
+    }
+
+    #[test]
+    fn test_intern_limit() {
+        // Tests run on their own threads, so the pool starts out empty.
+        for i in 0..MAX_INTERNED_NAMES {
+            TagName::from(format!("x-{}", i)).intern();
+        }
+
+        let mut known = TagName::from("x-0");
+        let mut new = TagName::from("x-new");
+        known.intern();
+        new.intern();
+
+        assert!(known.is_interned());
+        assert!(!new.is_interned());
+        assert_eq!(new, "x-new");
+        assert_eq!(
+            INTERNED.with(|interned| interned.borrow().len()),
+            MAX_INTERNED_NAMES
+        );
+    }
+}
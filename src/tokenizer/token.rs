@@ -1,4 +1,13 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use super::TagName;
+use crate::elements::{RAW_TEXT_ELEMENTS, RCDATA_ELEMENTS, VOID_ELEMENTS};
+use crate::entity::decode_character_references;
+use crate::extractors::extract_charset_from_content;
+use crate::selector::SimpleSelector;
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attribute {
     pub name: String,
     pub value: String,
@@ -15,15 +24,189 @@ impl Attribute {
             namespace: String::new(),
         }
     }
+
+    // The name as written in the source, e.g. `xlink:href` for an attribute with the prefix
+    // `xlink` and the name `href`.
+    pub fn qualified_name(&self) -> Cow<'_, str> {
+        if self.prefix.is_empty() {
+            Cow::Borrowed(&self.name)
+        } else {
+            Cow::Owned(format!("{}:{}", self.prefix, self.name))
+        }
+    }
+
+    // Decodes the character references in a raw value, e.g. one set by hand or taken from
+    // source text, as the tokenizer would decode them in an attribute value. Attributes the
+    // tokenizer produced are already decoded, so their `value` is the one to use: `&amp;amp;`
+    // is tokenized to `&amp;`, which this would turn into `&`. Borrows the value when it has
+    // no references.
+    pub fn normalized_value(&self) -> Cow<'_, str> {
+        decode_character_references(&self.value, true)
+    }
+
+    // The namespace bound to the attribute's prefix: the fixed `xml` and `xmlns` namespaces, or
+    // else an `xmlns:prefix` declaration on the tag itself. Declarations on ancestors aren't
+    // seen; use `namespace_uri_with` to supply them.
+    // https://www.w3.org/TR/xml-names/#ns-decl
+    pub fn namespace_uri<'a>(&'a self, tag: &'a Tag) -> Option<&'a str> {
+        self.resolve_namespace(tag, None)
+    }
+
+    // Like `namespace_uri`, falling back to `namespaces`, a map from prefix to namespace, for
+    // prefixes the tag doesn't declare.
+    pub fn namespace_uri_with<'a>(
+        &'a self,
+        tag: &'a Tag,
+        namespaces: &'a HashMap<String, String>,
+    ) -> Option<&'a str> {
+        self.resolve_namespace(tag, Some(namespaces))
+    }
+
+    fn resolve_namespace<'a>(
+        &'a self,
+        tag: &'a Tag,
+        namespaces: Option<&'a HashMap<String, String>>,
+    ) -> Option<&'a str> {
+        // Attributes outside foreign content keep their prefix in the name.
+        let prefix = if self.prefix.is_empty() {
+            match self.name.split_once(':') {
+                Some((prefix, _)) => prefix,
+                None if self.name == "xmlns" => return Some(XMLNS_NAMESPACE),
+                None => return None,
+            }
+        } else {
+            self.prefix.as_str()
+        };
+
+        match prefix {
+            "xml" => return Some(XML_NAMESPACE),
+            "xmlns" => return Some(XMLNS_NAMESPACE),
+            _ => {}
+        }
+
+        let declaration = format!("xmlns:{}", prefix);
+        tag.attributes
+            .iter()
+            .find(|attr| attr.qualified_name() == declaration)
+            .map(|attr| attr.value.as_str())
+            .or_else(|| namespaces?.get(prefix).map(String::as_str))
+            // The tokenizer sets the namespace of the prefixes foreign content predefines.
+            .or_else(|| Some(self.namespace.as_str()).filter(|namespace| !namespace.is_empty()))
+    }
+
+    pub fn is_boolean(&self) -> bool {
+        self.prefix.is_empty() && is_boolean(&self.name)
+    }
+
+    // Compares `name` with the qualified name, ASCII case-insensitively.
+    fn is_named(&self, name: &str) -> bool {
+        if self.prefix.is_empty() {
+            return self.name.eq_ignore_ascii_case(name);
+        }
+
+        name.split_once(':').is_some_and(|(prefix, name)| {
+            self.prefix.eq_ignore_ascii_case(prefix) && self.name.eq_ignore_ascii_case(name)
+        })
+    }
+
+    // Moves a `prefix:` at the start of the name into `prefix`, and sets `namespace` for the
+    // prefixes the spec knows about.
+    // https://html.spec.whatwg.org/#adjust-foreign-attributes
+    pub(crate) fn split_prefix(&mut self) {
+        let Some((prefix, name)) = self.name.split_once(':') else {
+            return;
+        };
+        if prefix.is_empty() || name.is_empty() {
+            return;
+        }
+
+        self.namespace = match prefix {
+            "xlink" => XLINK_NAMESPACE,
+            "xml" => XML_NAMESPACE,
+            "xmlns" => XMLNS_NAMESPACE,
+            _ => "",
+        }
+        .to_string();
+        self.prefix = prefix.to_string();
+        self.name = name.to_string();
+    }
+}
+
+// Attributes whose presence alone means true. The value, if any, has to be empty or the
+// attribute's own name. Matched ASCII case-insensitively.
+// https://html.spec.whatwg.org/#boolean-attributes
+pub fn is_boolean(name: &str) -> bool {
+    BOOLEAN_ATTRIBUTES
+        .iter()
+        .any(|attribute| attribute.eq_ignore_ascii_case(name))
+}
+
+// https://html.spec.whatwg.org/#attributes-3, along with `scoped` and `seamless`, which have
+// since been dropped from the spec.
+const BOOLEAN_ATTRIBUTES: [&str; 27] = [
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "scoped",
+    "seamless",
+    "selected",
+];
+
+// https://infra.spec.whatwg.org/#namespaces
+const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+
+impl Default for Attribute {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
+// `name="value"`, with `&` and `"` in the value escaped.
 impl std::fmt::Display for Attribute {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{} : {}", self.name, self.value)
+        write!(f, "{}=\"", self.qualified_name())?;
+
+        let mut rest = self.value.as_str();
+        while let Some(index) = rest.find(['&', '"']) {
+            f.write_str(&rest[..index])?;
+            f.write_str(if rest[index..].starts_with('&') {
+                "&amp;"
+            } else {
+                "&quot;"
+            })?;
+            rest = &rest[index + 1..];
+        }
+        f.write_str(rest)?;
+
+        f.write_str("\"")
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Doctype {
     pub name: String,
     pub public_id: Option<String>,
@@ -50,6 +233,47 @@ impl Doctype {
         }
     }
 
+    // `<!DOCTYPE html>`, without public or system identifiers.
+    pub fn is_html5(&self) -> bool {
+        self.name.eq_ignore_ascii_case("html")
+            && self.public_id.is_none()
+            && self.system_id.is_none()
+    }
+
+    // Whether a document starting with this DOCTYPE is rendered in quirks mode.
+    // https://html.spec.whatwg.org/#the-initial-insertion-mode
+    pub fn is_quirks_mode(&self) -> bool {
+        if self.force_quirks || !self.name.eq_ignore_ascii_case("html") {
+            return true;
+        }
+
+        if self
+            .system_id
+            .as_deref()
+            .is_some_and(|system_id| system_id.eq_ignore_ascii_case(QUIRKS_SYSTEM_ID))
+        {
+            return true;
+        }
+
+        let Some(public_id) = self.public_id.as_deref() else {
+            return false;
+        };
+        let starts_with = |prefix: &&str| {
+            public_id
+                .get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        };
+
+        QUIRKS_PUBLIC_IDS
+            .iter()
+            .any(|id| public_id.eq_ignore_ascii_case(id))
+            || QUIRKS_PUBLIC_ID_PREFIXES.iter().any(starts_with)
+            || (self.system_id.is_none()
+                && QUIRKS_PUBLIC_ID_PREFIXES_WITHOUT_SYSTEM_ID
+                    .iter()
+                    .any(starts_with))
+    }
+
     pub fn set_quirks_flag_to_on(&mut self) {
         self.force_quirks = true;
     }
@@ -79,15 +303,127 @@ impl Doctype {
     }
 }
 
+impl Default for Doctype {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// https://html.spec.whatwg.org/#the-initial-insertion-mode
+const QUIRKS_PUBLIC_IDS: [&str; 3] = [
+    "-//W3O//DTD W3 HTML Strict 3.0//EN//",
+    "-/W3C/DTD HTML 4.0 Transitional/EN",
+    "HTML",
+];
+
+const QUIRKS_SYSTEM_ID: &str = "http://www.ibm.com/data/dtd/v11/ibmxhtml1-transitional.dtd";
+
+const QUIRKS_PUBLIC_ID_PREFIXES: [&str; 55] = [
+    "+//Silmaril//dtd html Pro v0r11 19970101//",
+    "-//AS//DTD HTML 3.0 asWedit + extensions//",
+    "-//AdvaSoft Ltd//DTD HTML 3.0 asWedit + extensions//",
+    "-//IETF//DTD HTML 2.0 Level 1//",
+    "-//IETF//DTD HTML 2.0 Level 2//",
+    "-//IETF//DTD HTML 2.0 Strict Level 1//",
+    "-//IETF//DTD HTML 2.0 Strict Level 2//",
+    "-//IETF//DTD HTML 2.0 Strict//",
+    "-//IETF//DTD HTML 2.0//",
+    "-//IETF//DTD HTML 2.1E//",
+    "-//IETF//DTD HTML 3.0//",
+    "-//IETF//DTD HTML 3.2 Final//",
+    "-//IETF//DTD HTML 3.2//",
+    "-//IETF//DTD HTML 3//",
+    "-//IETF//DTD HTML Level 0//",
+    "-//IETF//DTD HTML Level 1//",
+    "-//IETF//DTD HTML Level 2//",
+    "-//IETF//DTD HTML Level 3//",
+    "-//IETF//DTD HTML Strict Level 0//",
+    "-//IETF//DTD HTML Strict Level 1//",
+    "-//IETF//DTD HTML Strict Level 2//",
+    "-//IETF//DTD HTML Strict Level 3//",
+    "-//IETF//DTD HTML Strict//",
+    "-//IETF//DTD HTML//",
+    "-//Metrius//DTD Metrius Presentational//",
+    "-//Microsoft//DTD Internet Explorer 2.0 HTML Strict//",
+    "-//Microsoft//DTD Internet Explorer 2.0 HTML//",
+    "-//Microsoft//DTD Internet Explorer 2.0 Tables//",
+    "-//Microsoft//DTD Internet Explorer 3.0 HTML Strict//",
+    "-//Microsoft//DTD Internet Explorer 3.0 HTML//",
+    "-//Microsoft//DTD Internet Explorer 3.0 Tables//",
+    "-//Netscape Comm. Corp.//DTD HTML//",
+    "-//Netscape Comm. Corp.//DTD Strict HTML//",
+    "-//O'Reilly and Associates//DTD HTML 2.0//",
+    "-//O'Reilly and Associates//DTD HTML Extended 1.0//",
+    "-//O'Reilly and Associates//DTD HTML Extended Relaxed 1.0//",
+    "-//SQ//DTD HTML 2.0 HoTMetaL + extensions//",
+    "-//SoftQuad Software//DTD HoTMetaL PRO 6.0::19990601::extensions to HTML 4.0//",
+    "-//SoftQuad//DTD HoTMetaL PRO 4.0::19971010::extensions to HTML 4.0//",
+    "-//Spyglass//DTD HTML 2.0 Extended//",
+    "-//Sun Microsystems Corp.//DTD HotJava HTML//",
+    "-//Sun Microsystems Corp.//DTD HotJava Strict HTML//",
+    "-//W3C//DTD HTML 3 1995-03-24//",
+    "-//W3C//DTD HTML 3.2 Draft//",
+    "-//W3C//DTD HTML 3.2 Final//",
+    "-//W3C//DTD HTML 3.2//",
+    "-//W3C//DTD HTML 3.2S Draft//",
+    "-//W3C//DTD HTML 4.0 Frameset//",
+    "-//W3C//DTD HTML 4.0 Transitional//",
+    "-//W3C//DTD HTML Experimental 19960712//",
+    "-//W3C//DTD HTML Experimental 970421//",
+    "-//W3C//DTD W3 HTML//",
+    "-//W3O//DTD W3 HTML 3.0//",
+    "-//WebTechs//DTD Mozilla HTML 2.0//",
+    "-//WebTechs//DTD Mozilla HTML//",
+];
+
+// These only trigger quirks mode when the system identifier is missing.
+const QUIRKS_PUBLIC_ID_PREFIXES_WITHOUT_SYSTEM_ID: [&str; 2] = [
+    "-//W3C//DTD HTML 4.01 Frameset//",
+    "-//W3C//DTD HTML 4.01 Transitional//",
+];
+
 impl std::fmt::Display for Doctype {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "<!DOCTYPE html>")
+        write!(f, "<!DOCTYPE")?;
+
+        if !self.name.is_empty() {
+            write!(f, " {}", self.name)?;
+        }
+
+        if let Some(public_id) = &self.public_id {
+            let quote = identifier_quote(public_id);
+            write!(f, " PUBLIC {0}{1}{0}", quote, public_id)?;
+        }
+        if let Some(system_id) = &self.system_id {
+            if self.public_id.is_none() {
+                write!(f, " SYSTEM")?;
+            }
+            let quote = identifier_quote(system_id);
+            write!(f, " {0}{1}{0}", quote, system_id)?;
+        }
+
+        write!(f, ">")
+    }
+}
+
+// An identifier that was single-quoted in the source can hold `"`, and can't hold `'`.
+pub(crate) fn identifier_quote(identifier: &str) -> char {
+    if identifier.contains('"') {
+        '\''
+    } else {
+        '"'
     }
 }
 
+// https://html.spec.whatwg.org/#formatting
+pub(crate) const FORMATTING_ELEMENTS: [&str; 14] = [
+    "a", "b", "big", "code", "em", "font", "i", "nobr", "s", "small", "strike", "strong", "tt", "u",
+];
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tag {
-    pub tag_name: String,
+    pub tag_name: TagName,
     pub self_closing: bool,
     pub attributes: Vec<Attribute>,
     pub is_end_tag: bool,
@@ -97,7 +433,7 @@ pub struct Tag {
 impl Tag {
     pub fn new_start_tag() -> Self {
         Tag {
-            tag_name: String::new(),
+            tag_name: TagName::default(),
             self_closing: false,
             attributes: Vec::new(),
             is_end_tag: false,
@@ -107,7 +443,7 @@ impl Tag {
 
     pub fn new_end_tag() -> Self {
         Tag {
-            tag_name: String::new(),
+            tag_name: TagName::default(),
             self_closing: false,
             attributes: Vec::new(),
             is_end_tag: true,
@@ -117,7 +453,7 @@ impl Tag {
 
     pub fn new_end_tag_with_name(name: String) -> Self {
         Tag {
-            tag_name: name,
+            tag_name: name.into(),
             self_closing: false,
             attributes: Vec::new(),
             is_end_tag: true,
@@ -127,7 +463,7 @@ impl Tag {
 
     pub fn new_start_tag_with_name(name: String) -> Self {
         Tag {
-            tag_name: name,
+            tag_name: name.into(),
             self_closing: false,
             attributes: Vec::new(),
             is_end_tag: false,
@@ -158,34 +494,369 @@ impl Tag {
     pub fn set_self_closing(&mut self) {
         self.self_closing = true;
     }
+
+    // Keeps the name interned if it was.
+    pub fn rename(&mut self, new_name: &str) {
+        let interned = self.tag_name.is_interned();
+
+        self.tag_name = TagName::from(new_name);
+        if interned {
+            self.tag_name.intern();
+        }
+    }
+
+    // The element kind checks only hold for start tags; they are always false for end tags.
+    pub fn is_void_element(&self) -> bool {
+        self.is_start_tag_in(&VOID_ELEMENTS)
+    }
+
+    pub fn is_raw_text_element(&self) -> bool {
+        self.is_start_tag_in(&RAW_TEXT_ELEMENTS)
+    }
+
+    pub fn is_rcdata_element(&self) -> bool {
+        self.is_start_tag_in(&RCDATA_ELEMENTS)
+    }
+
+    pub fn is_formatting_element(&self) -> bool {
+        !self.is_end_tag
+            && FORMATTING_ELEMENTS
+                .iter()
+                .any(|name| self.tag_name.eq_ignore_ascii_case(name))
+    }
+
+    // The sets hold lowercase names, so the name is only lowercased when it has to be.
+    fn is_start_tag_in(&self, names: &phf::Set<&'static str>) -> bool {
+        if self.is_end_tag {
+            return false;
+        }
+
+        if self.tag_name.bytes().any(|b| b.is_ascii_uppercase()) {
+            names.contains(self.tag_name.to_ascii_lowercase().as_str())
+        } else {
+            names.contains(self.tag_name.as_str())
+        }
+    }
+
+    // Attribute names are matched ASCII case-insensitively. The tokenizer drops repeated
+    // attributes, so for tokenized tags there is at most one match.
+    //
+    // This is a linear scan, which allocates nothing. Three lookups this way are about four
+    // times faster than building `attributes_as_map` for them, from 1 up to 20 attributes;
+    // see `benches/attributes.rs`.
+    pub fn find_attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|attr| attr.is_named(name))
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.find_attribute(name).map(|attr| attr.value.as_str())
+    }
+
+    // Every attribute's value by name. Building the map costs a pass over the attributes, a
+    // hash per attribute and an allocation, so it only pays off when the map is kept for many
+    // lookups; otherwise use `find_attribute`. Unlike `find_attribute`, keys are the
+    // names as written, matched case-sensitively and without their namespace prefix. If a
+    // name repeats, the first value is kept, as `get_attribute` would return.
+    pub fn attributes_as_map(&self) -> HashMap<&str, &str> {
+        let mut map = HashMap::with_capacity(self.attributes.len());
+
+        for attr in &self.attributes {
+            map.entry(attr.name.as_str()).or_insert(attr.value.as_str());
+        }
+
+        map
+    }
+
+    pub fn boolean_attributes(&self) -> impl Iterator<Item = &Attribute> {
+        self.attributes.iter().filter(|attr| attr.is_boolean())
+    }
+
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.get_attribute(name).is_some()
+    }
+
+    pub fn get_attribute_mut(&mut self, name: &str) -> Option<&mut Attribute> {
+        self.attributes.iter_mut().find(|attr| attr.is_named(name))
+    }
+
+    // Removes the first attribute with the name, returning whether there was one.
+    pub fn remove_attribute(&mut self, name: &str) -> bool {
+        match self.attributes.iter().position(|attr| attr.is_named(name)) {
+            Some(index) => {
+                self.attributes.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear_attributes(&mut self) {
+        self.attributes.clear();
+    }
+
+    // Orders the attributes by name, so tags that differ only in attribute order become equal.
+    // Attributes with the same name keep their order.
+    pub fn sort_attributes_alphabetically(&mut self) {
+        self.attributes.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    pub fn sorted_attributes(&self) -> Vec<&Attribute> {
+        let mut attributes = self.attributes.iter().collect::<Vec<_>>();
+        attributes.sort_by(|a, b| a.name.cmp(&b.name));
+        attributes
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.get_attribute("id")
+    }
+
+    pub fn class(&self) -> Option<&str> {
+        self.get_attribute("class")
+    }
+
+    pub fn href(&self) -> Option<&str> {
+        self.get_attribute("href")
+    }
+
+    pub fn src(&self) -> Option<&str> {
+        self.get_attribute("src")
+    }
+
+    pub fn alt(&self) -> Option<&str> {
+        self.get_attribute("alt")
+    }
+
+    // The `type` attribute.
+    pub fn type_attr(&self) -> Option<&str> {
+        self.get_attribute("type")
+    }
+
+    // The `name` attribute, not the tag name.
+    pub fn name(&self) -> Option<&str> {
+        self.get_attribute("name")
+    }
+
+    // The `value` attribute.
+    pub fn value_attr(&self) -> Option<&str> {
+        self.get_attribute("value")
+    }
+
+    // Replaces the value of an existing attribute, or adds the attribute at the end.
+    pub fn set_attribute(&mut self, name: &str, value: &str) {
+        if let Some(attr) = self.get_attribute_mut(name) {
+            attr.value = value.to_string();
+        } else {
+            self.attributes.push(Attribute {
+                name: name.to_ascii_lowercase(),
+                value: value.to_string(),
+                ..Attribute::new()
+            });
+        }
+    }
+
+    // The class names in the `class` attribute, split on ASCII whitespace.
+    // https://dom.spec.whatwg.org/#concept-ordered-set-parser
+    pub fn classes(&self) -> impl Iterator<Item = &str> {
+        self.get_attribute("class")
+            .unwrap_or_default()
+            .split_ascii_whitespace()
+    }
+
+    pub fn has_class(&self, name: &str) -> bool {
+        self.classes().any(|class| class == name)
+    }
+
+    // Whether the tag matches a selector such as `input[type="checkbox"].enabled`. Selectors
+    // `SimpleSelector` can't parse match nothing.
+    pub fn matches_selector(&self, selector: &str) -> bool {
+        SimpleSelector::parse(selector).is_some_and(|selector| selector.matches(self))
+    }
+
+    // The encoding a `<meta>` start tag declares, from `charset="..."` or from the `content` of
+    // `http-equiv="Content-Type"`, e.g. `text/html; charset=utf-8`.
+    // https://html.spec.whatwg.org/#character-encoding-declaration
+    pub fn effective_charset(&self) -> Option<&str> {
+        if self.is_end_tag || !self.tag_name.eq_ignore_ascii_case("meta") {
+            return None;
+        }
+
+        if let Some(charset) = self.get_attribute("charset") {
+            return Some(charset.trim());
+        }
+
+        let is_content_type = self
+            .get_attribute("http-equiv")
+            .is_some_and(|value| value.eq_ignore_ascii_case("content-type"));
+        if is_content_type {
+            self.get_attribute("content")
+                .and_then(extract_charset_from_content)
+        } else {
+            None
+        }
+    }
+
+    // Whether this is a `<meta http-equiv>` start tag, whatever the directive.
+    // https://html.spec.whatwg.org/#pragma-directives
+    pub fn is_pragma_directive(&self) -> bool {
+        !self.is_end_tag
+            && self.tag_name.eq_ignore_ascii_case("meta")
+            && self.has_attribute("http-equiv")
+    }
+
+    pub fn add_class(&mut self, name: &str) {
+        if self.has_class(name) {
+            return;
+        }
+
+        let mut classes = self.classes().collect::<Vec<_>>();
+        classes.push(name);
+        self.set_attribute("class", &classes.join(" "));
+    }
+
+    pub fn remove_class(&mut self, name: &str) {
+        if !self.has_class(name) {
+            return;
+        }
+
+        let classes = self
+            .classes()
+            .filter(|class| *class != name)
+            .collect::<Vec<_>>();
+        self.set_attribute("class", &classes.join(" "));
+    }
+
+    pub fn set_classes(&mut self, classes: &[&str]) {
+        self.set_attribute("class", &classes.join(" "));
+    }
+
+    // Adds the classes of `other` this tag doesn't have yet, after its own.
+    pub fn merge_class_from(&mut self, other: &Tag) {
+        for class in other.classes() {
+            self.add_class(class);
+        }
+    }
+
+    // Copies the attributes of `other` this tag doesn't have. Where both have one, this tag's
+    // value is kept, except that their classes are combined as by `merge_class_from`.
+    pub fn merge_attributes_from(&mut self, other: &Tag) {
+        for attr in &other.attributes {
+            let name = attr.qualified_name();
+            if !self.has_attribute(&name) {
+                self.attributes.push(attr.clone());
+            } else if attr.is_named("class") {
+                self.merge_class_from(other);
+            }
+        }
+    }
+
+    // The `data-*` attributes, with the `data-` prefix removed from their names.
+    // https://html.spec.whatwg.org/#embedding-custom-non-visible-data-with-the-data-*-attributes
+    pub fn data_attributes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes.iter().filter_map(|attr| {
+            let prefix = attr.name.get(..5)?;
+            if prefix.eq_ignore_ascii_case("data-") {
+                Some((&attr.name[5..], attr.value.as_str()))
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn get_data_attribute(&self, key: &str) -> Option<&str> {
+        self.get_attribute(&format!("data-{}", key))
+    }
+
+    // The declarations in the `style` attribute as trimmed `(property, value)` pairs, skipping
+    // any without a `:` or a property name. Semicolons inside quoted strings don't end a
+    // declaration, but this is not a CSS parser: comments, escapes and `!important` are left
+    // in the values as written.
+    pub fn style_properties(&self) -> impl Iterator<Item = (&str, &str)> {
+        let mut rest = self.get_attribute("style").unwrap_or_default();
+
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+
+            let mut quote = None;
+            let end = rest
+                .char_indices()
+                .find(|&(_, c)| match quote {
+                    Some(q) if c == q => {
+                        quote = None;
+                        false
+                    }
+                    Some(_) => false,
+                    None if c == '"' || c == '\'' => {
+                        quote = Some(c);
+                        false
+                    }
+                    None => c == ';',
+                })
+                .map_or(rest.len(), |(index, _)| index);
+
+            let declaration = &rest[..end];
+            rest = rest.get(end + 1..).unwrap_or_default();
+            Some(declaration)
+        })
+        .filter_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            let property = property.trim();
+            (!property.is_empty()).then_some((property, value.trim()))
+        })
+    }
+
+    // The value of the last declaration of `property`, compared ASCII case-insensitively.
+    pub fn get_style_property(&self, property: &str) -> Option<&str> {
+        self.style_properties()
+            .filter(|(name, _)| name.eq_ignore_ascii_case(property))
+            .last()
+            .map(|(_, value)| value)
+    }
 }
 
+// The tag as HTML. Attributes with empty values are written as bare names, which mean the
+// same, and void elements never get a `/`.
 impl std::fmt::Display for Tag {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        if !self.is_end_tag {
-            let _ = write!(f, "<{} ", self.tag_name);
-
-            for attr in &self.attributes {
-                let _ = write!(f, "{} ", attr);
-            }
+        if self.is_end_tag {
+            return write!(f, "</{}>", self.tag_name);
+        }
 
-            if self.self_closing {
-                write!(f, "/>")
+        write!(f, "<{}", self.tag_name)?;
+        for attr in &self.attributes {
+            if attr.value.is_empty() {
+                write!(f, " {}", attr.qualified_name())?;
             } else {
-                write!(f, ">")
+                write!(f, " {}", attr)?;
             }
+        }
+
+        if self.self_closing && !self.is_void_element() {
+            write!(f, "/>")
         } else {
-            write!(f, "</{}>", self.tag_name)
+            write!(f, ">")
         }
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Token {
     DOCTYPE(Doctype),
     Tag(Tag),
     Comment(String),
     Char(char),
+    // A run of consecutive characters. The tokenizer emits these rather than `Char`.
+    Text(String),
+    // The contents of a `<![CDATA[...]]>` section in foreign content.
+    CDATASection(String),
+    // `<?target data?>`, only emitted with `TokenizerOptions::processing_instructions`.
+    ProcessingInstruction { target: String, data: String },
+    // The tokens inside a `<template>`, between its start and end tag. Only emitted with
+    // `TokenizerOptions::template_contents`; nested templates are nested `Template` tokens.
+    Template(Vec<Token>),
     EOF,
 }
 
@@ -205,6 +876,105 @@ impl Token {
     pub fn new_doctype() -> Self {
         Token::DOCTYPE(Doctype::new())
     }
+
+    pub fn is_start_tag(&self) -> bool {
+        matches!(self, Token::Tag(tag) if !tag.is_end_tag)
+    }
+
+    pub fn is_end_tag(&self) -> bool {
+        matches!(self, Token::Tag(tag) if tag.is_end_tag)
+    }
+
+    pub fn is_eof(&self) -> bool {
+        matches!(self, Token::EOF)
+    }
+
+    pub fn is_comment(&self) -> bool {
+        matches!(self, Token::Comment(_))
+    }
+
+    pub fn is_doctype(&self) -> bool {
+        matches!(self, Token::DOCTYPE(_))
+    }
+
+    // Internet Explorer's conditional comments, e.g. `<!--[if IE]>...<![endif]-->`, or either
+    // half of one written as two comments, e.g. `<!--[if !IE]><!-->...<!--<![endif]-->`.
+    // https://learn.microsoft.com/previous-versions/windows/internet-explorer/ie-developer/compatibility/ms537512(v=vs.85)
+    pub fn is_conditional_comment(&self) -> bool {
+        self.comment_data()
+            .is_some_and(|data| data.starts_with("[if ") || data.ends_with("[endif]"))
+    }
+
+    // The HTML a conditional comment hides from other browsers, i.e. what is between
+    // `<!--[if condition]>` and `<![endif]-->`.
+    pub fn conditional_comment_body(&self) -> Option<&str> {
+        let data = self.comment_data()?.strip_prefix("[if ")?;
+        let (_, rest) = data.split_once("]>")?;
+        rest.strip_suffix("<![endif]")
+    }
+
+    pub fn as_tag(&self) -> Option<&Tag> {
+        match self {
+            Token::Tag(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
+    pub fn as_tag_mut(&mut self) -> Option<&mut Tag> {
+        match self {
+            Token::Tag(tag) => Some(tag),
+            _ => None,
+        }
+    }
+
+    pub fn as_comment(&self) -> Option<&str> {
+        match self {
+            Token::Comment(comment) => Some(comment),
+            _ => None,
+        }
+    }
+
+    // The text between `<!--` and `-->`, exactly as written, whitespace included.
+    pub fn comment_data(&self) -> Option<&str> {
+        match self {
+            Token::Comment(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn as_doctype(&self) -> Option<&Doctype> {
+        match self {
+            Token::DOCTYPE(doctype) => Some(doctype),
+            _ => None,
+        }
+    }
+
+    // The name of a start or end tag.
+    pub fn tag_name(&self) -> Option<&str> {
+        self.as_tag().map(|tag| tag.tag_name.as_str())
+    }
+
+    // Transform the token if it is of the matching kind and return any other token unchanged.
+    pub fn map_tag(self, f: impl FnOnce(Tag) -> Tag) -> Token {
+        match self {
+            Token::Tag(tag) => Token::Tag(f(tag)),
+            token => token,
+        }
+    }
+
+    pub fn map_comment(self, f: impl FnOnce(String) -> String) -> Token {
+        match self {
+            Token::Comment(comment) => Token::Comment(f(comment)),
+            token => token,
+        }
+    }
+
+    pub fn map_char(self, f: impl FnOnce(char) -> char) -> Token {
+        match self {
+            Token::Char(c) => Token::Char(f(c)),
+            token => token,
+        }
+    }
 }
 
 impl std::fmt::Display for Token {
@@ -216,7 +986,817 @@ impl std::fmt::Display for Token {
             Token::Tag(tag) => write!(f, "{}", tag),
             Token::Comment(comment) => write!(f, "<!--{}-->", comment),
             Token::Char(c) => write!(f, "{}", c),
+            Token::Text(text) => write!(f, "{}", text),
+            Token::CDATASection(data) => write!(f, "<![CDATA[{}]]>", data),
+            Token::ProcessingInstruction { target, data } if data.is_empty() => {
+                write!(f, "<?{}?>", target)
+            }
+            Token::ProcessingInstruction { target, data } => write!(f, "<?{} {}?>", target, data),
+            Token::Template(content) => content.iter().try_for_each(|token| write!(f, "{}", token)),
             Token::EOF => write!(f, ""),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag_with_attributes(attributes: &[(&str, &str)]) -> Tag {
+        let mut tag = Tag::new_start_tag_with_name("input".to_string());
+
+        for (name, value) in attributes {
+            tag.attributes.push(Attribute {
+                name: name.to_string(),
+                value: value.to_string(),
+                ..Attribute::new()
+            });
+        }
+
+        tag
+    }
+
+    fn assert_kind<'a>(names: impl IntoIterator<Item = &'a &'a str>, is_kind: fn(&Tag) -> bool) {
+        for name in names {
+            for name in [name.to_string(), name.to_ascii_uppercase()] {
+                assert!(
+                    is_kind(&Tag::new_start_tag_with_name(name.clone())),
+                    "{}",
+                    name
+                );
+                assert!(
+                    !is_kind(&Tag::new_end_tag_with_name(name.clone())),
+                    "{}",
+                    name
+                );
+            }
+        }
+
+        for name in ["div", "span", "p", "template"] {
+            assert!(
+                !is_kind(&Tag::new_start_tag_with_name(name.to_string())),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_element_kinds() {
+        assert_kind(&VOID_ELEMENTS, Tag::is_void_element);
+        assert_kind(&RAW_TEXT_ELEMENTS, Tag::is_raw_text_element);
+        assert_kind(&RCDATA_ELEMENTS, Tag::is_rcdata_element);
+        assert_kind(&FORMATTING_ELEMENTS, Tag::is_formatting_element);
+
+        // Every kind the spec defines, including the obsolete void elements.
+        assert_eq!(VOID_ELEMENTS.len(), 18);
+        assert!(VOID_ELEMENTS.contains("keygen") && VOID_ELEMENTS.contains("wbr"));
+        assert_eq!(RAW_TEXT_ELEMENTS.len(), 2);
+        assert_eq!(RCDATA_ELEMENTS.len(), 2);
+
+        let br = Tag::new_start_tag_with_name("br".to_string());
+        assert!(
+            !br.is_raw_text_element() && !br.is_rcdata_element() && !br.is_formatting_element()
+        );
+        let title = Tag::new_start_tag_with_name("Title".to_string());
+        assert!(title.is_rcdata_element() && !title.is_void_element());
+    }
+
+    #[test]
+    fn test_get_attribute() {
+        let tag = tag_with_attributes(&[("type", "text"), ("value", ""), ("disabled", "")]);
+
+        assert_eq!(tag.get_attribute("type"), Some("text"));
+        assert_eq!(tag.get_attribute("TYPE"), Some("text"));
+        assert_eq!(tag.get_attribute("value"), Some(""));
+        assert_eq!(tag.get_attribute("name"), None);
+
+        assert!(tag.has_attribute("disabled"));
+        assert!(tag.has_attribute("Disabled"));
+        assert!(!tag.has_attribute("checked"));
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut tag = Tag::new_start_tag_with_name("div".to_string());
+        tag.rename("section");
+        assert_eq!(tag.tag_name, "section");
+        assert!(!tag.tag_name.is_interned());
+
+        tag.tag_name.intern();
+        tag.rename("article");
+        assert_eq!(tag.tag_name, "article");
+        assert!(tag.tag_name.is_interned());
+    }
+
+    #[test]
+    fn test_map() {
+        let tokens = crate::Tokenizer::new("<b>bold</b> <i>x</i><B class=a>")
+            .map(|token| {
+                token.map_tag(|mut tag| {
+                    if tag.tag_name.eq_ignore_ascii_case("b") {
+                        tag.rename("strong");
+                    }
+                    tag
+                })
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens
+                .iter()
+                .filter_map(Token::tag_name)
+                .collect::<Vec<_>>(),
+            vec!["strong", "strong", "i", "i", "strong"]
+        );
+        assert_eq!(
+            tokens
+                .iter()
+                .rev()
+                .find_map(Token::as_tag)
+                .unwrap()
+                .get_attribute("class"),
+            Some("a")
+        );
+
+        assert_eq!(
+            Token::Comment(" note ".to_string()).map_comment(|comment| comment.trim().to_string()),
+            Token::Comment("note".to_string())
+        );
+        assert_eq!(
+            Token::Char('a').map_char(|c| c.to_ascii_uppercase()),
+            Token::Char('A')
+        );
+        assert_eq!(
+            Token::Char('a').map_comment(|_| String::new()),
+            Token::Char('a')
+        );
+        assert_eq!(Token::EOF.map_tag(|_| Tag::new_start_tag()), Token::EOF);
+    }
+
+    #[test]
+    fn test_boolean_attributes() {
+        let names = [
+            "disabled",
+            "checked",
+            "readonly",
+            "multiple",
+            "required",
+            "selected",
+            "autofocus",
+            "autoplay",
+            "controls",
+            "default",
+            "defer",
+            "formnovalidate",
+            "hidden",
+            "ismap",
+            "loop",
+            "novalidate",
+            "open",
+            "reversed",
+            "scoped",
+            "seamless",
+        ];
+        for name in names {
+            assert!(is_boolean(name), "{}", name);
+            assert!(is_boolean(&name.to_ascii_uppercase()), "{}", name);
+        }
+        for name in ["id", "value", "type", "data-disabled", "hidden-"] {
+            assert!(!is_boolean(name), "{}", name);
+        }
+
+        let mut tag = tag_with_attributes(&[
+            ("type", "checkbox"),
+            ("Checked", ""),
+            ("disabled", "disabled"),
+            ("name", "agree"),
+        ]);
+        tag.attributes.push(Attribute {
+            name: "hidden".to_string(),
+            prefix: "xlink".to_string(),
+            ..Attribute::new()
+        });
+
+        assert_eq!(
+            tag.boolean_attributes()
+                .map(|attr| attr.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Checked", "disabled"]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let attr = Attribute {
+            name: "title".to_string(),
+            value: r#"say "hi" & bye"#.to_string(),
+            ..Attribute::new()
+        };
+        assert_eq!(attr.to_string(), r#"title="say &quot;hi&quot; &amp; bye""#);
+
+        for (html, display) in [
+            (
+                r#"<input type=checkbox checked value="">"#,
+                r#"<input type="checkbox" checked value>"#,
+            ),
+            ("<br/>", "<br>"),
+            ("<path d='M 0 0'/>", r#"<path d="M 0 0"/>"#),
+            (
+                r#"<a href="/?a=1&amp;b=2" title='"x"'>"#,
+                r#"<a href="/?a=1&amp;b=2" title="&quot;x&quot;">"#,
+            ),
+            ("</div>", "</div>"),
+            ("<p >", "<p>"),
+        ] {
+            let tag = match crate::Tokenizer::new(html).next() {
+                Some(Token::Tag(tag)) => tag,
+                token => panic!("expected a tag, got {:?}", token),
+            };
+            let written = tag.to_string();
+            assert_eq!(written, display);
+
+            // The written tag tokenizes back to the same tag, apart from the `/` on void
+            // elements.
+            let mut reparsed = match crate::Tokenizer::new(&written).next() {
+                Some(Token::Tag(tag)) => tag,
+                token => panic!("expected a tag, got {:?}", token),
+            };
+            reparsed.self_closing |= tag.self_closing && tag.is_void_element();
+            assert_eq!(reparsed, tag, "{}", written);
+        }
+    }
+
+    #[test]
+    fn test_namespace_uri() {
+        let foreign_tag = |html: &str| {
+            let mut tokenizer = crate::Tokenizer::new(html);
+            tokenizer.set_in_foreign_content(html.starts_with("<svg>"));
+            tokenizer
+                .filter_map(|token| match token {
+                    Token::Tag(tag) if tag.tag_name == "use" => Some(tag),
+                    _ => None,
+                })
+                .next()
+                .unwrap()
+        };
+        fn uris(tag: &Tag) -> Vec<Option<&str>> {
+            tag.attributes
+                .iter()
+                .map(|attr| attr.namespace_uri(tag))
+                .collect()
+        }
+
+        let tag = foreign_tag(
+            r##"<svg><use xmlns:xlink="http://www.w3.org/1999/xlink" xlink:href="#icon" x="1" xml:lang="en"/></svg>"##,
+        );
+        assert_eq!(
+            uris(&tag),
+            vec![
+                Some(XMLNS_NAMESPACE),
+                Some(XLINK_NAMESPACE),
+                None,
+                Some(XML_NAMESPACE)
+            ]
+        );
+
+        // In foreign content `xlink` is bound even without a declaration.
+        let tag = foreign_tag(r##"<svg><use xlink:href="#icon"/></svg>"##);
+        assert_eq!(uris(&tag), vec![Some(XLINK_NAMESPACE)]);
+
+        // Outside foreign content the prefix stays in the name, and a declaration on the tag
+        // takes precedence over the map.
+        let tag = foreign_tag(r#"<use xmlns="urn:a" xmlns:ex="urn:ex" ex:one="1" other:two="2">"#);
+        let namespaces = HashMap::from([
+            ("ex".to_string(), "urn:ignored".to_string()),
+            ("other".to_string(), "urn:other".to_string()),
+        ]);
+        assert_eq!(
+            tag.attributes
+                .iter()
+                .map(|attr| attr.namespace_uri_with(&tag, &namespaces))
+                .collect::<Vec<_>>(),
+            vec![
+                Some(XMLNS_NAMESPACE),
+                Some(XMLNS_NAMESPACE),
+                Some("urn:ex"),
+                Some("urn:other")
+            ]
+        );
+        assert_eq!(tag.attributes[3].namespace_uri(&tag), None);
+    }
+
+    #[test]
+    fn test_attribute_lookup() {
+        let tag = tag_with_attributes(&[("id", "a"), ("class", "b"), ("id", "c")]);
+
+        assert_eq!(tag.find_attribute("ID"), Some(&tag.attributes[0]));
+        assert_eq!(tag.find_attribute("title"), None);
+
+        let map = tag.attributes_as_map();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["id"], "a");
+        assert_eq!(map["class"], "b");
+        assert_eq!(map.get("ID"), None);
+    }
+
+    #[test]
+    fn test_normalized_value() {
+        let value = |value: &str| Attribute {
+            name: "href".to_string(),
+            value: value.to_string(),
+            ..Attribute::new()
+        };
+
+        assert_eq!(
+            value("/search?q=hello&amp;world").normalized_value(),
+            "/search?q=hello&world"
+        );
+        assert_eq!(value("&lt;&#60;&#x3C;&#X3c").normalized_value(), "<<<<");
+        assert_eq!(
+            value("&#0;&#x110000;&#128;").normalized_value(),
+            "\u{FFFD}\u{FFFD}\u{20AC}"
+        );
+        assert_eq!(
+            value("&notin; &not &notit;").normalized_value(),
+            "\u{2209} \u{AC} &notit;"
+        );
+        assert_eq!(
+            value("?a=1&copy=2&lang&#;&unknown;&").normalized_value(),
+            "?a=1&copy=2&lang&#;&unknown;&"
+        );
+        assert!(matches!(
+            value("/search?q=hello").normalized_value(),
+            Cow::Borrowed("/search?q=hello")
+        ));
+    }
+
+    #[test]
+    fn test_normalized_value_of_tokenized_attribute() {
+        let tokens = crate::Tokenizer::new("<a title='&amp;amp;'>").collect::<Vec<_>>();
+        let attr = &tokens[0].as_tag().unwrap().attributes[0];
+
+        // The tokenizer has already decoded the value once.
+        assert_eq!(attr.value, "&amp;");
+        assert_eq!(attr.normalized_value(), "&");
+    }
+
+    #[test]
+    fn test_attribute_shorthands() {
+        let tag = tag_with_attributes(&[
+            ("id", "q"),
+            ("class", "search"),
+            ("type", "text"),
+            ("name", "query"),
+            ("value", ""),
+        ]);
+
+        assert_eq!(tag.id(), Some("q"));
+        assert_eq!(tag.class(), Some("search"));
+        assert_eq!(tag.type_attr(), Some("text"));
+        assert_eq!(tag.name(), Some("query"));
+        assert_eq!(tag.value_attr(), Some(""));
+        assert_eq!(tag.href(), None);
+        assert_eq!(tag.src(), None);
+        assert_eq!(tag.alt(), None);
+
+        let tag = tag_with_attributes(&[("HREF", "/a"), ("src", "a.png"), ("alt", "A")]);
+        assert_eq!(tag.href(), Some("/a"));
+        assert_eq!(tag.src(), Some("a.png"));
+        assert_eq!(tag.alt(), Some("A"));
+        assert_eq!(tag.id(), None);
+        assert_eq!(tag.class(), None);
+        assert_eq!(tag.type_attr(), None);
+        assert_eq!(tag.name(), None);
+        assert_eq!(tag.value_attr(), None);
+    }
+
+    #[test]
+    fn test_same_name_returns_first() {
+        let mut tag = tag_with_attributes(&[("class", "a"), ("class", "b")]);
+
+        assert_eq!(tag.get_attribute("class"), Some("a"));
+
+        assert!(tag.remove_attribute("class"));
+        assert_eq!(tag.get_attribute("class"), Some("b"));
+    }
+
+    fn doctype(html: &str) -> Doctype {
+        match crate::Tokenizer::new(html).next() {
+            Some(Token::DOCTYPE(doctype)) => doctype,
+            token => panic!("expected a DOCTYPE, got {:?}", token),
+        }
+    }
+
+    #[test]
+    fn test_html5_doctype() {
+        let html5 = doctype("<!DOCTYPE html>");
+        assert!(html5.is_html5());
+        assert!(!html5.is_quirks_mode());
+
+        assert!(doctype("<!DOCTYPE HTML>").is_html5());
+        assert!(!doctype(r#"<!DOCTYPE html SYSTEM "about:legacy-compat">"#).is_html5());
+    }
+
+    #[test]
+    fn test_html4_transitional_doctype() {
+        let with_system_id = doctype(
+            r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01 Transitional//EN" "http://www.w3.org/TR/html4/loose.dtd">"#,
+        );
+        assert!(!with_system_id.is_html5());
+        assert!(!with_system_id.is_quirks_mode());
+
+        let without_system_id =
+            doctype(r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01 Transitional//EN">"#);
+        assert!(without_system_id.is_quirks_mode());
+
+        let html4_0 =
+            doctype(r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.0 Transitional//EN" "x">"#);
+        assert!(html4_0.is_quirks_mode());
+    }
+
+    #[test]
+    fn test_doctype_display() {
+        let html4 = r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01 Transitional//EN" "http://www.w3.org/TR/html4/loose.dtd">"#;
+        assert_eq!(
+            doctype(html4).to_string(),
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD HTML 4.01 Transitional//EN" "http://www.w3.org/TR/html4/loose.dtd">"#
+        );
+
+        for html in [
+            "<!DOCTYPE html>",
+            "<!DOCTYPE svg>",
+            html4,
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD HTML 4.01//EN">"#,
+            r#"<!DOCTYPE html SYSTEM "about:legacy-compat">"#,
+            r#"<!DOCTYPE html PUBLIC '-//"quoted"//EN' 'a"b'>"#,
+            r#"<!DOCTYPE html SYSTEM 'say "hi"'>"#,
+        ] {
+            let original = doctype(html);
+            assert_eq!(doctype(&original.to_string()), original, "{html}");
+        }
+    }
+
+    #[test]
+    fn test_quirks_mode() {
+        let mut forced = doctype("<!DOCTYPE html>");
+        forced.set_quirks_flag_to_on();
+        assert!(forced.is_quirks_mode());
+
+        // Missing name and garbage after it both force quirks.
+        assert!(doctype("<!DOCTYPE>").is_quirks_mode());
+        assert!(doctype("<!DOCTYPE html bogus>").is_quirks_mode());
+        assert!(doctype("<!DOCTYPE svg>").is_quirks_mode());
+        assert!(doctype(r#"<!DOCTYPE html PUBLIC "html">"#).is_quirks_mode());
+        assert!(doctype(
+            r#"<!DOCTYPE html SYSTEM "http://www.IBM.com/data/dtd/v11/ibmxhtml1-transitional.dtd">"#
+        )
+        .is_quirks_mode());
+        assert!(!doctype(
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd">"#
+        )
+        .is_quirks_mode());
+    }
+
+    #[test]
+    fn test_token_predicates() {
+        let start = Token::Tag(Tag::new_start_tag_with_name("p".to_string()));
+        let end = Token::Tag(Tag::new_end_tag_with_name("p".to_string()));
+        let comment = Token::Comment("note".to_string());
+        let doctype = Token::DOCTYPE(Doctype::new_with_name("html".to_string()));
+        let text = Token::Text("hi".to_string());
+
+        assert!(start.is_start_tag() && !start.is_end_tag());
+        assert!(end.is_end_tag() && !end.is_start_tag());
+        assert!(!text.is_start_tag() && !text.is_end_tag());
+
+        assert!(Token::EOF.is_eof());
+        assert!(!text.is_eof());
+
+        assert!(comment.is_comment());
+        assert!(!start.is_comment());
+
+        assert!(doctype.is_doctype());
+        assert!(!comment.is_doctype());
+    }
+
+    #[test]
+    fn test_token_accessors() {
+        let mut start = Token::Tag(Tag::new_start_tag_with_name("a".to_string()));
+        let end = Token::Tag(Tag::new_end_tag_with_name("a".to_string()));
+        let comment = Token::Comment("note".to_string());
+        let doctype = Token::DOCTYPE(Doctype::new_with_name("html".to_string()));
+
+        assert_eq!(start.as_tag().map(|tag| tag.is_end_tag), Some(false));
+        assert_eq!(comment.as_tag(), None);
+
+        if let Some(tag) = start.as_tag_mut() {
+            tag.set_attribute("href", "/");
+        }
+        assert_eq!(
+            start.as_tag().and_then(|tag| tag.get_attribute("href")),
+            Some("/")
+        );
+        assert_eq!(Token::EOF.as_tag_mut(), None);
+
+        assert_eq!(comment.as_comment(), Some("note"));
+        assert_eq!(start.as_comment(), None);
+        assert_eq!(comment.comment_data(), Some("note"));
+        assert_eq!(Token::EOF.comment_data(), None);
+
+        assert_eq!(doctype.as_doctype().map(|d| d.name.as_str()), Some("html"));
+        assert_eq!(Token::EOF.as_doctype(), None);
+
+        assert_eq!(start.tag_name(), Some("a"));
+        assert_eq!(end.tag_name(), Some("a"));
+        assert_eq!(Token::Text("a".to_string()).tag_name(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let html = r#"<!DOCTYPE html><p class="a" hidden>Hi<br/><!-- c --></p>"#;
+        let tokens: Vec<Token> = crate::Tokenizer::new(html).collect();
+
+        let json = serde_json::to_string(&tokens).unwrap();
+        let round_trip: Vec<Token> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(round_trip, tokens);
+        assert_eq!(round_trip.last(), Some(&Token::EOF));
+    }
+
+    #[test]
+    fn test_effective_charset() {
+        let meta = |html: &str| match crate::Tokenizer::new(html).next() {
+            Some(Token::Tag(tag)) => tag,
+            token => panic!("expected a tag, got {:?}", token),
+        };
+
+        let tag = meta(r#"<meta charset=" UTF-8 ">"#);
+        assert_eq!(tag.effective_charset(), Some("UTF-8"));
+        assert!(!tag.is_pragma_directive());
+
+        let tag =
+            meta(r#"<meta http-equiv="Content-Type" content="TEXT/HTML; CHARSET='iso-8859-1'">"#);
+        assert_eq!(tag.effective_charset(), Some("iso-8859-1"));
+        assert!(tag.is_pragma_directive());
+
+        let tag = meta(r#"<meta http-equiv="refresh" content="5; charset=utf-8">"#);
+        assert_eq!(tag.effective_charset(), None);
+        assert!(tag.is_pragma_directive());
+
+        assert_eq!(
+            meta(r#"<meta name="viewport" content="width=device-width">"#).effective_charset(),
+            None
+        );
+        assert_eq!(meta(r#"<link charset="utf-8">"#).effective_charset(), None);
+        assert!(!meta("</meta>").is_pragma_directive());
+    }
+
+    #[test]
+    fn test_sort_attributes() {
+        let mut tag =
+            tag_with_attributes(&[("type", "text"), ("name", "q"), ("id", "1"), ("name", "r")]);
+        let names = |attributes: Vec<&Attribute>| {
+            attributes
+                .into_iter()
+                .map(|attr| format!("{}={}", attr.name, attr.value))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(tag.sorted_attributes()),
+            ["id=1", "name=q", "name=r", "type=text"]
+        );
+        assert_eq!(tag.attributes[0].name, "type");
+
+        tag.sort_attributes_alphabetically();
+        assert_eq!(
+            names(tag.attributes.iter().collect()),
+            names(tag.sorted_attributes())
+        );
+    }
+
+    #[test]
+    fn test_attribute_mutation() {
+        let mut tag = tag_with_attributes(&[("type", "text"), ("required", "")]);
+
+        if let Some(attr) = tag.get_attribute_mut("Type") {
+            attr.value.push_str("area");
+        }
+        assert_eq!(tag.get_attribute("type"), Some("textarea"));
+
+        tag.set_attribute("type", "email");
+        tag.set_attribute("Placeholder", "you@example.com");
+        assert_eq!(tag.attributes.len(), 3);
+        assert_eq!(tag.attributes[0].value, "email");
+        assert_eq!(tag.attributes[2].name, "placeholder");
+
+        assert!(tag.remove_attribute("required"));
+        assert!(!tag.remove_attribute("required"));
+        assert!(!tag.has_attribute("required"));
+
+        tag.clear_attributes();
+        assert!(tag.attributes.is_empty());
+    }
+
+    #[test]
+    fn test_classes() {
+        let mut tag =
+            tag_with_attributes(&[("id", "main"), ("class", " a\tb  a\n"), ("title", "")]);
+
+        assert_eq!(tag.classes().collect::<Vec<_>>(), vec!["a", "b", "a"]);
+        assert!(tag.has_class("b"));
+        assert!(!tag.has_class("A"));
+        assert!(!tag.has_class("a b"));
+
+        tag.add_class("c");
+        tag.add_class("c");
+        assert_eq!(tag.get_attribute("class"), Some("a b a c"));
+
+        tag.remove_class("a");
+        tag.remove_class("a");
+        assert_eq!(tag.get_attribute("class"), Some("b c"));
+
+        tag.set_classes(&["x", "y"]);
+        assert_eq!(tag.classes().collect::<Vec<_>>(), vec!["x", "y"]);
+
+        let names = tag
+            .attributes
+            .iter()
+            .map(|attr| attr.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["id", "class", "title"]);
+    }
+
+    #[test]
+    fn test_data_attributes() {
+        let tag = match crate::Tokenizer::new(
+            r#"<div id="x" data-user-id="42" data-userName="ann" data-empty>"#,
+        )
+        .next()
+        {
+            Some(Token::Tag(tag)) => tag,
+            token => panic!("expected a tag, got {:?}", token),
+        };
+
+        assert_eq!(
+            tag.data_attributes().collect::<Vec<_>>(),
+            vec![("user-id", "42"), ("username", "ann"), ("empty", "")]
+        );
+        assert_eq!(tag.get_data_attribute("user-id"), Some("42"));
+        assert_eq!(tag.get_data_attribute("userName"), Some("ann"));
+        assert_eq!(tag.get_data_attribute("empty"), Some(""));
+        assert_eq!(tag.get_data_attribute("id"), None);
+
+        let tag = tag_with_attributes(&[("data", "x"), ("id", "y")]);
+        assert_eq!(tag.data_attributes().count(), 0);
+    }
+
+    #[test]
+    fn test_style_properties() {
+        let tag = tag_with_attributes(&[("style", " color: red;font-size:12px ; ")]);
+        assert_eq!(
+            tag.style_properties().collect::<Vec<_>>(),
+            vec![("color", "red"), ("font-size", "12px")]
+        );
+        assert_eq!(tag.get_style_property("COLOR"), Some("red"));
+        assert_eq!(tag.get_style_property("margin"), None);
+
+        // Declarations without a value or a name are skipped; an empty value is kept.
+        let tag = tag_with_attributes(&[("style", "color; : red; margin:; width: 1px;;")]);
+        assert_eq!(
+            tag.style_properties().collect::<Vec<_>>(),
+            vec![("margin", ""), ("width", "1px")]
+        );
+
+        // A later declaration wins.
+        let tag = tag_with_attributes(&[("style", "color: red; color: blue")]);
+        assert_eq!(tag.get_style_property("color"), Some("blue"));
+
+        let tag = tag_with_attributes(&[(
+            "style",
+            r#"content: "a;b"; font-family: 'x;y', serif; background: url(a.png)"#,
+        )]);
+        assert_eq!(
+            tag.style_properties().collect::<Vec<_>>(),
+            vec![
+                ("content", r#""a;b""#),
+                ("font-family", "'x;y', serif"),
+                ("background", "url(a.png)")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_style_properties_without_declarations() {
+        assert_eq!(
+            tag_with_attributes(&[("style", "")])
+                .style_properties()
+                .count(),
+            0
+        );
+        assert_eq!(
+            tag_with_attributes(&[("style", " ; ")])
+                .style_properties()
+                .count(),
+            0
+        );
+        assert_eq!(
+            tag_with_attributes(&[("id", "a")])
+                .style_properties()
+                .count(),
+            0
+        );
+        assert_eq!(tag_with_attributes(&[]).get_style_property("color"), None);
+    }
+
+    #[test]
+    fn test_classes_without_attribute() {
+        let mut tag = Tag::new_start_tag_with_name("div".to_string());
+
+        assert_eq!(tag.classes().count(), 0);
+        assert!(!tag.has_class("a"));
+
+        tag.remove_class("a");
+        assert!(!tag.has_attribute("class"));
+
+        tag.add_class("a");
+        assert_eq!(tag.get_attribute("class"), Some("a"));
+    }
+
+    #[test]
+    fn test_merge_classes() {
+        let mut tag = tag_with_attributes(&[("class", "a b")]);
+        tag.merge_class_from(&tag_with_attributes(&[("class", " b c\tc a d ")]));
+        assert_eq!(tag.get_attribute("class"), Some("a b c d"));
+
+        // Nothing is added for an empty or missing class list.
+        let mut tag = tag_with_attributes(&[("id", "x")]);
+        tag.merge_class_from(&tag_with_attributes(&[("class", "  ")]));
+        tag.merge_class_from(&tag_with_attributes(&[]));
+        assert!(!tag.has_attribute("class"));
+
+        let mut tag = tag_with_attributes(&[("class", "")]);
+        tag.merge_class_from(&tag_with_attributes(&[("class", "a a")]));
+        assert_eq!(tag.get_attribute("class"), Some("a"));
+    }
+
+    #[test]
+    fn test_merge_attributes() {
+        let mut tag = tag_with_attributes(&[("id", "own"), ("class", "a"), ("hidden", "")]);
+        tag.merge_attributes_from(&tag_with_attributes(&[
+            ("ID", "other"),
+            ("title", "t"),
+            ("class", "b a"),
+            ("hidden", "hidden"),
+            ("data-x", "1"),
+        ]));
+
+        assert_eq!(
+            tag.attributes
+                .iter()
+                .map(|attr| (attr.name.as_str(), attr.value.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("id", "own"),
+                ("class", "a b"),
+                ("hidden", ""),
+                ("title", "t"),
+                ("data-x", "1"),
+            ]
+        );
+
+        // A class attribute this tag lacks is copied as it is.
+        let mut tag = tag_with_attributes(&[]);
+        tag.merge_attributes_from(&tag_with_attributes(&[("class", "")]));
+        assert_eq!(tag.get_attribute("class"), Some(""));
+    }
+
+    #[test]
+    fn test_conditional_comments() {
+        let comments = |html: &str| {
+            crate::Tokenizer::new(html)
+                .filter(Token::is_comment)
+                .collect::<Vec<_>>()
+        };
+
+        let hidden = comments("<!--[if IE 9]><p>old browser</p><![endif]-->");
+        assert!(hidden[0].is_conditional_comment());
+        assert_eq!(
+            hidden[0].conditional_comment_body(),
+            Some("<p>old browser</p>")
+        );
+
+        let revealed = comments("<!--[if !IE]><!--><p>new browser</p><!--<![endif]-->");
+        assert!(revealed.iter().all(Token::is_conditional_comment));
+        assert_eq!(revealed[0].conditional_comment_body(), None);
+        assert_eq!(revealed[1].conditional_comment_body(), None);
+
+        let plain = comments("<!-- [if IE] --><!--[if IE]-->");
+        assert!(!plain[0].is_conditional_comment());
+        assert!(plain[1].is_conditional_comment());
+        assert_eq!(plain[1].conditional_comment_body(), None);
+        assert!(!Token::Text("[if IE]>".to_string()).is_conditional_comment());
+    }
+}
@@ -0,0 +1,1641 @@
+use crate::document::Document;
+use crate::namespace::Namespace;
+use crate::node::Node;
+use crate::tokenizer::{Doctype, Tag, Token, FORMATTING_ELEMENTS};
+
+// https://html.spec.whatwg.org/#the-insertion-mode
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InsertionMode {
+    Initial,
+    BeforeHtml,
+    BeforeHead,
+    InHead,
+    AfterHead,
+    InBody,
+    Text,
+    AfterBody,
+    AfterAfterBody,
+}
+
+// https://html.spec.whatwg.org/#generate-implied-end-tags
+const IMPLIED_END_TAG_ELEMENTS: [&str; 10] = [
+    "dd", "dt", "li", "optgroup", "option", "p", "rb", "rp", "rt", "rtc",
+];
+
+// https://html.spec.whatwg.org/#has-an-element-in-scope
+const SCOPE_BOUNDARY_ELEMENTS: [&str; 9] = [
+    "applet", "caption", "html", "table", "td", "th", "marquee", "object", "template",
+];
+
+// https://html.spec.whatwg.org/#special
+const SPECIAL_ELEMENTS: [&str; 83] = [
+    "address",
+    "applet",
+    "area",
+    "article",
+    "aside",
+    "base",
+    "basefont",
+    "bgsound",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "caption",
+    "center",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dir",
+    "div",
+    "dl",
+    "dt",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "frame",
+    "frameset",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "iframe",
+    "img",
+    "input",
+    "keygen",
+    "li",
+    "link",
+    "listing",
+    "main",
+    "marquee",
+    "menu",
+    "meta",
+    "nav",
+    "noembed",
+    "noframes",
+    "noscript",
+    "object",
+    "ol",
+    "p",
+    "param",
+    "plaintext",
+    "pre",
+    "script",
+    "search",
+    "section",
+    "select",
+    "source",
+    "style",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "track",
+    "ul",
+    "wbr",
+    "xmp",
+];
+
+// Start tags that close an open `<p>` element in button scope.
+// https://html.spec.whatwg.org/#parsing-main-inbody
+const CLOSES_P_ELEMENTS: [&str; 35] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "center",
+    "details",
+    "dialog",
+    "dir",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "header",
+    "hgroup",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "search",
+    "section",
+    "summary",
+    "ul",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "pre",
+    "listing",
+    "hr",
+    "xmp",
+];
+
+// End tags that close the element with the same name if it is in scope.
+const BLOCK_END_TAG_ELEMENTS: [&str; 30] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "button",
+    "center",
+    "details",
+    "dialog",
+    "dir",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "header",
+    "hgroup",
+    "listing",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "pre",
+    "search",
+    "section",
+    "summary",
+    "ul",
+    "dd",
+    "dt",
+    "li",
+];
+
+// Start tags that end SVG and MathML content, `<font>` aside.
+const FOREIGN_CONTENT_BREAKOUT_ELEMENTS: [&str; 44] = [
+    "b",
+    "big",
+    "blockquote",
+    "body",
+    "br",
+    "center",
+    "code",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "listing",
+    "menu",
+    "meta",
+    "nobr",
+    "ol",
+    "p",
+    "pre",
+    "ruby",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "strike",
+    "sub",
+    "sup",
+    "table",
+    "tt",
+    "u",
+    "ul",
+    "var",
+];
+
+const HEADING_ELEMENTS: [&str; 6] = ["h1", "h2", "h3", "h4", "h5", "h6"];
+
+// Elements that are neither raw text nor RCDATA elements but whose contents the tokenizer
+// still emits as text, so that, like theirs, they are parsed in the "text" insertion mode.
+// https://html.spec.whatwg.org/#generic-raw-text-element-parsing-algorithm
+const OTHER_TEXT_ELEMENTS: [&str; 4] = ["iframe", "noembed", "noframes", "xmp"];
+
+// https://html.spec.whatwg.org/#list-of-active-formatting-elements
+#[derive(Debug, Clone)]
+enum FormattingEntry {
+    // The node index of a formatting element and the start tag it was created from, which is
+    // needed to create it again.
+    Element(usize, Tag),
+    // Pushed for `<applet>`, `<marquee>` and `<object>` so that formatting elements opened
+    // outside them aren't reopened inside.
+    Marker,
+}
+
+// Builds a tree out of tokens, following a subset of the WHATWG tree construction rules.
+// Tables and forms are not handled yet: apart from implied end tags and misnested formatting
+// elements, elements simply nest in the order their tags appear.
+// https://html.spec.whatwg.org/#tree-construction
+pub struct TreeBuilder {
+    current_insertion_mode: InsertionMode,
+    // The mode to go back to when leaving the "text" insertion mode.
+    original_insertion_mode: InsertionMode,
+    // https://html.spec.whatwg.org/#the-stack-of-open-elements
+    // Each element is moved into its parent's children when it is popped, so an element's
+    // parent is always the one below it on the stack.
+    open_elements: Vec<Node>,
+    // The node index of each element in `open_elements`. Nodes have no identity of their own,
+    // so this is how `active_formatting_elements` refers to them.
+    open_element_indices: Vec<usize>,
+    next_node_index: usize,
+    active_formatting_elements: Vec<FormattingEntry>,
+    doctype: Option<Doctype>,
+    html: Option<Node>,
+}
+
+impl TreeBuilder {
+    pub fn new() -> Self {
+        TreeBuilder {
+            current_insertion_mode: InsertionMode::Initial,
+            original_insertion_mode: InsertionMode::Initial,
+            open_elements: Vec::new(),
+            open_element_indices: Vec::new(),
+            next_node_index: 0,
+            active_formatting_elements: Vec::new(),
+            doctype: None,
+            html: None,
+        }
+    }
+
+    pub fn insertion_mode(&self) -> InsertionMode {
+        self.current_insertion_mode
+    }
+
+    // The namespace of the elements inserted next: that of the current node, unless it is one
+    // of the SVG and MathML elements whose contents are HTML again. A tokenizer should be told
+    // whether this is HTML; see `Tokenizer::set_in_foreign_content`.
+    // https://html.spec.whatwg.org/#html-integration-point
+    pub fn current_namespace(&self) -> Namespace {
+        match self.open_elements.last() {
+            Some(node) if !is_html_integration_point(node) => {
+                node.namespace().cloned().unwrap_or_default()
+            }
+            _ => Namespace::Html,
+        }
+    }
+
+    pub fn process(&mut self, token: Token) {
+        let token = match token {
+            Token::Char(c) => Token::Text(c.to_string()),
+            Token::CDATASection(data) => Token::Text(data),
+            // The contents of a template are its children, as if they had not been split out.
+            Token::Template(content) => {
+                content.into_iter().for_each(|token| self.process(token));
+                return;
+            }
+            token => token,
+        };
+
+        match self.current_insertion_mode {
+            InsertionMode::Initial => self.initial(token),
+            InsertionMode::BeforeHtml => self.before_html(token),
+            InsertionMode::BeforeHead => self.before_head(token),
+            InsertionMode::InHead => self.in_head(token),
+            InsertionMode::AfterHead => self.after_head(token),
+            InsertionMode::InBody if self.current_node_is_foreign() => {
+                self.in_foreign_content(token)
+            }
+            InsertionMode::InBody => self.in_body(token),
+            InsertionMode::Text => self.text(token),
+            InsertionMode::AfterBody => self.after_body(token),
+            InsertionMode::AfterAfterBody => self.after_after_body(token),
+        }
+    }
+
+    // Closes every open element and returns the document.
+    pub fn finish(mut self) -> Document {
+        while !self.open_elements.is_empty() {
+            self.pop();
+        }
+
+        Document {
+            doctype: self.doctype,
+            root: self.html,
+        }
+    }
+
+    // https://html.spec.whatwg.org/#the-initial-insertion-mode
+    fn initial(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => {
+                if let Some(rest) = skip_whitespace(&text) {
+                    self.switch_to(InsertionMode::BeforeHtml);
+                    self.process(Token::Text(rest.to_string()));
+                }
+            }
+            // Comments outside the `<html>` element have nowhere to go in a `Document`.
+            Token::Comment(_) => {}
+            Token::DOCTYPE(doctype) => {
+                self.doctype = Some(doctype);
+                self.switch_to(InsertionMode::BeforeHtml);
+            }
+            token => {
+                self.switch_to(InsertionMode::BeforeHtml);
+                self.process(token);
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/#the-before-html-insertion-mode
+    fn before_html(&mut self, token: Token) {
+        match token {
+            Token::DOCTYPE(_) | Token::Comment(_) => {}
+            Token::Text(text) => {
+                if let Some(rest) = skip_whitespace(&text) {
+                    self.insert_html_element(Tag::new_start_tag_with_name("html".to_string()));
+                    self.process(Token::Text(rest.to_string()));
+                }
+            }
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "html" => {
+                self.insert_html_element(tag);
+            }
+            Token::Tag(tag)
+                if tag.is_end_tag
+                    && !matches!(tag.tag_name.as_str(), "head" | "body" | "html" | "br") => {}
+            token => {
+                self.insert_html_element(Tag::new_start_tag_with_name("html".to_string()));
+                self.process(token);
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/#the-before-head-insertion-mode
+    fn before_head(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => {
+                if let Some(rest) = skip_whitespace(&text) {
+                    self.insert_head_element(Tag::new_start_tag_with_name("head".to_string()));
+                    self.process(Token::Text(rest.to_string()));
+                }
+            }
+            Token::Comment(comment) => self.insert_node(Node::Comment(comment)),
+            Token::DOCTYPE(_) => {}
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "html" => {
+                self.in_body(Token::Tag(tag))
+            }
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "head" => {
+                self.insert_head_element(tag);
+            }
+            Token::Tag(tag)
+                if tag.is_end_tag
+                    && !matches!(tag.tag_name.as_str(), "head" | "body" | "html" | "br") => {}
+            token => {
+                self.insert_head_element(Tag::new_start_tag_with_name("head".to_string()));
+                self.process(token);
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/#parsing-main-inhead
+    fn in_head(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => {
+                let rest = self.insert_whitespace(&text);
+                if !rest.is_empty() {
+                    self.pop();
+                    self.switch_to(InsertionMode::AfterHead);
+                    self.process(Token::Text(rest));
+                }
+            }
+            Token::Comment(comment) => self.insert_node(Node::Comment(comment)),
+            Token::DOCTYPE(_) => {}
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "html" => {
+                self.in_body(Token::Tag(tag))
+            }
+            Token::Tag(tag) if !tag.is_end_tag && is_head_content(&tag.tag_name) => {
+                self.insert_element(tag);
+            }
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "head" => {}
+            Token::Tag(tag) if tag.is_end_tag && tag.tag_name == "head" => {
+                self.pop();
+                self.switch_to(InsertionMode::AfterHead);
+            }
+            Token::Tag(tag) if tag.is_end_tag && tag.tag_name == "noscript" => {
+                if self.current_node_is("noscript") {
+                    self.pop();
+                }
+            }
+            Token::Tag(tag)
+                if tag.is_end_tag && !matches!(tag.tag_name.as_str(), "body" | "html" | "br") => {}
+            token => {
+                self.pop();
+                self.switch_to(InsertionMode::AfterHead);
+                self.process(token);
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/#the-after-head-insertion-mode
+    fn after_head(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => {
+                let rest = self.insert_whitespace(&text);
+                if !rest.is_empty() {
+                    self.insert_body_element(Tag::new_start_tag_with_name("body".to_string()));
+                    self.process(Token::Text(rest));
+                }
+            }
+            Token::Comment(comment) => self.insert_node(Node::Comment(comment)),
+            Token::DOCTYPE(_) => {}
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "html" => {
+                self.in_body(Token::Tag(tag))
+            }
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "body" => {
+                self.insert_body_element(tag);
+            }
+            // The head element has already been closed, so these end up in `<html>`.
+            Token::Tag(tag) if !tag.is_end_tag && is_head_content(&tag.tag_name) => {
+                self.insert_element(tag);
+            }
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "head" => {}
+            Token::Tag(tag)
+                if tag.is_end_tag && !matches!(tag.tag_name.as_str(), "body" | "html" | "br") => {}
+            token => {
+                self.insert_body_element(Tag::new_start_tag_with_name("body".to_string()));
+                self.process(token);
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/#parsing-main-inbody
+    fn in_body(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => {
+                let text = text.replace('\0', "");
+                if !text.is_empty() {
+                    self.reconstruct_active_formatting_elements();
+                    self.insert_text(&text);
+                }
+            }
+            Token::Comment(comment) => self.insert_node(Node::Comment(comment)),
+            // HTML has no processing instructions; the spec reads them as bogus comments.
+            token @ Token::ProcessingInstruction { .. } => {
+                let markup = token.to_string();
+                let comment = &markup[1..markup.len() - 1];
+                self.insert_node(Node::Comment(comment.to_string()))
+            }
+            // `process` has already turned character data into `Text` and unpacked templates.
+            Token::DOCTYPE(_)
+            | Token::EOF
+            | Token::Char(_)
+            | Token::CDATASection(_)
+            | Token::Template(_) => {}
+            Token::Tag(tag) if !tag.is_end_tag => match tag.tag_name.as_str() {
+                // Attributes on a misplaced `<html>` or `<body>` are added to the existing
+                // element unless it already has them.
+                "html" => self.merge_attributes(0, tag),
+                "body" => {
+                    if self.open_elements.get(1).and_then(Node::tag_name) == Some("body") {
+                        self.merge_attributes(1, tag);
+                    }
+                }
+                "head" => {}
+                name if is_head_content(name) => self.insert_element(tag),
+                "li" => {
+                    self.close_list_item(&["li"]);
+                    self.insert_element(tag);
+                }
+                "dd" | "dt" => {
+                    self.close_list_item(&["dd", "dt"]);
+                    self.insert_element(tag);
+                }
+                name if CLOSES_P_ELEMENTS.contains(&name) => {
+                    self.close_p_element_in_button_scope();
+
+                    // Headings don't nest.
+                    if HEADING_ELEMENTS.contains(&name)
+                        && self
+                            .open_elements
+                            .last()
+                            .and_then(Node::tag_name)
+                            .is_some_and(|current| HEADING_ELEMENTS.contains(&current))
+                    {
+                        self.pop();
+                    }
+                    self.insert_element(tag);
+                }
+                // A second `<a>` closes the first, even when the first wasn't closed properly.
+                "a" => {
+                    if let Some((_, index)) = self.last_formatting_element("a") {
+                        self.adoption_agency_algorithm("a");
+                        // Only an `<a>` outside the current scope can still be open, and that
+                        // can't be closed here without closing what is in between.
+                        self.remove_formatting_entry(index);
+                    }
+                    self.reconstruct_active_formatting_elements();
+                    self.insert_formatting_element(tag);
+                }
+                "nobr" => {
+                    self.reconstruct_active_formatting_elements();
+                    if self.has_element_in_scope("nobr", &[]) {
+                        self.adoption_agency_algorithm("nobr");
+                        self.reconstruct_active_formatting_elements();
+                    }
+                    self.insert_formatting_element(tag);
+                }
+                _ if tag.is_formatting_element() => {
+                    self.reconstruct_active_formatting_elements();
+                    self.insert_formatting_element(tag);
+                }
+                "applet" | "marquee" | "object" => {
+                    self.reconstruct_active_formatting_elements();
+                    self.insert_element(tag);
+                    self.active_formatting_elements
+                        .push(FormattingEntry::Marker);
+                }
+                // These are inserted as they are; the rest first reopen any formatting elements
+                // that were closed early.
+                "form" | "iframe" | "noembed" | "plaintext" | "table" | "textarea" => {
+                    self.insert_element(tag)
+                }
+                _ => {
+                    self.reconstruct_active_formatting_elements();
+                    self.insert_element(tag);
+                }
+            },
+            Token::Tag(tag) => match tag.tag_name.as_str() {
+                "body" => {
+                    if self.has_open_element("body") {
+                        self.switch_to(InsertionMode::AfterBody);
+                    }
+                }
+                "html" => {
+                    if self.has_open_element("body") {
+                        self.switch_to(InsertionMode::AfterBody);
+                        self.process(Token::Tag(tag));
+                    }
+                }
+                // An unmatched `</p>` inserts an empty paragraph.
+                "p" => {
+                    if !self.has_element_in_scope("p", &["button"]) {
+                        self.insert_element(Tag::new_start_tag_with_name("p".to_string()));
+                    }
+                    self.close_p_element();
+                }
+                "li" => {
+                    if self.has_element_in_scope("li", &["ol", "ul"]) {
+                        self.generate_implied_end_tags(Some("li"));
+                        self.pop_until(&["li"]);
+                    }
+                }
+                name if BLOCK_END_TAG_ELEMENTS.contains(&name) => {
+                    if self.has_element_in_scope(name, &[]) {
+                        self.generate_implied_end_tags(Some(name));
+                        self.pop_until(&[name]);
+                    }
+                }
+                name if HEADING_ELEMENTS.contains(&name) => {
+                    if HEADING_ELEMENTS
+                        .iter()
+                        .any(|heading| self.has_element_in_scope(heading, &[]))
+                    {
+                        self.generate_implied_end_tags(None);
+                        self.pop_until(&HEADING_ELEMENTS);
+                    }
+                }
+                name if FORMATTING_ELEMENTS.contains(&name) => self.adoption_agency_algorithm(name),
+                name @ ("applet" | "marquee" | "object") => {
+                    if self.has_element_in_scope(name, &[]) {
+                        self.generate_implied_end_tags(None);
+                        self.pop_until(&[name]);
+                        self.clear_active_formatting_elements_up_to_last_marker();
+                    }
+                }
+                name => self.close_element(name),
+            },
+        }
+    }
+
+    // https://html.spec.whatwg.org/#parsing-main-inforeign
+    // Start tags and text inside `<foreignObject>` and the other HTML integration points are
+    // handled as in HTML content.
+    fn in_foreign_content(&mut self, token: Token) {
+        let integration_point = self
+            .open_elements
+            .last()
+            .is_some_and(is_html_integration_point);
+
+        let tag = match token {
+            Token::Text(text) if !integration_point => {
+                return self.insert_text(&text.replace('\0', "\u{FFFD}"))
+            }
+            Token::Tag(tag) if tag.is_end_tag || !integration_point => tag,
+            token => return self.in_body(token),
+        };
+
+        if !tag.is_end_tag {
+            // HTML elements that can't appear in SVG or MathML close it.
+            if is_foreign_content_breakout(&tag) {
+                while self.current_node_is_foreign()
+                    && !self
+                        .open_elements
+                        .last()
+                        .is_some_and(is_html_integration_point)
+                {
+                    self.pop();
+                }
+                return self.process(Token::Tag(tag));
+            }
+            return self.insert_element(tag);
+        }
+
+        // An end tag closes the innermost SVG or MathML element with its name regardless of
+        // scope, and is otherwise handled as in HTML content.
+        for index in (0..self.open_elements.len()).rev() {
+            let node = &self.open_elements[index];
+            if node.namespace() == Some(&Namespace::Html) {
+                break;
+            }
+            if node
+                .tag_name()
+                .is_some_and(|name| name.eq_ignore_ascii_case(&tag.tag_name))
+            {
+                while self.open_elements.len() > index {
+                    self.pop();
+                }
+                return;
+            }
+        }
+
+        self.in_body(Token::Tag(tag));
+    }
+
+    // https://html.spec.whatwg.org/#parsing-main-incdata
+    fn text(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => self.insert_text(&text),
+            Token::EOF => {
+                self.pop();
+                self.switch_to(self.original_insertion_mode);
+                self.process(Token::EOF);
+            }
+            Token::Tag(tag) if tag.is_end_tag => {
+                self.pop();
+                self.switch_to(self.original_insertion_mode);
+            }
+            _ => {}
+        }
+    }
+
+    // https://html.spec.whatwg.org/#parsing-main-afterbody
+    fn after_body(&mut self, token: Token) {
+        match token {
+            Token::Text(text) if skip_whitespace(&text).is_none() => {
+                self.in_body(Token::Text(text))
+            }
+            Token::Comment(comment) => {
+                if let Some(Node::Element { children, .. }) = self.open_elements.first_mut() {
+                    children.push(Node::Comment(comment));
+                }
+            }
+            Token::DOCTYPE(_) | Token::EOF => {}
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "html" => {
+                self.in_body(Token::Tag(tag))
+            }
+            Token::Tag(tag) if tag.is_end_tag && tag.tag_name == "html" => {
+                self.switch_to(InsertionMode::AfterAfterBody);
+            }
+            token => {
+                self.switch_to(InsertionMode::InBody);
+                self.process(token);
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/#the-after-after-body-insertion-mode
+    fn after_after_body(&mut self, token: Token) {
+        match token {
+            Token::Comment(_) | Token::EOF => {}
+            Token::Text(text) if skip_whitespace(&text).is_none() => {
+                self.in_body(Token::Text(text))
+            }
+            Token::DOCTYPE(_) => {}
+            Token::Tag(tag) if !tag.is_end_tag && tag.tag_name == "html" => {
+                self.in_body(Token::Tag(tag))
+            }
+            token => {
+                self.switch_to(InsertionMode::InBody);
+                self.process(token);
+            }
+        }
+    }
+
+    fn switch_to(&mut self, mode: InsertionMode) {
+        self.current_insertion_mode = mode;
+    }
+
+    fn insert_html_element(&mut self, tag: Tag) {
+        self.push_open_element(Node::new_element("html", tag.attributes));
+        self.switch_to(InsertionMode::BeforeHead);
+    }
+
+    fn insert_head_element(&mut self, tag: Tag) {
+        self.insert_element(tag);
+        self.switch_to(InsertionMode::InHead);
+    }
+
+    fn insert_body_element(&mut self, tag: Tag) {
+        self.insert_element(tag);
+        self.switch_to(InsertionMode::InBody);
+    }
+
+    // Inserts an element, leaving it open unless it is void or self-closing. The contents of
+    // text elements are parsed in the "text" insertion mode. `<svg>` and `<math>` start SVG
+    // and MathML content, where every element can be self-closing and none holds text only.
+    fn insert_element(&mut self, tag: Tag) {
+        let namespace = match self.current_namespace() {
+            Namespace::Html => Namespace::opened_by(&tag.tag_name).unwrap_or_default(),
+            namespace => namespace,
+        };
+        let html = namespace == Namespace::Html;
+        let closed = tag.self_closing || (html && tag.is_void_element());
+        let text = html
+            && (tag.is_raw_text_element()
+                || tag.is_rcdata_element()
+                || OTHER_TEXT_ELEMENTS.contains(&tag.tag_name.as_str()));
+        let element = Node::new_element_in(&tag.tag_name, tag.attributes, namespace);
+
+        if closed {
+            self.insert_node(element);
+            return;
+        }
+
+        if text {
+            self.original_insertion_mode = self.current_insertion_mode;
+            self.switch_to(InsertionMode::Text);
+        }
+        self.push_open_element(element);
+    }
+
+    // Pushes an element onto the stack of open elements and returns its node index.
+    fn push_open_element(&mut self, element: Node) -> usize {
+        let index = self.new_node_index();
+        self.open_elements.push(element);
+        self.open_element_indices.push(index);
+        index
+    }
+
+    fn new_node_index(&mut self) -> usize {
+        self.next_node_index += 1;
+        self.next_node_index - 1
+    }
+
+    fn insert_formatting_element(&mut self, tag: Tag) {
+        let entry_tag = tag.clone();
+        let open = self.open_elements.len();
+        self.insert_element(tag);
+
+        // A self-closing tag such as `<b/>` isn't left open, so there is nothing to reopen.
+        if self.open_elements.len() > open {
+            let index = self.open_element_indices[open];
+            self.push_active_formatting_element(index, entry_tag);
+        }
+    }
+
+    // https://html.spec.whatwg.org/#push-onto-the-list-of-active-formatting-elements
+    // Of elements with the same name and attributes, only the last three since the last
+    // marker are kept (the "Noah's Ark" clause).
+    fn push_active_formatting_element(&mut self, index: usize, tag: Tag) {
+        let same = |other: &Tag| {
+            other.tag_name == tag.tag_name
+                && other.attributes.len() == tag.attributes.len()
+                && other.attributes.iter().all(|attr| {
+                    tag.attributes
+                        .iter()
+                        .any(|a| a.name == attr.name && a.value == attr.value)
+                })
+        };
+        let earlier = self
+            .entries_after_last_marker()
+            .filter(|&position| {
+                matches!(&self.active_formatting_elements[position],
+                    FormattingEntry::Element(_, other) if same(other))
+            })
+            .collect::<Vec<_>>();
+
+        if earlier.len() >= 3 {
+            self.active_formatting_elements
+                .remove(earlier[earlier.len() - 1]);
+        }
+        self.active_formatting_elements
+            .push(FormattingEntry::Element(index, tag));
+    }
+
+    // Positions in `active_formatting_elements` after the last marker, last first.
+    fn entries_after_last_marker(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.active_formatting_elements.len())
+            .rev()
+            .take_while(|&position| {
+                !matches!(
+                    self.active_formatting_elements[position],
+                    FormattingEntry::Marker
+                )
+            })
+    }
+
+    // The position in `active_formatting_elements` and node index of the last element named
+    // `name` after the last marker.
+    fn last_formatting_element(&self, name: &str) -> Option<(usize, usize)> {
+        self.entries_after_last_marker().find_map(|position| {
+            match &self.active_formatting_elements[position] {
+                FormattingEntry::Element(index, tag) if tag.tag_name == name => {
+                    Some((position, *index))
+                }
+                _ => None,
+            }
+        })
+    }
+
+    fn formatting_entry_position(&self, index: usize) -> Option<usize> {
+        self.active_formatting_elements
+            .iter()
+            .position(|entry| matches!(entry, FormattingEntry::Element(i, _) if *i == index))
+    }
+
+    fn remove_formatting_entry(&mut self, index: usize) {
+        if let Some(position) = self.formatting_entry_position(index) {
+            self.active_formatting_elements.remove(position);
+        }
+    }
+
+    fn stack_position(&self, index: usize) -> Option<usize> {
+        self.open_element_indices.iter().rposition(|&i| i == index)
+    }
+
+    // https://html.spec.whatwg.org/#reconstruct-the-active-formatting-elements
+    // Formatting elements closed early, e.g. the `<b>` in `<p><b>a</p>b`, are created again
+    // for the content that follows.
+    fn reconstruct_active_formatting_elements(&mut self) {
+        // The closed entries since the last marker or the last entry that is still open. The
+        // spec rewinds to the first of them and advances from there, which comes to the same.
+        let mut first = self.active_formatting_elements.len();
+        while first > 0 {
+            match &self.active_formatting_elements[first - 1] {
+                FormattingEntry::Element(index, _) if self.stack_position(*index).is_none() => {
+                    first -= 1
+                }
+                _ => break,
+            }
+        }
+
+        for position in first..self.active_formatting_elements.len() {
+            let FormattingEntry::Element(_, tag) = &self.active_formatting_elements[position]
+            else {
+                continue;
+            };
+            let tag = tag.clone();
+            let index =
+                self.push_open_element(Node::new_element(&tag.tag_name, tag.attributes.clone()));
+            self.active_formatting_elements[position] = FormattingEntry::Element(index, tag);
+        }
+    }
+
+    // https://html.spec.whatwg.org/#clear-the-list-of-active-formatting-elements-up-to-the-last-marker
+    fn clear_active_formatting_elements_up_to_last_marker(&mut self) {
+        while let Some(entry) = self.active_formatting_elements.pop() {
+            if let FormattingEntry::Marker = entry {
+                break;
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/#adoption-agency-algorithm
+    // Handles the end tag of a formatting element that may not be the current node, as in
+    // `<b>1<p>2</b>3`: the elements opened inside the formatting element after a special
+    // element such as `<p>` are moved out of it, into a copy of it placed inside the special
+    // element, giving `<b>1</b><p><b>2</b>3</p>`.
+    fn adoption_agency_algorithm(&mut self, subject: &str) {
+        let current = self.open_element_indices.last().copied();
+        if self.current_node_is(subject)
+            && current.is_some_and(|index| self.formatting_entry_position(index).is_none())
+        {
+            self.pop();
+            return;
+        }
+
+        for _ in 0..8 {
+            let Some((formatting_position, formatting_index)) =
+                self.last_formatting_element(subject)
+            else {
+                return self.close_element(subject);
+            };
+            let FormattingEntry::Element(_, formatting_tag) =
+                self.active_formatting_elements[formatting_position].clone()
+            else {
+                return;
+            };
+            let Some(formatting_element) = self.stack_position(formatting_index) else {
+                self.active_formatting_elements.remove(formatting_position);
+                return;
+            };
+            if !self.is_in_scope(formatting_element) {
+                return;
+            }
+
+            let furthest_block = (formatting_element + 1..self.open_elements.len()).find(|&i| {
+                self.open_elements[i]
+                    .tag_name()
+                    .is_some_and(|name| SPECIAL_ELEMENTS.contains(&name))
+            });
+            let Some(furthest_block) = furthest_block else {
+                while self.open_elements.len() > formatting_element {
+                    self.pop();
+                }
+                self.active_formatting_elements.remove(formatting_position);
+                return;
+            };
+
+            // Where the new formatting element goes in `active_formatting_elements`.
+            let mut bookmark = formatting_position;
+            // The elements between the formatting element and the furthest block that are
+            // still formatting elements are copied, outermost first. The copies nest in each
+            // other and hold the furthest block; the originals are closed where they are.
+            let mut copies = Vec::new();
+            let between =
+                self.open_element_indices[formatting_element + 1..furthest_block].to_vec();
+            for (counter, &index) in between.iter().rev().enumerate() {
+                let Some(position) = self.formatting_entry_position(index) else {
+                    continue;
+                };
+                // Formatting elements more than three elements up from the furthest block
+                // are dropped rather than copied.
+                if counter >= 3 {
+                    self.active_formatting_elements.remove(position);
+                    if position < bookmark {
+                        bookmark -= 1;
+                    }
+                    continue;
+                }
+
+                let FormattingEntry::Element(_, tag) =
+                    self.active_formatting_elements[position].clone()
+                else {
+                    continue;
+                };
+                let copy = self.new_node_index();
+                if copies.is_empty() {
+                    bookmark = position + 1;
+                }
+                let element = Node::new_element(&tag.tag_name, tag.attributes.clone());
+                copies.insert(0, (copy, element));
+                self.active_formatting_elements[position] = FormattingEntry::Element(copy, tag);
+            }
+
+            // Everything from the furthest block on stays open, with a copy of the formatting
+            // element around the furthest block's contents.
+            let mut rest = self.open_elements.split_off(furthest_block);
+            let mut rest_indices = self.open_element_indices.split_off(furthest_block);
+            while self.open_elements.len() > formatting_element {
+                self.pop();
+            }
+            for (index, copy) in copies {
+                self.open_elements.push(copy);
+                self.open_element_indices.push(index);
+            }
+
+            let mut element =
+                Node::new_element(&formatting_tag.tag_name, formatting_tag.attributes.clone());
+            if let (
+                Node::Element { children, .. },
+                Node::Element {
+                    children: block, ..
+                },
+            ) = (&mut element, &mut rest[0])
+            {
+                *children = std::mem::take(block);
+            }
+            let index = self.new_node_index();
+            rest.insert(1, element);
+            rest_indices.insert(1, index);
+            self.open_elements.append(&mut rest);
+            self.open_element_indices.append(&mut rest_indices);
+
+            self.active_formatting_elements
+                .insert(bookmark, FormattingEntry::Element(index, formatting_tag));
+            self.remove_formatting_entry(formatting_index);
+        }
+    }
+
+    // Appends `node` to the current node.
+    fn insert_node(&mut self, node: Node) {
+        if let Some(Node::Element { children, .. }) = self.open_elements.last_mut() {
+            children.push(node);
+        }
+    }
+
+    // https://html.spec.whatwg.org/#insert-a-character
+    fn insert_text(&mut self, text: &str) {
+        if let Some(Node::Element { children, .. }) = self.open_elements.last_mut() {
+            if let Some(Node::Text(last)) = children.last_mut() {
+                last.push_str(text);
+            } else {
+                children.push(Node::Text(text.to_string()));
+            }
+        }
+    }
+
+    // Inserts the leading whitespace of `text` and returns the rest.
+    fn insert_whitespace(&mut self, text: &str) -> String {
+        let rest = skip_whitespace(text).unwrap_or_default();
+        let whitespace = &text[..text.len() - rest.len()];
+
+        if !whitespace.is_empty() {
+            self.insert_text(whitespace);
+        }
+        rest.to_string()
+    }
+
+    // Pops the current node off the stack of open elements and appends it to its parent.
+    fn pop(&mut self) {
+        let Some(node) = self.open_elements.pop() else {
+            return;
+        };
+        self.open_element_indices.pop();
+
+        match self.open_elements.last_mut() {
+            Some(Node::Element { children, .. }) => children.push(node),
+            _ => self.html = Some(node),
+        }
+    }
+
+    // https://html.spec.whatwg.org/#parsing-main-inbody:any-other-end-tag
+    // The end tag is ignored if a special element such as `<div>` is open above the element
+    // it closes.
+    fn close_element(&mut self, name: &str) {
+        for index in (0..self.open_elements.len()).rev() {
+            let Some(tag_name) = self.open_elements[index].tag_name() else {
+                continue;
+            };
+
+            if tag_name == name {
+                self.generate_implied_end_tags(Some(name));
+                while self.open_elements.len() > index {
+                    self.pop();
+                }
+                return;
+            }
+
+            if SPECIAL_ELEMENTS.contains(&tag_name) {
+                return;
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/#generate-implied-end-tags
+    fn generate_implied_end_tags(&mut self, except: Option<&str>) {
+        while let Some(name) = self.open_elements.last().and_then(Node::tag_name) {
+            if Some(name) == except || !IMPLIED_END_TAG_ELEMENTS.contains(&name) {
+                break;
+            }
+            self.pop();
+        }
+    }
+
+    // Pops elements until one named in `names` has been popped.
+    fn pop_until(&mut self, names: &[&str]) {
+        while let Some(node) = self.open_elements.last() {
+            let done = node.tag_name().is_some_and(|name| names.contains(&name));
+            self.pop();
+            if done {
+                break;
+            }
+        }
+    }
+
+    // https://html.spec.whatwg.org/#has-an-element-in-scope
+    // `boundaries` are the elements that limit this particular kind of scope in addition to
+    // the ones every scope has, e.g. `button` for button scope.
+    fn has_element_in_scope(&self, name: &str, boundaries: &[&str]) -> bool {
+        for node in self.open_elements.iter().rev() {
+            let Some(tag_name) = node.tag_name() else {
+                continue;
+            };
+
+            if tag_name == name {
+                return true;
+            }
+            if limits_scope(node) || boundaries.contains(&tag_name) {
+                return false;
+            }
+        }
+
+        false
+    }
+
+    // Whether no element between the one at `position` on the stack of open elements and the
+    // current node limits the scope.
+    fn is_in_scope(&self, position: usize) -> bool {
+        !self.open_elements[position + 1..].iter().any(limits_scope)
+    }
+
+    // https://html.spec.whatwg.org/#close-a-p-element
+    fn close_p_element(&mut self) {
+        self.generate_implied_end_tags(Some("p"));
+        self.pop_until(&["p"]);
+    }
+
+    fn close_p_element_in_button_scope(&mut self) {
+        if self.has_element_in_scope("p", &["button"]) {
+            self.close_p_element();
+        }
+    }
+
+    // The steps run before inserting an `<li>`, `<dd>` or `<dt>`: an open item of the same
+    // kind is closed unless a special element other than `<address>`, `<div>` or `<p>` sits
+    // above it.
+    fn close_list_item(&mut self, names: &[&str]) {
+        for index in (0..self.open_elements.len()).rev() {
+            let Some(tag_name) = self.open_elements[index].tag_name() else {
+                continue;
+            };
+
+            if names.contains(&tag_name) {
+                let name = tag_name.to_string();
+                self.generate_implied_end_tags(Some(&name));
+                self.pop_until(&[&name]);
+                break;
+            }
+
+            if SPECIAL_ELEMENTS.contains(&tag_name) && !matches!(tag_name, "address" | "div" | "p")
+            {
+                break;
+            }
+        }
+
+        self.close_p_element_in_button_scope();
+    }
+
+    fn merge_attributes(&mut self, index: usize, tag: Tag) {
+        if let Some(Node::Element { attributes, .. }) = self.open_elements.get_mut(index) {
+            for attr in tag.attributes {
+                if !attributes.iter().any(|existing| existing.name == attr.name) {
+                    attributes.push(attr);
+                }
+            }
+        }
+    }
+
+    fn current_node_is(&self, name: &str) -> bool {
+        self.open_elements.last().and_then(Node::tag_name) == Some(name)
+    }
+
+    fn current_node_is_foreign(&self) -> bool {
+        self.open_elements
+            .last()
+            .and_then(Node::namespace)
+            .is_some_and(|namespace| *namespace != Namespace::Html)
+    }
+
+    fn has_open_element(&self, name: &str) -> bool {
+        self.open_elements
+            .iter()
+            .any(|node| node.tag_name() == Some(name))
+    }
+}
+
+impl Default for TreeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The elements the "in head" insertion mode accepts.
+fn is_head_content(name: &str) -> bool {
+    matches!(
+        name,
+        "base"
+            | "basefont"
+            | "bgsound"
+            | "link"
+            | "meta"
+            | "title"
+            | "noscript"
+            | "noframes"
+            | "style"
+            | "script"
+            | "template"
+    )
+}
+
+// https://html.spec.whatwg.org/#parsing-main-inforeign:the-stack-of-open-elements
+fn is_foreign_content_breakout(tag: &Tag) -> bool {
+    match tag.tag_name.as_str() {
+        "font" => ["color", "face", "size"]
+            .iter()
+            .any(|name| tag.get_attribute(name).is_some()),
+        name => FOREIGN_CONTENT_BREAKOUT_ELEMENTS.contains(&name),
+    }
+}
+
+// Whether `node` is one of the elements every kind of scope stops at.
+// https://html.spec.whatwg.org/#has-an-element-in-the-specific-scope
+fn limits_scope(node: &Node) -> bool {
+    match (node.namespace(), node.tag_name()) {
+        (Some(Namespace::Html), Some(name)) => SCOPE_BOUNDARY_ELEMENTS.contains(&name),
+        (Some(Namespace::MathML), Some("annotation-xml")) => true,
+        _ => is_html_integration_point(node),
+    }
+}
+
+// https://html.spec.whatwg.org/#html-integration-point
+// The MathML text integration points are counted as well, including for the `<mglyph>` and
+// `<malignmark>` start tags that the spec keeps in MathML.
+fn is_html_integration_point(node: &Node) -> bool {
+    match (node.namespace(), node.tag_name()) {
+        // The tokenizer lowercases tag names, so this is `foreignObject`.
+        (Some(Namespace::Svg), Some(name)) => matches!(name, "foreignobject" | "desc" | "title"),
+        (Some(Namespace::MathML), Some("annotation-xml")) => {
+            node.get_attribute("encoding").is_some_and(|encoding| {
+                encoding.eq_ignore_ascii_case("text/html")
+                    || encoding.eq_ignore_ascii_case("application/xhtml+xml")
+            })
+        }
+        (Some(Namespace::MathML), Some(name)) => {
+            matches!(name, "mi" | "mo" | "mn" | "ms" | "mtext")
+        }
+        _ => false,
+    }
+}
+
+// Returns the text after any leading ASCII whitespace, or `None` if it is all whitespace.
+fn skip_whitespace(text: &str) -> Option<&str> {
+    let rest = text.trim_start_matches(['\t', '\n', '\u{000C}', '\r', ' ']);
+    (!rest.is_empty()).then_some(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tokenizer;
+
+    fn build(html: &str) -> Document {
+        let mut builder = TreeBuilder::new();
+        for token in Tokenizer::new(html) {
+            builder.process(token);
+        }
+        builder.finish()
+    }
+
+    fn body_html(html: &str) -> String {
+        build(html).body().map(Node::inner_html).unwrap_or_default()
+    }
+
+    fn element(tag_name: &str, children: Vec<Node>) -> Node {
+        Node::Element {
+            tag_name: tag_name.to_string(),
+            attributes: Vec::new(),
+            children,
+            namespace: Namespace::Html,
+        }
+    }
+
+    fn text(text: &str) -> Node {
+        Node::Text(text.to_string())
+    }
+
+    #[test]
+    fn test_document() {
+        let document = build(
+            "<!DOCTYPE html><html><head><title>T</title></head><body><p>Hi</p></body></html>",
+        );
+
+        assert_eq!(
+            document.doctype,
+            Some(Doctype::new_with_name("html".to_string()))
+        );
+        assert_eq!(
+            document.head().cloned(),
+            Some(element("head", vec![element("title", vec![text("T")])]))
+        );
+        assert_eq!(
+            document.body().cloned(),
+            Some(element("body", vec![element("p", vec![text("Hi")])]))
+        );
+    }
+
+    #[test]
+    fn test_implied_html_head_and_body() {
+        let document = build("<title>T</title><meta charset=utf-8>\n<p>Hi<br>there");
+
+        let head = document.head().unwrap();
+        assert_eq!(
+            head.children()
+                .iter()
+                .map(|node| node.tag_name())
+                .collect::<Vec<_>>(),
+            vec![Some("title"), Some("meta"), None]
+        );
+        assert_eq!(
+            document.body().cloned(),
+            Some(element(
+                "body",
+                vec![element(
+                    "p",
+                    vec![text("Hi"), element("br", vec![]), text("there")]
+                )]
+            ))
+        );
+        assert_eq!(document.doctype, None);
+    }
+
+    #[test]
+    fn test_insertion_modes() {
+        let mut builder = TreeBuilder::new();
+        let mut modes = Vec::new();
+
+        for token in
+            Tokenizer::new("<!DOCTYPE html><html><head><title>T</title></head><body></body></html>")
+        {
+            builder.process(token);
+            modes.push(builder.insertion_mode());
+        }
+
+        assert_eq!(
+            modes,
+            vec![
+                InsertionMode::BeforeHtml,
+                InsertionMode::BeforeHead,
+                InsertionMode::InHead,
+                InsertionMode::Text,
+                InsertionMode::Text,
+                InsertionMode::InHead,
+                InsertionMode::AfterHead,
+                InsertionMode::InBody,
+                InsertionMode::AfterBody,
+                InsertionMode::AfterAfterBody,
+                InsertionMode::AfterAfterBody,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_implied_end_tags() {
+        let document = build("<p><p>text");
+        assert_eq!(
+            document.body().cloned(),
+            Some(element(
+                "body",
+                vec![element("p", vec![]), element("p", vec![text("text")])]
+            ))
+        );
+
+        let document = build("<p>a<div>b</div><h1>c<h2>d</h2>");
+        assert_eq!(
+            document.body().cloned(),
+            Some(element(
+                "body",
+                vec![
+                    element("p", vec![text("a")]),
+                    element("div", vec![text("b")]),
+                    element("h1", vec![text("c")]),
+                    element("h2", vec![text("d")]),
+                ]
+            ))
+        );
+
+        let document = build("<ul><li>a<li><p>b<li>c");
+        assert_eq!(
+            document.body().cloned(),
+            Some(element(
+                "body",
+                vec![element(
+                    "ul",
+                    vec![
+                        element("li", vec![text("a")]),
+                        element("li", vec![element("p", vec![text("b")])]),
+                        element("li", vec![text("c")]),
+                    ]
+                )]
+            ))
+        );
+
+        let document = build("<dl><dt>d<dd>e<dt>f");
+        assert_eq!(
+            document.body().cloned(),
+            Some(element(
+                "body",
+                vec![element(
+                    "dl",
+                    vec![
+                        element("dt", vec![text("d")]),
+                        element("dd", vec![text("e")]),
+                        element("dt", vec![text("f")]),
+                    ]
+                )]
+            ))
+        );
+
+        // A `</p>` without an open paragraph inserts an empty one.
+        let mut builder = TreeBuilder::new();
+        builder.process(Token::Text("a".to_string()));
+        builder.process(Token::Tag(Tag::new_end_tag_with_name("p".to_string())));
+        let document = builder.finish();
+        assert_eq!(
+            document.body().cloned(),
+            Some(element("body", vec![text("a"), element("p", vec![])]))
+        );
+    }
+
+    #[test]
+    fn test_unmatched_end_tags_are_ignored() {
+        let start = |name: &str| Token::Tag(Tag::new_start_tag_with_name(name.to_string()));
+        let end = |name: &str| Token::Tag(Tag::new_end_tag_with_name(name.to_string()));
+
+        // `<body><div><span>a</div>b</em></body>`
+        let mut builder = TreeBuilder::new();
+        for token in [
+            start("body"),
+            start("div"),
+            start("span"),
+            Token::Text("a".to_string()),
+            end("div"),
+            Token::Text("b".to_string()),
+            end("em"),
+            end("body"),
+            Token::EOF,
+        ] {
+            builder.process(token);
+        }
+        let document = builder.finish();
+
+        assert_eq!(
+            document.body().cloned(),
+            Some(element(
+                "body",
+                vec![
+                    element("div", vec![element("span", vec![text("a")])]),
+                    text("b")
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_misnested_formatting_elements() {
+        // The examples from the spec.
+        // https://html.spec.whatwg.org/#misnested-tags:-b-i-/b-/i
+        assert_eq!(body_html("<b>1<i>2</b>3</i>4"), "<b>1<i>2</i></b><i>3</i>4");
+        // https://html.spec.whatwg.org/#misnested-tags:-b-p-/b-/p
+        assert_eq!(body_html("<b>1<p>2</b>3</p>"), "<b>1</b><p><b>2</b>3</p>");
+
+        assert_eq!(body_html("<b><i></b></i>"), "<b><i></i></b>");
+        assert_eq!(body_html("<a><p>X</a>Y"), "<a></a><p><a>X</a>Y</p>");
+        assert_eq!(
+            body_html("<b><div>x</b>y</div>"),
+            "<b></b><div><b>x</b>y</div>"
+        );
+        // Elements in between that aren't formatting elements stay where they were.
+        assert_eq!(
+            body_html("<b><span><div>x</b>y"),
+            "<b><span></span></b><div><b>x</b>y</div>"
+        );
+        // Formatting elements in between are copied into the new position.
+        assert_eq!(
+            body_html("<a><b><div>x</a>y"),
+            "<a><b></b></a><b><div><a>x</a>y</div></b>"
+        );
+        assert_eq!(
+            body_html("<a href=1><em><div><p>x</a>y</p></div>z"),
+            "<a href=\"1\"><em></em></a><em><div><a href=\"1\"></a><p><a href=\"1\">x</a>y</p></div>z</em>"
+        );
+    }
+
+    #[test]
+    fn test_reconstruct_active_formatting_elements() {
+        assert_eq!(body_html("<p><b>x</p>y"), "<p><b>x</b></p><b>y</b>");
+        assert_eq!(
+            body_html("<p><b class=a><i>x</p><p>y</i>z"),
+            "<p><b class=\"a\"><i>x</i></b></p><p><b class=\"a\"><i>y</i>z</b></p>"
+        );
+        // A new `<a>` closes the last one.
+        assert_eq!(body_html("<a>1<a>2</a>3"), "<a>1</a><a>2</a>3");
+        assert_eq!(body_html("<nobr>1<nobr>2"), "<nobr>1</nobr><nobr>2</nobr>");
+
+        // Only three copies of an element with the same attributes are reopened.
+        assert_eq!(
+            body_html("<p><b><b><b><b>x</p>y"),
+            "<p><b><b><b><b>x</b></b></b></b></p><b><b><b>y</b></b></b>"
+        );
+    }
+
+    #[test]
+    fn test_markers() {
+        // Formatting elements opened outside an `<object>` are not reopened inside it, and
+        // those opened inside it are forgotten once it closes.
+        assert_eq!(
+            body_html("<p><b>x</p><object><i>y</object>z"),
+            "<p><b>x</b></p><b><object><i>y</i></object>z</b>"
+        );
+        assert_eq!(
+            body_html("<object><p><i>x</p>y</object>z"),
+            "<object><p><i>x</i></p><i>y</i></object>z"
+        );
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let document = build(
+            "<p><svg viewBox='0 0 1 1'><g><circle/><title>t</title>\
+             <foreignObject><div>x<svg></svg></div></foreignObject></svg>\
+             <math><mi>y</mi><mrow><mo>+</mo></mrow></math><b>z</b></p>",
+        );
+        let body = document.body().unwrap();
+        let namespace = |name: &str| {
+            body.find_all_by_tag(name)
+                .into_iter()
+                .map(|node| node.namespace().unwrap().clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(namespace("svg"), vec![Namespace::Svg, Namespace::Svg]);
+        assert_eq!(namespace("circle"), vec![Namespace::Svg]);
+        assert_eq!(namespace("title"), vec![Namespace::Svg]);
+        assert_eq!(namespace("foreignobject"), vec![Namespace::Svg]);
+        assert_eq!(namespace("div"), vec![Namespace::Html]);
+        assert_eq!(namespace("mo"), vec![Namespace::MathML]);
+        assert_eq!(namespace("b"), vec![Namespace::Html]);
+
+        // The end tags closed the SVG and MathML elements, so `<b>` is back in the paragraph.
+        let p = &body.children()[0];
+        assert_eq!(
+            p.children()
+                .iter()
+                .filter_map(Node::tag_name)
+                .collect::<Vec<_>>(),
+            vec!["svg", "math", "b"]
+        );
+        assert_eq!(p.children()[0].children()[0].children().len(), 3);
+
+        // HTML elements that can't be in SVG close it.
+        let document = build("<svg><g><p>x</p></g></svg>");
+        assert_eq!(
+            document
+                .body()
+                .unwrap()
+                .children()
+                .iter()
+                .filter_map(Node::tag_name)
+                .collect::<Vec<_>>(),
+            vec!["svg", "p"]
+        );
+    }
+
+    #[test]
+    fn test_current_namespace() {
+        let mut builder = TreeBuilder::new();
+        let mut namespaces = Vec::new();
+        for token in Tokenizer::new("<svg><desc><p></p></desc><g></g></svg><math></math>") {
+            builder.process(token);
+            namespaces.push(builder.current_namespace());
+        }
+
+        assert_eq!(
+            namespaces,
+            vec![
+                Namespace::Svg,
+                Namespace::Html,
+                Namespace::Html,
+                Namespace::Html,
+                Namespace::Svg,
+                Namespace::Svg,
+                Namespace::Svg,
+                Namespace::Html,
+                Namespace::MathML,
+                Namespace::Html,
+                Namespace::Html,
+            ]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use crate::tokenizer::{Doctype, Tag, Token};
+
+// Callbacks for `visit_tokens`. Every method does nothing by default, so implementors only
+// override the ones they care about.
+pub trait Visitor {
+    fn visit_doctype(&mut self, _doctype: &Doctype) {}
+
+    fn visit_start_tag(&mut self, _tag: &Tag) {}
+
+    fn visit_end_tag(&mut self, _tag: &Tag) {}
+
+    fn visit_text(&mut self, _text: &str) {}
+
+    fn visit_comment(&mut self, _comment: &str) {}
+
+    fn visit_processing_instruction(&mut self, _target: &str, _data: &str) {}
+
+    fn visit_eof(&mut self) {}
+}
+
+// Dispatches each token to `visitor`. Adjacent `Char`, `Text` and `CDATASection` tokens are
+// reported as a single `visit_text` call.
+pub fn visit_tokens(tokens: &[Token], visitor: &mut impl Visitor) {
+    let mut text = String::new();
+
+    for token in tokens {
+        match token {
+            Token::Char(c) => {
+                text.push(*c);
+                continue;
+            }
+            Token::Text(content) | Token::CDATASection(content) => {
+                text.push_str(content);
+                continue;
+            }
+            _ => {}
+        }
+
+        if !text.is_empty() {
+            visitor.visit_text(&text);
+            text.clear();
+        }
+
+        match token {
+            Token::DOCTYPE(doctype) => visitor.visit_doctype(doctype),
+            Token::Tag(tag) if tag.is_end_tag => visitor.visit_end_tag(tag),
+            Token::Tag(tag) => visitor.visit_start_tag(tag),
+            Token::Comment(comment) => visitor.visit_comment(comment),
+            Token::ProcessingInstruction { target, data } => {
+                visitor.visit_processing_instruction(target, data)
+            }
+            Token::Template(content) => visit_tokens(content, visitor),
+            Token::EOF => visitor.visit_eof(),
+            Token::Char(_) | Token::Text(_) | Token::CDATASection(_) => {}
+        }
+    }
+
+    if !text.is_empty() {
+        visitor.visit_text(&text);
+    }
+}
+
+// Collects all text, leaving out markup and comments.
+#[derive(Debug, Default)]
+pub struct TextExtractor {
+    pub text: String,
+}
+
+impl Visitor for TextExtractor {
+    fn visit_text(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tokenizer;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+    }
+
+    impl Visitor for Recorder {
+        fn visit_doctype(&mut self, doctype: &Doctype) {
+            self.events.push(format!("doctype {}", doctype.name));
+        }
+
+        fn visit_start_tag(&mut self, tag: &Tag) {
+            self.events.push(format!("start {}", tag.tag_name));
+        }
+
+        fn visit_end_tag(&mut self, tag: &Tag) {
+            self.events.push(format!("end {}", tag.tag_name));
+        }
+
+        fn visit_text(&mut self, text: &str) {
+            self.events.push(format!("text {}", text));
+        }
+
+        fn visit_comment(&mut self, comment: &str) {
+            self.events.push(format!("comment {}", comment));
+        }
+
+        fn visit_eof(&mut self) {
+            self.events.push("eof".to_string());
+        }
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let tokens: Vec<Token> = Tokenizer::new("<!DOCTYPE html><p>Hi<!--x--></p>").collect();
+        let mut recorder = Recorder::default();
+
+        visit_tokens(&tokens, &mut recorder);
+
+        assert_eq!(
+            recorder.events,
+            vec![
+                "doctype html",
+                "start p",
+                "text Hi",
+                "comment x",
+                "end p",
+                "eof"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_adjacent_characters_are_merged() {
+        let tokens = vec![
+            Token::Char('a'),
+            Token::Text("bc".to_string()),
+            Token::Char('d'),
+            Token::Comment("x".to_string()),
+            Token::Char('e'),
+        ];
+        let mut recorder = Recorder::default();
+
+        visit_tokens(&tokens, &mut recorder);
+
+        assert_eq!(recorder.events, vec!["text abcd", "comment x", "text e"]);
+    }
+
+    #[test]
+    fn test_text_extractor() {
+        let tokens: Vec<Token> =
+            Tokenizer::new("<h1>Title</h1><!-- skip --><p>Body &amp; more</p>").collect();
+        let mut extractor = TextExtractor::default();
+
+        visit_tokens(&tokens, &mut extractor);
+
+        assert_eq!(extractor.text, "TitleBody & more");
+    }
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "tokio")]
+
+use futures_core::Stream;
+use html_parser::{AsyncTokenizer, Tokenizer};
+use tokio::io::BufReader;
+
+const HTML: &str =
+    r#"<!DOCTYPE html><p class="intro">Hello &amp; welcome, <b>café</b>!</p><!-- end -->"#;
+
+#[tokio::test]
+async fn test_matches_tokenizer() {
+    let mut tokenizer = AsyncTokenizer::new(BufReader::new(HTML.as_bytes()));
+    let mut tokens = Vec::new();
+    while let Some(token) = tokenizer.next_token().await {
+        tokens.push(token);
+    }
+
+    assert_eq!(tokens, Tokenizer::new(HTML).collect::<Vec<_>>());
+    assert!(tokenizer.error().is_none());
+}
+
+#[tokio::test]
+async fn test_stream() {
+    let stream = AsyncTokenizer::new(BufReader::new(HTML.as_bytes())).into_stream();
+    let mut stream = std::pin::pin!(stream);
+    let mut tokens = Vec::new();
+    while let Some(token) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+        tokens.push(token);
+    }
+
+    assert_eq!(tokens, Tokenizer::new(HTML).collect::<Vec<_>>());
+}
@@ -0,0 +1,175 @@
+use html_parser::{Doctype, Document, Node, Tokenizer};
+
+const PAGE: &str = include_str!("fixtures/page.html");
+
+fn element_names(node: &Node) -> Vec<&str> {
+    node.children().iter().filter_map(Node::tag_name).collect()
+}
+
+fn texts(nodes: Vec<&Node>) -> Vec<String> {
+    nodes
+        .into_iter()
+        .map(|node| node.text_content().trim().to_string())
+        .collect()
+}
+
+#[test]
+fn test_page_structure() {
+    let document = Document::from_str(PAGE);
+
+    assert_eq!(
+        document.doctype,
+        Some(Doctype::new_with_name("html".to_string()))
+    );
+    assert_eq!(document.lang(), Some("en"));
+    assert_eq!(
+        document.title().as_deref(),
+        Some("Release notes \u{2014} Example Project")
+    );
+
+    let root = document.root.as_ref().unwrap();
+    assert_eq!(root.tag_name(), Some("html"));
+    assert_eq!(element_names(root), vec!["head", "body"]);
+
+    let head = document.head().unwrap();
+    assert_eq!(element_names(head), vec!["meta", "meta", "title", "link"]);
+    assert!(head
+        .children()
+        .iter()
+        .any(|node| matches!(node, Node::Comment(comment) if comment.contains("Analytics"))));
+    assert_eq!(
+        head.find_all_by_tag("link")[0].get_attribute("href"),
+        Some("/assets/site.css")
+    );
+
+    let body = document.body().unwrap();
+    assert_eq!(
+        element_names(body),
+        vec!["header", "main", "footer", "script"]
+    );
+
+    let links = body.find_all_by_tag("nav")[0]
+        .find_all_by_tag("a")
+        .into_iter()
+        .filter_map(|link| link.get_attribute("href"))
+        .collect::<Vec<_>>();
+    assert_eq!(links, vec!["/", "/docs/", "/blog/", "/about/"]);
+
+    let main = body.find_by_id("content").unwrap();
+    assert_eq!(main.tag_name(), Some("main"));
+    assert_eq!(
+        element_names(&main.children()[1]),
+        vec!["h1", "p", "p", "pre", "h2", "dl", "h2", "table", "form"]
+    );
+    assert_eq!(
+        texts(main.find_all_by_tag("h2")),
+        vec!["Changes", "Benchmarks"]
+    );
+    assert_eq!(
+        texts(main.find_all_by_tag("dt")),
+        vec!["Faster tokenizing", "Better errors"]
+    );
+    assert_eq!(
+        texts(main.find_all_by_tag("td")),
+        vec![
+            "Small page",
+            "1.2 ms",
+            "0.8 ms",
+            "Large page",
+            "48 ms",
+            "31 ms"
+        ]
+    );
+    assert_eq!(
+        main.find_all_by_tag("code")[0].text_content(),
+        "[dependencies]\nexample = \"2.4\"\n"
+    );
+
+    let form = &main.find_all_by_tag("form")[0];
+    assert_eq!(form.get_attribute("method"), Some("post"));
+    assert_eq!(
+        form.find_by_id("rating")
+            .map(|select| texts(select.find_all_by_tag("option"))),
+        Some(vec!["Yes".to_string(), "No".to_string()])
+    );
+    assert_eq!(
+        texts(form.find_all_by_tag("textarea")),
+        vec!["Tell us more..."]
+    );
+
+    let footer = body.find_all_by_tag("footer")[0];
+    assert_eq!(
+        footer.text_content().trim(),
+        "\u{a9} 2024 Example Project. License"
+    );
+
+    let script = body.find_all_by_tag("script")[0];
+    assert!(script.text_content().contains("console.log(\"loaded\");"));
+}
+
+#[test]
+fn test_from_tokens_matches_from_str() {
+    assert_eq!(
+        Document::from_tokens(Tokenizer::new(PAGE).collect()),
+        Document::from_str(PAGE)
+    );
+}
+
+#[test]
+fn test_serialize_round_trip() {
+    let document = Document::from_str(PAGE);
+    let html = document.serialize();
+
+    assert!(html.starts_with("<!DOCTYPE html><html lang=\"en\"><head>"));
+    assert!(html.contains("<meta charset=\"utf-8\">"));
+    assert!(html.contains("console.log(\"loaded\");"));
+    assert_eq!(Document::from_str(&html), document);
+
+    for html in [
+        "<!DOCTYPE html><html><head></head><body><p>a &amp; b &lt; c</p></body></html>",
+        "<!DOCTYPE html><html><head><style>a > b { content: \"&amp;\" }</style></head>\
+         <body><script>if (a < b && c) {}</script></body></html>",
+        "<!DOCTYPE html><html><head></head><body><input disabled value=\"\">\
+         <img alt=\"&quot;x&quot;\"></body></html>",
+    ] {
+        assert_eq!(Document::from_str(html).serialize(), html);
+    }
+}
+
+#[test]
+fn test_traversals() {
+    let document = Document::from_str(PAGE);
+    let root = document.root.as_ref().unwrap();
+    let nav = root.find_all_by_tag("nav")[0];
+
+    let menu = &nav.element_children().collect::<Vec<_>>();
+    assert_eq!(menu.len(), 1);
+    assert_eq!(
+        menu[0]
+            .element_children()
+            .map(|item| item.text_content())
+            .collect::<Vec<_>>(),
+        vec!["Home", "Documentation", "Blog", "About"]
+    );
+    // The whitespace between the list items is text.
+    assert!(menu[0].children().len() > 4);
+
+    assert_eq!(
+        nav.descendants()
+            .filter_map(Node::tag_name)
+            .collect::<Vec<_>>(),
+        vec!["ul", "li", "a", "li", "a", "li", "a", "li", "a"]
+    );
+    assert_eq!(
+        nav.descendants()
+            .filter(|node| matches!(node, Node::Text(text) if !text.trim().is_empty()))
+            .count(),
+        4
+    );
+    assert_eq!(
+        root.descendants()
+            .filter(|node| node.tag_name() == Some("td"))
+            .count(),
+        root.find_all_by_tag("td").len()
+    );
+}
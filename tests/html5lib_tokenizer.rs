@@ -0,0 +1,271 @@
+use html_parser::{State, Token, Tokenizer, TokenizerOptions};
+use serde_json::{json, Map, Value};
+
+// Tokenizer tests in the html5lib format:
+// https://github.com/html5lib/html5lib-tests/tree/master/tokenizer
+// Files from the html5lib suite can be added here as they are.
+const FIXTURES: [(&str, &str); 5] = [
+    ("cdata.test", include_str!("html5lib/tokenizer/cdata.test")),
+    (
+        "character_references.test",
+        include_str!("html5lib/tokenizer/character_references.test"),
+    ),
+    (
+        "comments_and_doctypes.test",
+        include_str!("html5lib/tokenizer/comments_and_doctypes.test"),
+    ),
+    (
+        "script_data.test",
+        include_str!("html5lib/tokenizer/script_data.test"),
+    ),
+    ("tags.test", include_str!("html5lib/tokenizer/tags.test")),
+];
+
+// Tests the tokenizer is known to fail, by description. A test on this list that starts
+// passing fails the run too, so the list has to be kept up to date.
+const KNOWN_FAILURES: [&str; 7] = [
+    "CDATA section",
+    "Brackets in a CDATA section",
+    "Unterminated CDATA section",
+    "Windows-1252 numeric reference",
+    "Noncharacter numeric reference",
+    "Lowercase doctype",
+    "Double escaped script data",
+];
+
+struct Failure {
+    description: String,
+    report: String,
+}
+
+#[test]
+fn test_html5lib_tokenizer() {
+    let mut failures = Vec::new();
+    let mut count = 0;
+
+    for (file, fixture) in FIXTURES {
+        let fixture: Value = serde_json::from_str(fixture)
+            .unwrap_or_else(|error| panic!("{} is not valid JSON: {}", file, error));
+
+        for test in fixture["tests"].as_array().expect("no tests") {
+            count += 1;
+
+            if let Some(report) = run(test) {
+                failures.push(Failure {
+                    description: test["description"].as_str().unwrap_or_default().to_string(),
+                    report: format!("{}: {}", file, report),
+                });
+            }
+        }
+    }
+
+    let unexpected = failures
+        .iter()
+        .filter(|failure| !KNOWN_FAILURES.contains(&failure.description.as_str()))
+        .map(|failure| failure.report.as_str())
+        .collect::<Vec<_>>();
+    let fixed = KNOWN_FAILURES
+        .iter()
+        .filter(|description| {
+            !failures
+                .iter()
+                .any(|failure| failure.description == **description)
+        })
+        .collect::<Vec<_>>();
+
+    assert!(
+        unexpected.is_empty() && fixed.is_empty(),
+        "{} of {} html5lib tests failed\n\n{}\n\nKnown failures that now pass: {:?}",
+        unexpected.len(),
+        count,
+        unexpected.join("\n\n"),
+        fixed
+    );
+}
+
+// Runs a test in each of its initial states and describes the first mismatch, if any.
+fn run(test: &Value) -> Option<String> {
+    let description = test["description"].as_str().unwrap_or_default();
+    let double_escaped = test["doubleEscaped"].as_bool().unwrap_or(false);
+
+    // Lone surrogates can't be put in a `str`, so those tests can't be run.
+    let input = unescape(test["input"].as_str()?, double_escaped)?;
+    let expected = test["output"]
+        .as_array()?
+        .iter()
+        .map(|token| unescape_token(token, double_escaped))
+        .collect::<Option<Vec<_>>>()?;
+
+    let states = match test["initialStates"].as_array() {
+        Some(states) => states.iter().filter_map(Value::as_str).collect(),
+        None => vec!["Data state"],
+    };
+
+    for name in states {
+        let Some(state) = initial_state(name) else {
+            return Some(format!("{}: unknown initial state {:?}", description, name));
+        };
+
+        let options = TokenizerOptions::new().initial_state(state).build();
+        let mut tokenizer = Tokenizer::with_options(&input, options);
+        if let Some(tag) = test["lastStartTag"].as_str() {
+            tokenizer.set_last_start_tag(tag.to_string());
+        }
+
+        let actual = to_html5lib(tokenizer.collect());
+
+        if actual != expected {
+            return Some(format!(
+                "{} ({})\ninput: {:?}\n{}",
+                description,
+                name,
+                input,
+                diff(&expected, &actual)
+            ));
+        }
+    }
+
+    None
+}
+
+fn initial_state(name: &str) -> Option<State> {
+    match name {
+        "Data state" => Some(State::Data),
+        "PLAINTEXT state" => Some(State::PLAINTEXT),
+        "RCDATA state" => Some(State::RCDATA),
+        "RAWTEXT state" => Some(State::RAWTEXT),
+        "Script data state" => Some(State::ScriptData),
+        "CDATA section state" => Some(State::CDATASection),
+        _ => None,
+    }
+}
+
+// Tokens in the html5lib output format. Runs of text, including CDATA sections, become a
+// single `Character` token.
+fn to_html5lib(tokens: Vec<Token>) -> Vec<Value> {
+    let mut output: Vec<Value> = Vec::new();
+
+    for token in tokens {
+        let text = match token {
+            Token::DOCTYPE(doctype) => {
+                let name = Some(doctype.name).filter(|name| !name.is_empty());
+                output.push(json!([
+                    "DOCTYPE",
+                    name,
+                    doctype.public_id,
+                    doctype.system_id,
+                    !doctype.force_quirks
+                ]));
+                continue;
+            }
+            Token::Tag(tag) if tag.is_end_tag => {
+                output.push(json!(["EndTag", tag.tag_name.as_str()]));
+                continue;
+            }
+            Token::Tag(tag) => {
+                let attributes = tag
+                    .attributes
+                    .iter()
+                    .map(|attr| {
+                        (
+                            attr.qualified_name().into_owned(),
+                            Value::from(attr.value.as_str()),
+                        )
+                    })
+                    .collect::<Map<_, _>>();
+
+                output.push(if tag.self_closing {
+                    json!(["StartTag", tag.tag_name.as_str(), attributes, true])
+                } else {
+                    json!(["StartTag", tag.tag_name.as_str(), attributes])
+                });
+                continue;
+            }
+            Token::Comment(comment) => {
+                output.push(json!(["Comment", comment]));
+                continue;
+            }
+            Token::Char(c) => c.to_string(),
+            Token::Text(text) | Token::CDATASection(text) => text,
+            Token::ProcessingInstruction { .. } => unreachable!("processing instructions are off"),
+            Token::Template(_) => unreachable!("template contents are off"),
+            Token::EOF => continue,
+        };
+
+        match output.last_mut().and_then(Value::as_array_mut) {
+            Some(previous) if previous[0] == "Character" => {
+                let joined = format!("{}{}", previous[1].as_str().unwrap_or_default(), text);
+                previous[1] = Value::from(joined);
+            }
+            _ => output.push(json!(["Character", text])),
+        }
+    }
+
+    output
+}
+
+fn unescape_token(token: &Value, double_escaped: bool) -> Option<Value> {
+    match token {
+        Value::String(text) => unescape(text, double_escaped).map(Value::from),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| unescape_token(value, double_escaped))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| {
+                Some((
+                    unescape(key, double_escaped)?,
+                    unescape_token(value, double_escaped)?,
+                ))
+            })
+            .collect::<Option<Map<_, _>>>()
+            .map(Value::Object),
+        value => Some(value.clone()),
+    }
+}
+
+// Tests marked `doubleEscaped` write characters that JSON can't carry as `\uXXXX` inside the
+// already escaped string.
+fn unescape(text: &str, double_escaped: bool) -> Option<String> {
+    if !double_escaped {
+        return Some(text.to_string());
+    }
+
+    let mut unescaped = String::new();
+    let mut rest = text;
+
+    while let Some(index) = rest.find("\\u") {
+        unescaped.push_str(&rest[..index]);
+        let code = u32::from_str_radix(rest.get(index + 2..index + 6)?, 16).ok()?;
+        unescaped.push(char::from_u32(code)?);
+        rest = &rest[index + 6..];
+    }
+    unescaped.push_str(rest);
+
+    Some(unescaped)
+}
+
+// Lists both token streams, marking the tokens that differ.
+fn diff(expected: &[Value], actual: &[Value]) -> String {
+    let mut lines = Vec::new();
+
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if expected == actual => {
+                lines.push(format!("  {}", expected))
+            }
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    lines.push(format!("- {}", expected));
+                }
+                if let Some(actual) = actual {
+                    lines.push(format!("+ {}", actual));
+                }
+            }
+        }
+    }
+
+    lines.join("\n")
+}
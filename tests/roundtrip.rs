@@ -0,0 +1,185 @@
+use html_parser::{
+    decode_html_text, encode_html_attribute, encode_html_text, serialize, Attribute, Doctype,
+    ReadTokenizer, Tag, Token, Tokenizer,
+};
+use proptest::{collection::btree_map, prelude::*};
+
+// Names that put the tokenizer into another state, void elements (whose end tags the serializer
+// drops) and foreign elements (whose attributes get namespaces) are left out.
+const SPECIAL_NAMES: [&str; 30] = [
+    "area",
+    "base",
+    "basefont",
+    "bgsound",
+    "br",
+    "col",
+    "embed",
+    "frame",
+    "hr",
+    "img",
+    "input",
+    "keygen",
+    "link",
+    "meta",
+    "param",
+    "source",
+    "track",
+    "wbr",
+    "iframe",
+    "math",
+    "noembed",
+    "noframes",
+    "noscript",
+    "plaintext",
+    "script",
+    "style",
+    "svg",
+    "textarea",
+    "title",
+    "xmp",
+];
+
+fn tag_name() -> impl Strategy<Value = String> {
+    "[a-z]{1,8}".prop_filter("special element", |name| {
+        !SPECIAL_NAMES.contains(&name.as_str())
+    })
+}
+
+fn start_tag(self_closing: bool) -> impl Strategy<Value = Tag> {
+    (
+        tag_name(),
+        btree_map("[a-z][a-z-]{0,6}", "[ -~é\n\t]{0,10}", 0..4),
+    )
+        .prop_map(move |(name, attributes)| {
+            let mut tag = Tag::new_start_tag_with_name(name);
+            tag.self_closing = self_closing;
+            tag.attributes = attributes
+                .into_iter()
+                .map(|(name, value)| Attribute {
+                    name,
+                    value,
+                    ..Attribute::new()
+                })
+                .collect();
+            tag
+        })
+}
+
+// https://html.spec.whatwg.org/#comments
+fn comment() -> impl Strategy<Value = Token> {
+    "[a-z0-9 <>!.-]{0,12}"
+        .prop_filter("invalid comment", |data| {
+            !data.starts_with('>')
+                && !data.starts_with("->")
+                && !data.ends_with('-')
+                && !data.ends_with("<!-")
+                && !data.contains("--")
+                && !data.contains("<!--")
+        })
+        .prop_map(Token::Comment)
+}
+
+fn text() -> impl Strategy<Value = Token> {
+    prop_oneof![
+        "[a-zA-Z0-9 <>&;#é\n\t]{1,12}".prop_map(Token::Text),
+        "[a-z <>&é]".prop_map(|c| Token::Char(c.chars().next().unwrap())),
+    ]
+}
+
+fn doctype() -> impl Strategy<Value = Token> {
+    let id = || proptest::option::of("[a-zA-Z0-9 /.:-]{0,12}");
+
+    ("[a-z]{1,8}", id(), id()).prop_map(|(name, public_id, system_id)| {
+        Token::DOCTYPE(Doctype {
+            name,
+            public_id,
+            system_id,
+            ..Doctype::new()
+        })
+    })
+}
+
+// A well-nested run of text, comments and elements.
+fn fragment() -> impl Strategy<Value = Vec<Token>> {
+    let leaf = prop_oneof![text(), comment(), start_tag(true).prop_map(Token::Tag),]
+        .prop_map(|token| vec![token]);
+
+    leaf.prop_recursive(4, 32, 6, |inner| {
+        prop_oneof![
+            (start_tag(false), prop::collection::vec(inner.clone(), 0..6)).prop_map(
+                |(tag, children)| {
+                    let end_tag = Tag::new_end_tag_with_name(tag.tag_name.to_string());
+                    let mut tokens = vec![Token::Tag(tag)];
+                    tokens.extend(children.into_iter().flatten());
+                    tokens.push(Token::Tag(end_tag));
+                    tokens
+                }
+            ),
+            prop::collection::vec(inner, 1..4)
+                .prop_map(|fragments| fragments.into_iter().flatten().collect()),
+        ]
+    })
+}
+
+fn document() -> impl Strategy<Value = Vec<Token>> {
+    (proptest::option::of(doctype()), fragment())
+        .prop_map(|(doctype, fragment)| doctype.into_iter().chain(fragment).collect())
+}
+
+// Joins adjacent characters and text into one text token and drops EOF, since the tokenizer
+// emits runs of text as a single token.
+fn normalize(tokens: Vec<Token>) -> Vec<Token> {
+    let mut normalized: Vec<Token> = Vec::new();
+
+    for token in tokens {
+        let text = match token {
+            Token::Char(c) => c.to_string(),
+            Token::Text(text) => text,
+            Token::EOF => continue,
+            token => {
+                normalized.push(token);
+                continue;
+            }
+        };
+
+        match normalized.last_mut() {
+            Some(Token::Text(previous)) => previous.push_str(&text),
+            _ => normalized.push(Token::Text(text)),
+        }
+    }
+
+    normalized
+}
+
+proptest! {
+    #[test]
+    fn serialized_tokens_tokenize_to_themselves(tokens in document()) {
+        let html = serialize(&tokens);
+
+        prop_assert_eq!(
+            normalize(Tokenizer::new(&html).collect()),
+            normalize(tokens),
+            "{}",
+            html
+        );
+    }
+
+    #[test]
+    fn any_input_ends_with_eof(bytes in prop::collection::vec(any::<u8>(), 0..256)) {
+        let html = String::from_utf8_lossy(&bytes);
+
+        for tokens in [
+            Tokenizer::new(&html).collect::<Vec<_>>(),
+            ReadTokenizer::new(bytes.as_slice()).collect(),
+        ] {
+            prop_assert_eq!(tokens.last(), Some(&Token::EOF));
+            prop_assert_eq!(tokens.iter().filter(|token| token.is_eof()).count(), 1);
+        }
+    }
+
+    #[test]
+    fn encoded_text_decodes_to_itself(s in "[\\x00-\\x7F]*") {
+        prop_assert_eq!(decode_html_text(&encode_html_text(&s)), s.as_str());
+        prop_assert_eq!(decode_html_text(&encode_html_attribute(&s)), s.as_str());
+    }
+}